@@ -1,15 +1,27 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Person<'a> {
     pub name: &'a str,
     pub age: i64,
 }
 
-#[derive(Debug, Clone)]
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, age={})", self.name, self.age)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Company<'a> {
     pub name: &'a str,
     pub employee__count: i64,
 }
 
+impl<'a> std::fmt::Display for Company<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Company(name={}, employee_count={})", self.name, self.employee__count)
+    }
+}
+
 fn process_value(val__param: i64) -> i64 {
     return val__param * 2;
 }
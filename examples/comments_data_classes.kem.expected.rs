@@ -1,12 +1,18 @@
 // Test comment handling in data classes and type annotations
 // Data class with field comments
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Person<'a> {
     pub name: &'a str,  // Person's full name
     pub age: i64,   // Age in years
     pub email: &'a str,  // Contact email
 }
 
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, age={}, email={})", self.name, self.age, self.email)
+    }
+}
+
 // Function with parameter comments
 fn greet<'a>(
     bump: &'a bumpalo::Bump,
@@ -1,10 +1,16 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Person<'a> {
     pub name: &'a str,
     pub age: i64,
     pub email: &'a str,
 }
 
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, age={}, email={})", self.name, self.age, self.email)
+    }
+}
+
 fn greet<'a>(bump: &'a bumpalo::Bump, person: &'a Person<'a>, formal: bool) {
     if formal {
         println!("Good day, {}", person.name);
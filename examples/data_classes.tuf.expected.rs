@@ -1,36 +1,66 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: i64,
     pub y: i64,
 }
 
-#[derive(Debug, Clone)]
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={}, y={})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Person<'a> {
     pub name: &'a str,
     pub age: i64,
 }
 
-#[derive(Debug, Clone)]
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, age={})", self.name, self.age)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Book<'a> {
     pub title: &'a str,
     pub author: &'a Person<'a>,
     pub pages: i64,
 }
 
-#[derive(Debug, Clone)]
+impl<'a> std::fmt::Display for Book<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Book(title={}, author={}, pages={})", self.title, self.author, self.pages)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Address<'a> {
     pub street: &'a str,
     pub city: &'a str,
     pub zip_code: i64,
 }
 
-#[derive(Debug, Clone)]
+impl<'a> std::fmt::Display for Address<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Address(street={}, city={}, zipCode={})", self.street, self.city, self.zip_code)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Company<'a> {
     pub name: &'a str,
     pub address: &'a Address<'a>,
     pub employees: i64,
 }
 
+impl<'a> std::fmt::Display for Company<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Company(name={}, address={}, employees={})", self.name, self.address, self.employees)
+    }
+}
+
 fn main() {
     let p = Point { x: 10, y: 20 };
     let alice = Person { name: "Alice", age: 30 };
@@ -4,6 +4,7 @@
 //! literals, operators, and various expression forms.
 
 use super::Located;
+use crate::types::VeltranoType;
 
 /// Type alias for located expressions
 pub type LocatedExpr = Located<Expr>;
@@ -18,6 +19,10 @@ pub enum Expr {
     MethodCall(MethodCallExpr),
     FieldAccess(FieldAccessExpr),
     Parenthesized(ParenthesizedExpr),
+    Tuple(TupleExpr),
+    Index(IndexExpr),
+    RustCode(RustCodeExpr),
+    FunctionRef(FunctionRefExpr),
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +38,7 @@ pub enum LiteralExpr {
 pub struct UnaryExpr {
     pub operator: UnaryOp,
     pub operand: Box<LocatedExpr>,
+    pub id: usize, // Unique ID for operator resolution tracking
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +46,15 @@ pub enum UnaryOp {
     Minus,
 }
 
+impl UnaryOp {
+    /// Name of the `operator fun` that overloads this operator on data classes
+    pub fn operator_function_name(&self) -> &'static str {
+        match self {
+            UnaryOp::Minus => "unaryMinus",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BinaryExpr {
     pub left: Box<LocatedExpr>,
@@ -47,6 +62,7 @@ pub struct BinaryExpr {
     pub operator: BinaryOp,
     pub comment_after_operator: Option<(String, String)>, // Optional comment after operator
     pub right: Box<LocatedExpr>,
+    pub id: usize, // Unique ID for operator resolution tracking
 }
 
 #[derive(Debug, Clone)]
@@ -66,7 +82,60 @@ pub enum BinaryOp {
     Or,
 }
 
-#[derive(Debug, Clone)]
+impl BinaryOp {
+    /// Source symbol of this operator
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+            BinaryOp::Multiply => "*",
+            BinaryOp::Divide => "/",
+            BinaryOp::Modulo => "%",
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterEqual => ">=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+
+    /// Name of the `operator fun` that overloads this operator on data classes
+    pub fn operator_function_name(&self) -> Option<&'static str> {
+        match self {
+            BinaryOp::Add => Some("plus"),
+            BinaryOp::Subtract => Some("minus"),
+            BinaryOp::Multiply => Some("times"),
+            BinaryOp::Divide => Some("div"),
+            BinaryOp::Modulo => Some("rem"),
+            BinaryOp::Equal | BinaryOp::NotEqual => Some("equals"),
+            BinaryOp::Less | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::GreaterEqual => {
+                Some("compareTo")
+            }
+            BinaryOp::And | BinaryOp::Or => None,
+        }
+    }
+
+    /// Rust `std::ops`/`std::cmp` trait that implements this operator
+    pub fn std_trait_name(&self) -> Option<&'static str> {
+        match self {
+            BinaryOp::Add => Some("Add"),
+            BinaryOp::Subtract => Some("Sub"),
+            BinaryOp::Multiply => Some("Mul"),
+            BinaryOp::Divide => Some("Div"),
+            BinaryOp::Modulo => Some("Rem"),
+            BinaryOp::Equal | BinaryOp::NotEqual => Some("PartialEq"),
+            BinaryOp::Less | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::GreaterEqual => {
+                Some("PartialOrd")
+            }
+            BinaryOp::And | BinaryOp::Or => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ArgumentComment {
     pub before: Option<(String, String)>,
     pub after: Option<(String, String)>,
@@ -95,12 +164,15 @@ pub struct MethodCallExpr {
     pub args: Vec<LocatedExpr>,
     pub inline_comment: Option<(String, String)>, // Optional inline comment after method call
     pub id: usize,                                // Unique ID for type resolution tracking
+    pub named_args: Vec<Argument>,                // Named arguments, as in `p.copy(age = 31)`
+    pub is_multiline: bool,                       // Whether named arguments span multiple lines
 }
 
 #[derive(Debug, Clone)]
 pub struct FieldAccessExpr {
     pub object: Box<LocatedExpr>,
     pub field: String,
+    pub id: usize, // Unique ID for type resolution tracking
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +182,33 @@ pub struct ParenthesizedExpr {
     pub close_paren_comment: Option<CommentSequence>, // Comments before )
 }
 
+#[derive(Debug, Clone)]
+pub struct TupleExpr {
+    pub elements: Vec<LocatedExpr>, // At least two elements
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexExpr {
+    pub object: Box<LocatedExpr>,
+    pub index: Box<LocatedExpr>,
+    pub id: usize, // Unique ID for type resolution tracking
+}
+
+/// Inline Rust expression: `rust<I64>("a.wrapping_add(b)")`
+#[derive(Debug, Clone)]
+pub struct RustCodeExpr {
+    pub code: String,                       // Rust source copied into the output
+    pub result_type: Located<VeltranoType>, // Declared Veltrano type of the expression
+}
+
+/// Reference to a named function: `::add`, or `Person::greet` for a member
+/// function, which takes the receiver as its first parameter
+#[derive(Debug, Clone)]
+pub struct FunctionRefExpr {
+    pub receiver: Option<String>, // Data class of a member function
+    pub name: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct CommentSequence {
     pub comments: Vec<(String, String)>, // (content, whitespace) pairs
@@ -3,9 +3,80 @@
 //! This module contains the top-level Program type that represents
 //! a complete Veltrano source file.
 
-use super::Stmt;
+use super::{FunDeclStmt, Stmt};
+use crate::error::{ErrorKind, SourceLocation, Span, VeltranoError};
 
 #[derive(Debug, Clone)]
 pub struct Program {
     pub statements: Vec<Stmt>,
 }
+
+impl Program {
+    /// Turn a script into a regular program: declarations stay at item level
+    /// and the remaining top-level statements become the body of a generated
+    /// `fun main()`, in source order.
+    ///
+    /// Comments go with the statement that follows them, so a comment above a
+    /// declaration is hoisted along with it.
+    pub fn into_script(self) -> Result<Program, VeltranoError> {
+        let mut items = Vec::new();
+        let mut body = Vec::new();
+        let mut comments = Vec::new();
+
+        for stmt in self.statements {
+            if matches!(stmt, Stmt::Comment(_)) {
+                comments.push(stmt);
+            } else if is_item(&stmt) {
+                items.append(&mut comments);
+                items.push(stmt);
+            } else {
+                body.append(&mut comments);
+                body.push(stmt);
+            }
+        }
+        // Trailing comments stay at the end of the script body
+        body.append(&mut comments);
+
+        if !body.iter().any(|stmt| !matches!(stmt, Stmt::Comment(_))) {
+            items.append(&mut body);
+            return Ok(Program { statements: items });
+        }
+
+        if let Some(main) = items.iter().find_map(|stmt| match stmt {
+            Stmt::FunDecl(fun_decl) if fun_decl.name == "main" => Some(fun_decl),
+            _ => None,
+        }) {
+            return Err(VeltranoError::new(
+                ErrorKind::SyntaxError,
+                "A script with top-level statements cannot also declare 'fun main'",
+            )
+            .with_span(Span::single(main.location.clone()))
+            .with_help("Move the statements into 'main', or remove 'main'"));
+        }
+
+        items.push(Stmt::FunDecl(FunDeclStmt {
+            name: "main".to_string(),
+            params: Vec::new(),
+            return_type: None,
+            body: Box::new(Stmt::Block(body)),
+            annotations: Vec::new(),
+            lifetime_params: Vec::new(),
+            is_async: false,
+            location: SourceLocation::new(1, 1),
+        }));
+        Ok(Program { statements: items })
+    }
+}
+
+/// Whether a top-level statement is a declaration that Rust expects at item level
+fn is_item(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::FunDecl(_)
+        | Stmt::DataClass(_)
+        | Stmt::Import(_)
+        | Stmt::ExternFun(_)
+        | Stmt::RustBlock(_) => true,
+        Stmt::Annotated(annotated) => is_item(&annotated.stmt),
+        _ => false,
+    }
+}
@@ -24,6 +24,8 @@ impl AstQuery {
             Expr::Unary(u) => Self::contains_calls(&u.operand),
             Expr::FieldAccess(f) => Self::contains_calls(&f.object),
             Expr::Parenthesized(p) => Self::contains_calls(&p.expr),
+            Expr::Tuple(t) => t.elements.iter().any(Self::contains_calls),
+            Expr::Index(i) => Self::contains_calls(&i.object) || Self::contains_calls(&i.index),
             _ => false,
         }
     }
@@ -71,6 +73,15 @@ impl AstQuery {
                 for expr in &m.args {
                     Self::collect_identifiers_impl(expr, acc);
                 }
+                for arg in &m.named_args {
+                    match arg {
+                        Argument::Named(_, expr, _) => Self::collect_identifiers_impl(expr, acc),
+                        Argument::Shorthand(field, _) => {
+                            acc.insert(field.clone());
+                        }
+                        _ => {}
+                    }
+                }
             }
             Expr::FieldAccess(f) => {
                 Self::collect_identifiers_impl(&f.object, acc);
@@ -78,7 +89,16 @@ impl AstQuery {
             Expr::Parenthesized(p) => {
                 Self::collect_identifiers_impl(&p.expr, acc);
             }
-            Expr::Literal(_) => {}
+            Expr::Tuple(t) => {
+                for element in &t.elements {
+                    Self::collect_identifiers_impl(element, acc);
+                }
+            }
+            Expr::Index(i) => {
+                Self::collect_identifiers_impl(&i.object, acc);
+                Self::collect_identifiers_impl(&i.index, acc);
+            }
+            Expr::Literal(_) | Expr::RustCode(_) | Expr::FunctionRef(_) => {}
         }
     }
 
@@ -117,6 +137,10 @@ impl AstQuery {
                 // Check nested expressions
                 Self::uses_bump_allocation(&method_call.object)
                     || method_call.args.iter().any(Self::uses_bump_allocation)
+                    || method_call.named_args.iter().any(|arg| match arg {
+                        Argument::Named(_, expr, _) => Self::uses_bump_allocation(expr),
+                        _ => false,
+                    })
             }
             Expr::Binary(b) => {
                 Self::uses_bump_allocation(&b.left) || Self::uses_bump_allocation(&b.right)
@@ -133,7 +157,13 @@ impl AstQuery {
             }
             Expr::FieldAccess(f) => Self::uses_bump_allocation(&f.object),
             Expr::Parenthesized(p) => Self::uses_bump_allocation(&p.expr),
-            Expr::Literal(_) | Expr::Identifier(_) => false,
+            Expr::Tuple(t) => t.elements.iter().any(Self::uses_bump_allocation),
+            Expr::Index(i) => {
+                Self::uses_bump_allocation(&i.object) || Self::uses_bump_allocation(&i.index)
+            }
+            Expr::Literal(_) | Expr::Identifier(_) | Expr::RustCode(_) | Expr::FunctionRef(_) => {
+                false
+            }
         }
     }
 
@@ -147,6 +177,11 @@ impl AstQuery {
                 .initializer
                 .as_ref()
                 .map_or(false, Self::uses_bump_allocation),
+            Stmt::Destructure(destructure) => Self::uses_bump_allocation(&destructure.initializer),
+            Stmt::Assign(assign) => {
+                Self::uses_bump_allocation(&assign.target)
+                    || Self::uses_bump_allocation(&assign.value)
+            }
             Stmt::If(if_stmt) => {
                 Self::uses_bump_allocation(&if_stmt.condition)
                     || Self::stmt_uses_bump_allocation(&if_stmt.then_branch)
@@ -160,8 +195,16 @@ impl AstQuery {
                     || Self::stmt_uses_bump_allocation(&while_stmt.body)
             }
             Stmt::Return(expr) => expr.as_ref().map_or(false, Self::uses_bump_allocation),
-            Stmt::Block(statements) => statements.iter().any(Self::stmt_uses_bump_allocation),
-            Stmt::FunDecl(_) | Stmt::Comment(_) | Stmt::Import(_) | Stmt::DataClass(_) => false,
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
+                statements.iter().any(Self::stmt_uses_bump_allocation)
+            }
+            Stmt::Annotated(annotated) => Self::stmt_uses_bump_allocation(&annotated.stmt),
+            Stmt::FunDecl(_)
+            | Stmt::Comment(_)
+            | Stmt::Import(_)
+            | Stmt::DataClass(_)
+            | Stmt::RustBlock(_)
+            | Stmt::ExternFun(_) => false,
         }
     }
 
@@ -186,11 +229,12 @@ impl AstQuery {
     fn collect_var_decls<'a>(stmt: &'a Stmt, acc: &mut Vec<&'a VarDeclStmt>) {
         match stmt {
             Stmt::VarDecl(var_decl) => acc.push(var_decl),
-            Stmt::Block(statements) => {
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
                 for s in statements {
                     Self::collect_var_decls(s, acc);
                 }
             }
+            Stmt::Annotated(annotated) => Self::collect_var_decls(&annotated.stmt, acc),
             Stmt::If(if_stmt) => {
                 Self::collect_var_decls(&if_stmt.then_branch, acc);
                 if let Some(else_branch) = &if_stmt.else_branch {
@@ -214,11 +258,12 @@ impl AstQuery {
     fn collect_function_decls<'a>(stmt: &'a Stmt, acc: &mut Vec<&'a FunDeclStmt>) {
         match stmt {
             Stmt::FunDecl(fun_decl) => acc.push(fun_decl),
-            Stmt::Block(statements) => {
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
                 for s in statements {
                     Self::collect_function_decls(s, acc);
                 }
             }
+            Stmt::Annotated(annotated) => Self::collect_function_decls(&annotated.stmt, acc),
             Stmt::If(if_stmt) => {
                 Self::collect_function_decls(&if_stmt.then_branch, acc);
                 if let Some(else_branch) = &if_stmt.else_branch {
@@ -251,6 +296,13 @@ impl AstQuery {
                     acc.extend(Self::collect_identifiers(init));
                 }
             }
+            Stmt::Destructure(destructure) => {
+                acc.extend(Self::collect_identifiers(&destructure.initializer));
+            }
+            Stmt::Assign(assign) => {
+                acc.extend(Self::collect_identifiers(&assign.target));
+                acc.extend(Self::collect_identifiers(&assign.value));
+            }
             Stmt::Return(Some(expr)) => {
                 acc.extend(Self::collect_identifiers(expr));
             }
@@ -265,11 +317,12 @@ impl AstQuery {
                 acc.extend(Self::collect_identifiers(&while_stmt.condition));
                 Self::collect_stmt_variable_refs(&while_stmt.body, acc);
             }
-            Stmt::Block(statements) => {
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
                 for s in statements {
                     Self::collect_stmt_variable_refs(s, acc);
                 }
             }
+            Stmt::Annotated(annotated) => Self::collect_stmt_variable_refs(&annotated.stmt, acc),
             Stmt::FunDecl(fun_decl) => {
                 Self::collect_stmt_variable_refs(&fun_decl.body, acc);
             }
@@ -322,10 +375,17 @@ fn expr_children(expr: &LocatedExpr) -> Vec<&LocatedExpr> {
         Expr::MethodCall(m) => {
             let mut children = vec![&*m.object];
             children.extend(m.args.iter());
+            for arg in &m.named_args {
+                if let Argument::Named(_, expr, _) = arg {
+                    children.push(expr);
+                }
+            }
             children
         }
         Expr::FieldAccess(f) => vec![&f.object],
         Expr::Parenthesized(p) => vec![&p.expr],
-        Expr::Literal(_) | Expr::Identifier(_) => vec![],
+        Expr::Tuple(t) => t.elements.iter().collect(),
+        Expr::Index(i) => vec![&i.object, &i.index],
+        Expr::Literal(_) | Expr::Identifier(_) | Expr::RustCode(_) | Expr::FunctionRef(_) => vec![],
     }
 }
@@ -15,9 +15,15 @@ pub enum Stmt {
     While(WhileStmt),
     Return(Option<LocatedExpr>),
     Block(Vec<Stmt>),
-    Comment(CommentStmt),     // All comments (standalone and inline)
-    Import(ImportStmt),       // Import statement
-    DataClass(DataClassStmt), // Data class declaration
+    Unsafe(Vec<Stmt>),            // `unsafe { ... }` block of statements
+    Comment(CommentStmt),         // All comments (standalone and inline)
+    Import(ImportStmt),           // Import statement
+    DataClass(DataClassStmt),     // Data class declaration
+    Destructure(DestructureStmt), // Destructuring declaration
+    Assign(AssignStmt),           // Assignment: `target.deref() = value`
+    RustBlock(String),            // Raw Rust code from a `rust { ... }` block
+    ExternFun(ExternFunStmt),     // Foreign function: `extern "C" fun`
+    Annotated(AnnotatedStmt),     // Statement with annotations: `@Cfg(test)`
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -41,16 +47,45 @@ pub struct VarDeclStmt {
     pub initializer: Option<LocatedExpr>,
 }
 
+/// Destructuring declaration: `val (a, b) = expr`
+#[derive(Debug, Clone)]
+pub struct DestructureStmt {
+    pub names: Vec<String>, // Component names in declaration order (`_` skips a component)
+    pub initializer: LocatedExpr,
+    pub id: usize, // Unique ID for type resolution tracking
+}
+
+/// Assignment statement: `target = value`
+#[derive(Debug, Clone)]
+pub struct AssignStmt {
+    pub target: LocatedExpr, // Only `.deref()` of a MutRef is a valid target
+    pub value: LocatedExpr,
+}
+
 #[derive(Debug, Clone)]
 pub struct FunDeclStmt {
     pub name: String,
     pub params: Vec<Parameter>,
     pub return_type: Option<Located<VeltranoType>>,
     pub body: Box<Stmt>,
-    pub has_hidden_bump: bool, // Whether this function should receive a hidden bump parameter
+    pub annotations: Vec<Annotation>,
+    pub lifetime_params: Vec<String>, // Declared lifetime labels: `fun<@a, @b>`
+    pub is_async: bool,               // Declared with `suspend fun` (or `async fun`)
+    pub location: crate::error::SourceLocation,
 }
 
 impl FunDeclStmt {
+    /// Whether this is a `@Test` function
+    pub fn is_test(&self) -> bool {
+        self.annotations.iter().any(|a| a.name == "Test")
+    }
+
+    /// Whether this function is called by Rust itself (`main` or a `@Test`
+    /// function), so it creates its own bump allocator instead of receiving one
+    pub fn is_entry_point(&self) -> bool {
+        self.name == "main" || self.is_test()
+    }
+
     /// Analyzes if this function actually uses bump allocation (not just reference types)
     pub fn uses_bump_allocation(
         &self,
@@ -58,7 +93,7 @@ impl FunDeclStmt {
     ) -> bool {
         use crate::ast::query::AstQuery;
 
-        if self.name == "main" {
+        if self.is_entry_point() {
             return false;
         }
 
@@ -70,13 +105,20 @@ impl FunDeclStmt {
         // Check if we call any functions that use bump
         let mut uses_bump = false;
         let _ = self.body.walk_expressions(&mut |expr| {
-            if let Expr::Call(call) = &expr.node {
-                if let Expr::Identifier(name) = &call.callee.node {
-                    if functions_with_bump.contains(name) {
-                        uses_bump = true;
-                        return Err(()); // Early exit
-                    }
+            let callee = match &expr.node {
+                Expr::Call(call) => match &call.callee.node {
+                    Expr::Identifier(name) => Some(name),
+                    _ => None,
+                },
+                // References to such functions pass the bump along
+                Expr::FunctionRef(function_ref) if function_ref.receiver.is_none() => {
+                    Some(&function_ref.name)
                 }
+                _ => None,
+            };
+            if callee.is_some_and(|name| functions_with_bump.contains(name)) {
+                uses_bump = true;
+                return Err(()); // Early exit
             }
             Ok::<(), ()>(())
         });
@@ -85,11 +127,13 @@ impl FunDeclStmt {
     }
 
     /// Analyzes if this function needs lifetime parameters (for bump allocation or reference handling)
+    /// `value_classes` are the Copy data classes, which are passed by value
     pub fn needs_lifetime_params(
         &self,
         functions_with_bump: &std::collections::HashSet<String>,
+        value_classes: &std::collections::HashSet<String>,
     ) -> bool {
-        if self.name == "main" {
+        if self.is_entry_point() {
             return false;
         }
 
@@ -99,7 +143,7 @@ impl FunDeclStmt {
         }
 
         // Check if function has reference types in parameters or return type
-        if self.has_reference_types() {
+        if self.has_reference_types(value_classes) {
             return true;
         }
 
@@ -107,17 +151,17 @@ impl FunDeclStmt {
     }
 
     /// Checks if this function has reference types in its signature
-    fn has_reference_types(&self) -> bool {
+    fn has_reference_types(&self, value_classes: &std::collections::HashSet<String>) -> bool {
         // Check parameters for reference types
         for param in &self.params {
-            if self.type_needs_lifetime(&param.param_type.node) {
+            if Self::type_needs_lifetime(&param.param_type.node, value_classes) {
                 return true;
             }
         }
 
         // Check return type for reference types
         if let Some(return_type) = &self.return_type {
-            if self.type_needs_lifetime(&return_type.node) {
+            if Self::type_needs_lifetime(&return_type.node, value_classes) {
                 return true;
             }
         }
@@ -126,24 +170,32 @@ impl FunDeclStmt {
     }
 
     /// Checks if a type needs lifetime parameters
-    fn type_needs_lifetime(&self, type_: &VeltranoType) -> bool {
+    fn type_needs_lifetime(
+        type_: &VeltranoType,
+        value_classes: &std::collections::HashSet<String>,
+    ) -> bool {
         use crate::types::TypeConstructor;
 
         match &type_.constructor {
-            TypeConstructor::Str | TypeConstructor::String => true,
-            TypeConstructor::Custom(_) => true, // Custom types might have lifetimes
+            TypeConstructor::Str | TypeConstructor::String | TypeConstructor::Dyn(_) => true,
+            // Custom types might have lifetimes, unless they are Copy data classes
+            TypeConstructor::Custom(name) => !value_classes.contains(name),
             TypeConstructor::MutRef | TypeConstructor::Ref => {
                 // Check the inner type if it has args
                 if let Some(inner) = type_.inner() {
-                    self.type_needs_lifetime(inner)
+                    Self::type_needs_lifetime(inner, value_classes)
                 } else {
                     false
                 }
             }
-            TypeConstructor::Box => {
+            TypeConstructor::Box
+            | TypeConstructor::Rc
+            | TypeConstructor::Arc
+            | TypeConstructor::RefCell
+            | TypeConstructor::Cell => {
                 // Check the inner type if it has args
                 if let Some(inner) = type_.inner() {
-                    self.type_needs_lifetime(inner)
+                    Self::type_needs_lifetime(inner, value_classes)
                 } else {
                     false
                 }
@@ -159,7 +211,10 @@ impl FunDeclStmt {
             | TypeConstructor::Unit
             | TypeConstructor::Nothing => false,
             // For other constructors, conservatively assume they might need lifetimes
-            _ => type_.args.iter().any(|arg| self.type_needs_lifetime(arg)),
+            _ => type_
+                .args
+                .iter()
+                .any(|arg| Self::type_needs_lifetime(arg, value_classes)),
         }
     }
 }
@@ -168,6 +223,7 @@ impl FunDeclStmt {
 pub struct Parameter {
     pub name: String,
     pub param_type: Located<VeltranoType>,
+    pub default_value: Option<LocatedExpr>, // Filled in at call sites that omit the argument
     pub inline_comment: Option<(String, String)>, // Optional inline comment after parameter
 }
 
@@ -184,6 +240,23 @@ pub struct WhileStmt {
     pub body: Box<Stmt>,
 }
 
+/// A foreign function declared with `extern "ABI" fun name(params): Type`
+#[derive(Debug, Clone)]
+pub struct ExternFunStmt {
+    pub abi: String,
+    pub name: String,
+    pub params: Vec<Parameter>,
+    pub return_type: Option<Located<VeltranoType>>,
+    pub location: crate::error::SourceLocation,
+}
+
+/// A statement with annotations; functions and data classes keep their own
+#[derive(Debug, Clone)]
+pub struct AnnotatedStmt {
+    pub annotations: Vec<Annotation>,
+    pub stmt: Box<Stmt>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ImportStmt {
     pub type_name: String,
@@ -196,11 +269,41 @@ pub struct ImportStmt {
 pub struct DataClassStmt {
     pub name: String,
     pub fields: Vec<DataClassField>,
+    pub annotations: Vec<Annotation>,
+    pub lifetime_params: Vec<String>, // Declared lifetime labels: `data class Name<@a>`
+    pub methods: Vec<MemberFunction>, // Member functions declared in the class body
+    pub location: crate::error::SourceLocation,
+}
+
+/// Member function in a data class body: `fun name(...)`, `operator fun plus(...)`
+/// or `override fun toString()`
+#[derive(Debug, Clone)]
+pub struct MemberFunction {
+    pub is_operator: bool, // Declared with `operator`, overloading the matching operator
+    pub is_override: bool, // Declared with `override`, replacing a generated implementation
+    pub fun_decl: FunDeclStmt,
+    pub location: crate::error::SourceLocation,
 }
 
 #[derive(Debug, Clone)]
 pub struct DataClassField {
     pub name: String,
     pub field_type: Located<VeltranoType>,
+    pub default_value: Option<LocatedExpr>, // Filled in by constructor calls that omit the field
     pub inline_comment: Option<(String, String)>, // Inline comment after field
+    pub annotations: Vec<Annotation>,
+}
+
+/// Annotation on a declaration: `@Name` or `@Name(arg, ...)`
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub name: String,
+    pub args: Vec<AnnotationArg>,
+    pub location: crate::error::SourceLocation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationArg {
+    Identifier(String), // Bare name, e.g. `Hash` in `@Derive(Hash)`
+    String(String),     // Unescaped string literal, e.g. `"inline"` in `@Rust("inline")`
 }
@@ -78,6 +78,11 @@ impl ExprExt for LocatedExpr {
                 for arg in &method_call.args {
                     arg.walk(visitor)?;
                 }
+                for arg in &method_call.named_args {
+                    if let Argument::Named(_, expr, _) = arg {
+                        expr.walk(visitor)?;
+                    }
+                }
             }
             Expr::FieldAccess(field_access) => {
                 field_access.object.walk(visitor)?;
@@ -85,7 +90,16 @@ impl ExprExt for LocatedExpr {
             Expr::Parenthesized(paren_expr) => {
                 paren_expr.expr.walk(visitor)?;
             }
-            Expr::Literal(_) | Expr::Identifier(_) => {
+            Expr::Tuple(tuple) => {
+                for element in &tuple.elements {
+                    element.walk(visitor)?;
+                }
+            }
+            Expr::Index(index) => {
+                index.object.walk(visitor)?;
+                index.index.walk(visitor)?;
+            }
+            Expr::Literal(_) | Expr::Identifier(_) | Expr::RustCode(_) | Expr::FunctionRef(_) => {
                 // Leaf nodes - no children to visit
             }
         }
@@ -121,6 +135,11 @@ impl ExprExt for LocatedExpr {
                 for arg in &method_call.args {
                     arg.walk_post(visitor)?;
                 }
+                for arg in &method_call.named_args {
+                    if let Argument::Named(_, expr, _) = arg {
+                        expr.walk_post(visitor)?;
+                    }
+                }
             }
             Expr::FieldAccess(field_access) => {
                 field_access.object.walk_post(visitor)?;
@@ -128,7 +147,16 @@ impl ExprExt for LocatedExpr {
             Expr::Parenthesized(paren_expr) => {
                 paren_expr.expr.walk_post(visitor)?;
             }
-            Expr::Literal(_) | Expr::Identifier(_) => {
+            Expr::Tuple(tuple) => {
+                for element in &tuple.elements {
+                    element.walk_post(visitor)?;
+                }
+            }
+            Expr::Index(index) => {
+                index.object.walk_post(visitor)?;
+                index.index.walk_post(visitor)?;
+            }
+            Expr::Literal(_) | Expr::Identifier(_) | Expr::RustCode(_) | Expr::FunctionRef(_) => {
                 // Leaf nodes - no children to visit
             }
         }
@@ -173,6 +201,11 @@ impl ExprExt for LocatedExpr {
                     for arg in &method_call.args {
                         collect(arg, predicate, results);
                     }
+                    for arg in &method_call.named_args {
+                        if let Argument::Named(_, e, _) = arg {
+                            collect(e, predicate, results);
+                        }
+                    }
                 }
                 Expr::FieldAccess(field_access) => {
                     collect(&field_access.object, predicate, results);
@@ -180,7 +213,19 @@ impl ExprExt for LocatedExpr {
                 Expr::Parenthesized(paren_expr) => {
                     collect(&paren_expr.expr, predicate, results);
                 }
-                Expr::Literal(_) | Expr::Identifier(_) => {}
+                Expr::Tuple(tuple) => {
+                    for element in &tuple.elements {
+                        collect(element, predicate, results);
+                    }
+                }
+                Expr::Index(index) => {
+                    collect(&index.object, predicate, results);
+                    collect(&index.index, predicate, results);
+                }
+                Expr::Literal(_)
+                | Expr::Identifier(_)
+                | Expr::RustCode(_)
+                | Expr::FunctionRef(_) => {}
             }
         }
 
@@ -272,7 +317,7 @@ impl StmtExt for Stmt {
 
         // Then visit children
         match self {
-            Stmt::Block(statements) => {
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
                 for stmt in statements {
                     stmt.walk(visitor)?;
                 }
@@ -289,13 +334,20 @@ impl StmtExt for Stmt {
             Stmt::FunDecl(fun_decl) => {
                 fun_decl.body.walk(visitor)?;
             }
+            Stmt::Annotated(annotated) => {
+                annotated.stmt.walk(visitor)?;
+            }
             // Leaf nodes
             Stmt::Expression(_)
             | Stmt::VarDecl(_)
             | Stmt::Return(_)
             | Stmt::Comment(_)
             | Stmt::Import(_)
-            | Stmt::DataClass(_) => {}
+            | Stmt::DataClass(_)
+            | Stmt::Destructure(_)
+            | Stmt::Assign(_)
+            | Stmt::RustBlock(_)
+            | Stmt::ExternFun(_) => {}
         }
         Ok(())
     }
@@ -306,7 +358,7 @@ impl StmtExt for Stmt {
     {
         // Visit children first (post-order)
         match self {
-            Stmt::Block(statements) => {
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
                 for stmt in statements {
                     stmt.walk_post(visitor)?;
                 }
@@ -323,13 +375,20 @@ impl StmtExt for Stmt {
             Stmt::FunDecl(fun_decl) => {
                 fun_decl.body.walk_post(visitor)?;
             }
+            Stmt::Annotated(annotated) => {
+                annotated.stmt.walk_post(visitor)?;
+            }
             // Leaf nodes
             Stmt::Expression(_)
             | Stmt::VarDecl(_)
             | Stmt::Return(_)
             | Stmt::Comment(_)
             | Stmt::Import(_)
-            | Stmt::DataClass(_) => {}
+            | Stmt::DataClass(_)
+            | Stmt::Destructure(_)
+            | Stmt::Assign(_)
+            | Stmt::RustBlock(_)
+            | Stmt::ExternFun(_) => {}
         }
 
         // Then visit this node
@@ -349,7 +408,7 @@ impl StmtExt for Stmt {
             }
 
             match stmt {
-                Stmt::Block(statements) => {
+                Stmt::Block(statements) | Stmt::Unsafe(statements) => {
                     for s in statements {
                         collect(s, predicate, results);
                     }
@@ -366,6 +425,9 @@ impl StmtExt for Stmt {
                 Stmt::FunDecl(fun_decl) => {
                     collect(&fun_decl.body, predicate, results);
                 }
+                Stmt::Annotated(annotated) => {
+                    collect(&annotated.stmt, predicate, results);
+                }
                 _ => {}
             }
         }
@@ -386,6 +448,11 @@ impl StmtExt for Stmt {
                     init.walk(visitor)?;
                 }
             }
+            Stmt::Destructure(destructure) => destructure.initializer.walk(visitor)?,
+            Stmt::Assign(assign) => {
+                assign.target.walk(visitor)?;
+                assign.value.walk(visitor)?;
+            }
             Stmt::Return(Some(expr)) => expr.walk(visitor)?,
             Stmt::If(if_stmt) => {
                 if_stmt.condition.walk(visitor)?;
@@ -398,7 +465,7 @@ impl StmtExt for Stmt {
                 while_stmt.condition.walk(visitor)?;
                 while_stmt.body.walk_expressions(visitor)?;
             }
-            Stmt::Block(statements) => {
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
                 for stmt in statements {
                     stmt.walk_expressions(visitor)?;
                 }
@@ -406,6 +473,9 @@ impl StmtExt for Stmt {
             Stmt::FunDecl(fun_decl) => {
                 fun_decl.body.walk_expressions(visitor)?;
             }
+            Stmt::Annotated(annotated) => {
+                annotated.stmt.walk_expressions(visitor)?;
+            }
             _ => {}
         }
         Ok(())
@@ -414,7 +484,9 @@ impl StmtExt for Stmt {
     fn can_exit_early(&self) -> bool {
         match self {
             Stmt::Return(_) => true,
-            Stmt::Block(statements) => statements.iter().any(|s| s.can_exit_early()),
+            Stmt::Block(statements) | Stmt::Unsafe(statements) => {
+                statements.iter().any(|s| s.can_exit_early())
+            }
             Stmt::If(if_stmt) => {
                 if_stmt.then_branch.can_exit_early()
                     || if_stmt
@@ -424,6 +496,7 @@ impl StmtExt for Stmt {
             }
             Stmt::While(while_stmt) => while_stmt.body.can_exit_early(),
             Stmt::FunDecl(fun_decl) => fun_decl.body.can_exit_early(),
+            Stmt::Annotated(annotated) => annotated.stmt.can_exit_early(),
             _ => false,
         }
     }
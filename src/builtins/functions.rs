@@ -7,15 +7,24 @@ use super::types::BuiltinFunctionKind;
 use crate::types::{FunctionSignature, VeltranoType};
 use std::collections::HashMap;
 
+/// Builtins that are Rust macros (variadic, skip type checking): (Veltrano name, macro name)
+const RUST_MACROS: &[(&str, &str)] = &[
+    ("println", "println"),
+    ("print", "print"),
+    ("panic", "panic"),
+    ("assert", "assert"),
+    ("assertEquals", "assert_eq"),
+    ("debug_assert", "debug_assert"),
+];
+
 /// Register all built-in functions
 pub fn register_builtin_functions() -> HashMap<String, BuiltinFunctionKind> {
     let mut functions = HashMap::new();
 
     // Rust macros (variadic, skip type checking)
-    let rust_macros = vec!["println", "print", "panic", "assert", "debug_assert"];
-    for macro_name in rust_macros {
+    for (name, macro_name) in RUST_MACROS {
         functions.insert(
-            macro_name.to_string(),
+            name.to_string(),
             BuiltinFunctionKind::RustMacro {
                 macro_name: macro_name.to_string(),
             },
@@ -38,9 +47,52 @@ pub fn register_builtin_functions() -> HashMap<String, BuiltinFunctionKind> {
         },
     );
 
+    // Box(x), Rc(x), Arc(x), RefCell(x) and Cell(x) take ownership of x in a new wrapper
+    let wrappers = [
+        (
+            "Box",
+            VeltranoType::boxed as fn(VeltranoType) -> VeltranoType,
+        ),
+        ("Rc", VeltranoType::rc),
+        ("Arc", VeltranoType::arc),
+        ("RefCell", VeltranoType::ref_cell),
+        ("Cell", VeltranoType::cell),
+    ];
+    for (name, wrap) in wrappers {
+        let value_type = VeltranoType::generic("T".to_string(), vec![]);
+        functions.insert(
+            name.to_string(),
+            BuiltinFunctionKind::SpecialFunction {
+                function_name: name.to_string(),
+                parameters: vec![value_type.clone()],
+                return_type: VeltranoType::own(wrap(value_type)),
+            },
+        );
+    }
+
     functions
 }
 
+/// Rust constructor called by a wrapper builtin such as `Rc(x)`
+pub fn wrapper_constructor(name: &str) -> Option<&'static str> {
+    match name {
+        "Box" => Some("Box::new"),
+        "Rc" => Some("std::rc::Rc::new"),
+        "Arc" => Some("std::sync::Arc::new"),
+        "RefCell" => Some("std::cell::RefCell::new"),
+        "Cell" => Some("std::cell::Cell::new"),
+        _ => None,
+    }
+}
+
+/// Rust macro called by a macro builtin such as `assertEquals(a, b)`
+pub fn rust_macro_name(name: &str) -> Option<&'static str> {
+    RUST_MACROS
+        .iter()
+        .find(|(veltrano_name, _)| *veltrano_name == name)
+        .map(|(_, macro_name)| *macro_name)
+}
+
 /// Check if a function is a Rust macro (skips type checking)
 pub fn is_rust_macro(name: &str, functions: &HashMap<String, BuiltinFunctionKind>) -> bool {
     if let Some(BuiltinFunctionKind::RustMacro { .. }) = functions.get(name) {
@@ -70,6 +122,11 @@ pub fn get_function_signatures(
                     name: function_name.clone(),
                     parameters: parameters.clone(),
                     return_type: return_type.clone(),
+                    lifetime_params: vec![],
+                    parameter_names: vec![],
+                    parameter_defaults: vec![],
+                    is_async: false,
+                    is_unsafe: false,
                 });
             }
         }
@@ -85,6 +85,22 @@ fn compute_operator_return_type(op: &OperatorMethod, receiver_type: &VeltranoTyp
             }
         }
         OperatorMethod::MutRef => VeltranoType::mut_ref(receiver_type.clone()),
+        // Future<T> → T
+        OperatorMethod::Await => match &receiver_type.constructor {
+            TypeConstructor::Future => receiver_type
+                .inner()
+                .cloned()
+                .unwrap_or_else(VeltranoType::unit),
+            _ => receiver_type.clone(),
+        },
+        // Ref<T> → T, MutRef<T> → T (strips exactly one reference level)
+        OperatorMethod::Deref => match &receiver_type.constructor {
+            TypeConstructor::Ref | TypeConstructor::MutRef => receiver_type
+                .inner()
+                .cloned()
+                .unwrap_or_else(|| receiver_type.clone()),
+            _ => receiver_type.clone(),
+        },
     }
 }
 
@@ -19,6 +19,8 @@ pub fn register_builtin_methods() -> HashMap<String, Vec<BuiltinMethodKind>> {
         OperatorMethod::Ref,
         OperatorMethod::MutRef,
         OperatorMethod::BumpRef,
+        OperatorMethod::Await,
+        OperatorMethod::Deref,
     ];
 
     for op in operators {
@@ -13,6 +13,7 @@ mod methods;
 mod method_resolution;
 
 // Re-export all types for convenience
+pub use functions::{rust_macro_name, wrapper_constructor};
 pub use types::*;
 
 use crate::rust_interop::{RustInteropRegistry, SelfKind};
@@ -38,6 +39,11 @@ impl BuiltinRegistry {
         functions::is_rust_macro(name, &self.functions)
     }
 
+    /// Check if a name refers to a built-in function or macro
+    pub fn is_builtin_function(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
     /// Get function signatures for type checker initialization
     pub fn get_function_signatures(&self) -> Vec<FunctionSignature> {
         functions::get_function_signatures(&self.functions)
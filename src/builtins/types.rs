@@ -27,6 +27,10 @@ pub enum OperatorMethod {
     MutRef,
     /// bumpRef() method - generates bump.alloc()
     BumpRef,
+    /// await() method - generates .await
+    Await,
+    /// deref() method - generates *
+    Deref,
 }
 
 impl OperatorMethod {
@@ -36,6 +40,8 @@ impl OperatorMethod {
             OperatorMethod::Ref => "ref",
             OperatorMethod::MutRef => "mutRef",
             OperatorMethod::BumpRef => "bumpRef",
+            OperatorMethod::Await => "await",
+            OperatorMethod::Deref => "deref",
         }
     }
 }
@@ -5,9 +5,11 @@
 use super::{CodeGenerator, CodegenError};
 use crate::ast::*;
 use crate::ast::{Argument, ParenthesizedExpr};
+use crate::builtins::{rust_macro_name, wrapper_constructor};
 use crate::comments::{Comment, CommentStyle};
 use crate::error::{Span, VeltranoError};
 use crate::rust_interop::camel_to_snake_case;
+use crate::types::{tuple_component_index, TypeConstructor};
 
 /// String used for one level of indentation
 const INDENT_STR: &str = "    ";
@@ -22,6 +24,9 @@ impl CodeGenerator {
             Expr::Literal(literal) => {
                 self.generate_literal(literal, expr.span.clone());
             }
+            Expr::Identifier(name) if name == "this" && self.in_member_function => {
+                self.output.push_str("self");
+            }
             Expr::Identifier(name) => {
                 let snake_name = camel_to_snake_case(name);
                 self.output.push_str(&snake_name);
@@ -30,6 +35,12 @@ impl CodeGenerator {
                 match &unary.operator {
                     UnaryOp::Minus => {
                         self.output.push('-');
+                        // Owned data class operands are borrowed for the `Neg` impl on references
+                        if let Some(resolution) = self.operator_resolutions.get(&unary.id) {
+                            if resolution.borrow_left {
+                                return self.generate_borrowed_operand(&unary.operand);
+                            }
+                        }
                         // Wrap non-simple expressions in parentheses
                         match &unary.operand.node {
                             Expr::Literal(_) | Expr::Identifier(_) => {
@@ -63,6 +74,26 @@ impl CodeGenerator {
             Expr::Parenthesized(paren_expr) => {
                 self.generate_parenthesized_expression(paren_expr)?;
             }
+            Expr::Tuple(tuple) => {
+                self.output.push('(');
+                for (i, element) in tuple.elements.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(", ");
+                    }
+                    self.generate_expression(element)?;
+                }
+                self.output.push(')');
+            }
+            Expr::Index(index) => {
+                self.generate_index_expression(index)?;
+            }
+            Expr::FunctionRef(function_ref) => {
+                self.generate_function_reference(function_ref);
+            }
+            Expr::RustCode(rust_code) => {
+                let code = self.convert_embedded_identifiers(&rust_code.code);
+                self.output.push_str(&code);
+            }
         }
         Ok(())
     }
@@ -92,7 +123,17 @@ impl CodeGenerator {
 
     /// Generate code for binary expressions with proper comment handling
     fn generate_binary_expression(&mut self, binary: &BinaryExpr) -> Result<(), VeltranoError> {
-        self.generate_expression(&binary.left)?;
+        // Operators resolved through `operator fun` may need owned operands borrowed
+        let (borrow_left, borrow_right) = match self.operator_resolutions.get(&binary.id) {
+            Some(resolution) => (resolution.borrow_left, resolution.borrow_right),
+            None => (false, false),
+        };
+
+        if borrow_left {
+            self.generate_borrowed_operand(&binary.left)?;
+        } else {
+            self.generate_expression(&binary.left)?;
+        }
 
         // Generate comment after left operand if present
         self.generate_binary_operator_comment(&binary.comment_after_left);
@@ -102,7 +143,24 @@ impl CodeGenerator {
         // Generate comment after operator if present
         self.generate_binary_operator_comment(&binary.comment_after_operator);
 
-        self.generate_expression(&binary.right)?;
+        if borrow_right {
+            self.generate_borrowed_operand(&binary.right)?;
+        } else {
+            self.generate_expression(&binary.right)?;
+        }
+        Ok(())
+    }
+
+    /// Generate `&operand`, parenthesizing operator expressions
+    fn generate_borrowed_operand(&mut self, operand: &LocatedExpr) -> Result<(), VeltranoError> {
+        self.output.push('&');
+        if matches!(operand.node, Expr::Binary(_) | Expr::Unary(_)) {
+            self.output.push('(');
+            self.generate_expression(operand)?;
+            self.output.push(')');
+        } else {
+            self.generate_expression(operand)?;
+        }
         Ok(())
     }
 
@@ -143,22 +201,7 @@ impl CodeGenerator {
 
     /// Generate code for binary operators
     fn generate_binary_operator(&mut self, op: &BinaryOp) {
-        let op_str = match op {
-            BinaryOp::Add => "+",
-            BinaryOp::Subtract => "-",
-            BinaryOp::Multiply => "*",
-            BinaryOp::Divide => "/",
-            BinaryOp::Modulo => "%",
-            BinaryOp::Equal => "==",
-            BinaryOp::NotEqual => "!=",
-            BinaryOp::Less => "<",
-            BinaryOp::LessEqual => "<=",
-            BinaryOp::Greater => ">",
-            BinaryOp::GreaterEqual => ">=",
-            BinaryOp::And => "&&",
-            BinaryOp::Or => "||",
-        };
-        self.output.push_str(op_str);
+        self.output.push_str(op.symbol());
     }
 
     /// Generate code for generic function calls
@@ -178,18 +221,253 @@ impl CodeGenerator {
         Ok(())
     }
 
+    /// Generate a function reference as a Rust function path, or as a closure
+    /// where the function takes bump allocators or the receiver is a Copy value
+    fn generate_function_reference(&mut self, function_ref: &FunctionRefExpr) {
+        let method_name = camel_to_snake_case(&function_ref.name);
+
+        if let Some(class_name) = &function_ref.receiver {
+            let is_copy = self
+                .data_class_derives
+                .get(class_name)
+                .is_some_and(|derives| derives.iter().any(|d| d == "Copy"));
+            if !is_copy {
+                self.output
+                    .push_str(&format!("{}::{}", class_name, method_name));
+                return;
+            }
+            // Copy receivers are passed by value, but members borrow `self`
+            let params = Self::closure_params(
+                self.member_function_params
+                    .get(&(class_name.clone(), function_ref.name.clone())),
+            );
+            let mut closure_params = vec![format!("receiver: {}", class_name)];
+            closure_params.extend(params.iter().cloned());
+            self.output.push_str(&format!(
+                "|{}| receiver.{}({})",
+                closure_params.join(", "),
+                method_name,
+                params.join(", ")
+            ));
+            return;
+        }
+
+        if !self.local_functions_with_bump.contains(&function_ref.name) {
+            self.output.push_str(&method_name);
+            return;
+        }
+        // The closure captures the caller's bump for each bump parameter
+        let bump_count = self
+            .function_lifetime_params
+            .get(&function_ref.name)
+            .map_or(1, |lifetime_params| {
+                self.bump_labels(&function_ref.name, lifetime_params).len()
+            });
+        let bump = self.bump_for(self.bump_target.clone().as_ref());
+        let params = Self::closure_params(self.function_params.get(&function_ref.name));
+        let args: Vec<String> = std::iter::repeat_n(bump, bump_count)
+            .chain(params.iter().cloned())
+            .collect();
+        self.output.push_str(&format!(
+            "move |{}| {}({})",
+            params.join(", "),
+            method_name,
+            args.join(", ")
+        ));
+    }
+
+    /// Closure parameter names for the parameters of a referenced function
+    fn closure_params(params: Option<&Vec<Parameter>>) -> Vec<String> {
+        params
+            .map(|params| {
+                params
+                    .iter()
+                    .map(|param| camel_to_snake_case(&param.name))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Generate the object of a field access, method call or index; a builtin
+    /// `.deref()` is parenthesized so that its `*` applies to the object alone,
+    /// and an indexed element is used in place rather than borrowed
+    fn generate_receiver(&mut self, object: &LocatedExpr) -> Result<(), VeltranoError> {
+        let is_deref = match &object.node {
+            Expr::MethodCall(method_call) => {
+                method_call.method == "deref"
+                    && method_call.args.is_empty()
+                    && !self.method_resolutions.contains_key(&method_call.id)
+                    && !self.node_types.contains_key(&method_call.id)
+                    && !self.imports.contains_key(&method_call.method)
+            }
+            _ => false,
+        };
+        if is_deref {
+            self.output.push('(');
+            self.generate_expression(object)?;
+            self.output.push(')');
+            Ok(())
+        } else if let Expr::Index(index) = &object.node {
+            self.generate_indexed_place(index)
+        } else {
+            self.generate_expression(object)
+        }
+    }
+
     /// Generate code for field access expressions
     fn generate_field_access(
         &mut self,
         field_access: &FieldAccessExpr,
     ) -> Result<(), VeltranoError> {
-        self.generate_expression(&field_access.object)?;
+        self.generate_receiver(&field_access.object)?;
         self.output.push('.');
+
+        // Tuple components (`first`, `second`, ...) become positional fields
+        let object_type = self.node_types.get(&field_access.id);
+        let tuple_type = match object_type.map(|t| &t.constructor) {
+            Some(TypeConstructor::Ref) => object_type.and_then(|t| t.inner()),
+            _ => object_type,
+        };
+        if let Some(tuple_type) = tuple_type {
+            if tuple_type.constructor == TypeConstructor::Tuple {
+                if let Some(index) = tuple_component_index(&field_access.field) {
+                    self.output.push_str(&index.to_string());
+                    return Ok(());
+                }
+            }
+        }
+
         self.output
             .push_str(&camel_to_snake_case(&field_access.field));
         Ok(())
     }
 
+    /// Generate code for index expressions; non-Copy elements are borrowed
+    fn generate_index_expression(&mut self, index: &IndexExpr) -> Result<(), VeltranoError> {
+        // Data classes and maps are indexed through `get`, which returns its own value
+        let object_type = self.node_types.get(&index.id).cloned();
+        let borrowed = object_type.is_some_and(|object_type| {
+            object_type.data_class_name().is_none()
+                && object_type.map_types().is_none()
+                && object_type.element_type().is_some_and(|element_type| {
+                    element_type
+                        .borrowed_component_type(&mut self.trait_checker)
+                        .is_some()
+                })
+        });
+
+        if borrowed {
+            self.output.push('&');
+        }
+        self.generate_indexed_place(index)
+    }
+
+    /// Generate an index expression as a place, such as the object of another
+    /// index, a field access or a method call, which is never borrowed itself
+    fn generate_indexed_place(&mut self, index: &IndexExpr) -> Result<(), VeltranoError> {
+        // Data classes are indexed through their `operator fun get`
+        if self
+            .node_types
+            .get(&index.id)
+            .and_then(|t| t.data_class_name())
+            .is_some()
+        {
+            self.generate_receiver(&index.object)?;
+            self.output.push_str(".get(");
+            self.generate_expression(&index.index)?;
+            self.output.push(')');
+            return Ok(());
+        }
+
+        // Maps are indexed through `get`; Copy keys are passed by reference
+        if let Some((key_type, _)) = self.node_types.get(&index.id).and_then(|t| t.map_types()) {
+            let key_type = key_type.clone();
+            self.generate_receiver(&index.object)?;
+            self.output.push_str(".get(");
+            if key_type.implements_copy(&mut self.trait_checker) {
+                self.output.push('&');
+            }
+            self.generate_expression(&index.index)?;
+            self.output.push(')');
+            return Ok(());
+        }
+
+        self.generate_receiver(&index.object)?;
+        self.output.push('[');
+        self.generate_expression(&index.index)?;
+        self.output.push(']');
+        Ok(())
+    }
+
+    /// Generate code for collection literals: `vec![..]`, `[..]`, `[value; size]`
+    /// and `from([..])` for maps and sets
+    fn generate_collection_literal(
+        &mut self,
+        name: &str,
+        call: &CallExpr,
+        call_span: Span,
+    ) -> Result<(), VeltranoError> {
+        if name == "Array" {
+            let args: Vec<&LocatedExpr> = call
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    Argument::Bare(expr, _) => Some(expr),
+                    _ => None,
+                })
+                .collect();
+            if args.len() != 2 {
+                return Err(CodegenError::InvalidBuiltinArguments {
+                    builtin: "Array".to_string(),
+                    reason: format!(
+                        "requires a size and a value, found {} arguments",
+                        args.len()
+                    ),
+                    location: call_span.start.clone(),
+                }
+                .into());
+            }
+            self.output.push('[');
+            self.generate_expression(args[1])?;
+            self.output.push_str("; ");
+            self.generate_expression(args[0])?;
+            self.output.push(']');
+            return Ok(());
+        }
+
+        let collection_path = match name {
+            "mapOf" => Some("std::collections::HashMap"),
+            "sortedMapOf" => Some("std::collections::BTreeMap"),
+            "setOf" => Some("std::collections::HashSet"),
+            _ => None,
+        };
+        if let Some(path) = collection_path {
+            self.output.push_str(path);
+            if call.args.is_empty() {
+                self.output.push_str("::new()");
+                return Ok(());
+            }
+            self.output.push_str("::from([");
+            self.generate_comma_separated_args_for_function_call_with_multiline(
+                &call.args,
+                call.is_multiline,
+                call_span,
+            )?;
+            self.output.push_str("])");
+            return Ok(());
+        }
+
+        self.output
+            .push_str(if name == "vecOf" { "vec![" } else { "[" });
+        self.generate_comma_separated_args_for_function_call_with_multiline(
+            &call.args,
+            call.is_multiline,
+            call_span,
+        )?;
+        self.output.push(']');
+        Ok(())
+    }
+
     /// Generate code for function call expressions
     pub(super) fn generate_call_expression(
         &mut self,
@@ -212,6 +490,31 @@ impl CodeGenerator {
                 return self.generate_mutref_builtin(call, call_span);
             } else if self.local_functions.contains(name) {
                 return self.generate_local_function_call(name, call, call_span);
+            } else if let Some(constructor) = wrapper_constructor(name) {
+                self.output.push_str(constructor);
+                self.output.push('(');
+                self.generate_comma_separated_args_for_function_call_with_multiline(
+                    &call.args,
+                    call.is_multiline,
+                    call_span,
+                )?;
+                self.output.push(')');
+                return Ok(());
+            } else if matches!(
+                name.as_str(),
+                "vecOf" | "arrayOf" | "Array" | "mapOf" | "sortedMapOf" | "setOf"
+            ) {
+                return self.generate_collection_literal(name, call, call_span);
+            } else if name == "Pair" || name == "Triple" {
+                // Pair/Triple are plain Rust tuples
+                self.output.push('(');
+                self.generate_comma_separated_args_for_function_call_with_multiline(
+                    &call.args,
+                    call.is_multiline,
+                    call_span,
+                )?;
+                self.output.push(')');
+                return Ok(());
             } else if let Some((type_name, original_method)) = self.imports.get(name) {
                 let type_name = type_name.clone();
                 let original_method = original_method.clone();
@@ -221,8 +524,8 @@ impl CodeGenerator {
                     call,
                     call_span,
                 );
-            } else if self.is_rust_macro(name) {
-                return self.generate_macro_call(name, call, call_span);
+            } else if let Some(macro_name) = rust_macro_name(name) {
+                return self.generate_macro_call(macro_name, call, call_span);
             }
         }
 
@@ -247,6 +550,26 @@ impl CodeGenerator {
             return self.generate_resolved_method_call(&resolution, method_call);
         }
 
+        // `.copy()` of a data class without a `copy` member copies it with overrides
+        if method_call.method == "copy" {
+            let class_name = self
+                .node_types
+                .get(&method_call.id)
+                .and_then(|t| t.data_class_name())
+                .map(str::to_string);
+            if let Some(class_name) = class_name {
+                let key = (class_name.clone(), method_call.method.clone());
+                if !self.member_function_params.contains_key(&key) {
+                    return self.generate_copy_expression(class_name, method_call, expr_span);
+                }
+            }
+        }
+
+        // Member functions of data classes are called with method syntax
+        if self.node_types.contains_key(&method_call.id) {
+            return self.generate_member_function_call(method_call);
+        }
+
         if let Some((type_name, original_method)) = self.imports.get(&method_call.method) {
             let type_name = type_name.clone();
             let original_method = original_method.clone();
@@ -261,7 +584,9 @@ impl CodeGenerator {
                 Ok(())
             }
             "bumpRef" if method_call.args.is_empty() => {
-                self.output.push_str("bump.alloc(");
+                let bump = self.bump_for(self.bump_target.clone().as_ref());
+                self.output.push_str(&bump);
+                self.output.push_str(".alloc(");
                 self.generate_expression(&method_call.object)?;
                 self.output.push(')');
                 Ok(())
@@ -271,6 +596,16 @@ impl CodeGenerator {
                 self.generate_expression(&method_call.object)?;
                 Ok(())
             }
+            "deref" if method_call.args.is_empty() => {
+                self.output.push('*');
+                self.generate_expression(&method_call.object)?;
+                Ok(())
+            }
+            "await" if method_call.args.is_empty() => {
+                self.generate_receiver(&method_call.object)?;
+                self.output.push_str(".await");
+                Ok(())
+            }
             _ => {
                 // Method requires import but wasn't imported
                 Err(CodegenError::MissingImport {
@@ -335,15 +670,126 @@ impl CodeGenerator {
         name: &str,
         call: &CallExpr,
         call_span: Span,
+    ) -> Result<(), VeltranoError> {
+        // Fields left out of the call take their default values
+        let mut args = call.args.clone();
+        let fields = self
+            .data_class_fields
+            .get(name)
+            .cloned()
+            .unwrap_or_default();
+        for field in fields {
+            let given = call.args.iter().any(|arg| match arg {
+                Argument::Named(arg_name, _, _) | Argument::Shorthand(arg_name, _) => {
+                    *arg_name == field.name
+                }
+                _ => false,
+            });
+            if let (false, Some(default_value)) = (given, field.default_value) {
+                args.push(Argument::Named(
+                    field.name,
+                    default_value,
+                    ArgumentComment::default(),
+                ));
+            }
+        }
+
+        self.generate_struct_literal(name, &args, &[], call.is_multiline, call_span)
+    }
+
+    /// Generate a data class copy: `p.copy(age = 31)` becomes `Person { age: 31, ..p.clone() }`.
+    /// Classes holding references are rebuilt field by field instead, copying the
+    /// references and cloning only the owned fields.
+    fn generate_copy_expression(
+        &mut self,
+        class_name: String,
+        copy: &MethodCallExpr,
+        span: Span,
+    ) -> Result<(), VeltranoError> {
+        let saved_output = std::mem::take(&mut self.output);
+        self.generate_receiver(&copy.object)?;
+        let object = std::mem::replace(&mut self.output, saved_output);
+
+        let is_place = |expr: &LocatedExpr| {
+            let mut expr = expr;
+            while let Expr::FieldAccess(field_access) = &expr.node {
+                expr = &field_access.object;
+            }
+            matches!(expr.node, Expr::Identifier(_))
+        };
+
+        let remaining_fields =
+            if self.data_classes_with_lifetime.contains(&class_name) && is_place(&copy.object) {
+                let fields = self
+                    .data_class_fields
+                    .get(&class_name)
+                    .cloned()
+                    .unwrap_or_default();
+                fields
+                    .iter()
+                    .filter(|field| {
+                        !copy.named_args.iter().any(|arg| match arg {
+                            Argument::Named(arg_name, _, _) | Argument::Shorthand(arg_name, _) => {
+                                *arg_name == field.name
+                            }
+                            _ => false,
+                        })
+                    })
+                    .map(|field| {
+                        let field_name = camel_to_snake_case(&field.name);
+                        let clone = match field.field_type.node.constructor {
+                            TypeConstructor::Own => ".clone()",
+                            _ => "",
+                        };
+                        format!("{}: {}.{}{}", field_name, object, field_name, clone)
+                    })
+                    .collect()
+            } else {
+                let is_copy = self
+                    .data_class_derives
+                    .get(&class_name)
+                    .is_some_and(|traits| traits.iter().any(|t| t == "Copy"));
+                let object = match &copy.object.node {
+                    // `&x` and `&mut x` must be parenthesized to clone the referenced value
+                    Expr::MethodCall(method_call)
+                        if !is_copy && matches!(method_call.method.as_str(), "ref" | "mutRef") =>
+                    {
+                        format!("({})", object)
+                    }
+                    Expr::Binary(_) | Expr::Unary(_) if !is_copy => format!("({})", object),
+                    _ => object,
+                };
+                let clone = if is_copy { "" } else { ".clone()" };
+                vec![format!("..{}{}", object, clone)]
+            };
+
+        self.generate_struct_literal(
+            &class_name,
+            &copy.named_args,
+            &remaining_fields,
+            copy.is_multiline,
+            span,
+        )
+    }
+
+    /// Generate a struct literal from named and shorthand arguments, followed by
+    /// `extra_fields` already in Rust syntax (such as a `..base` functional update)
+    fn generate_struct_literal(
+        &mut self,
+        name: &str,
+        args: &[Argument],
+        extra_fields: &[String],
+        is_multiline: bool,
+        call_span: Span,
     ) -> Result<(), VeltranoError> {
         self.output.push_str(name);
 
-        if call.is_multiline {
+        if is_multiline {
             // Multiline struct initialization
             self.output.push_str(" {\n");
             self.indent_level += 1;
 
-            for arg in call.args.iter() {
+            for arg in args.iter() {
                 match arg {
                     Argument::StandaloneComment(content, whitespace) => {
                         // Generate standalone comment
@@ -394,13 +840,32 @@ impl CodeGenerator {
                 }
             }
 
+            for extra_field in extra_fields {
+                self.indent();
+                self.output.push_str(extra_field);
+                // The functional update base must not be followed by a comma
+                if !extra_field.starts_with("..") {
+                    self.output.push(',');
+                }
+                self.output.push('\n');
+            }
+
             self.indent_level -= 1;
             self.indent();
             self.output.push('}');
         } else {
             // Single-line struct initialization
             self.output.push_str(" { ");
-            self.generate_comma_separated_args_for_struct_init(&call.args, call_span)?;
+            self.generate_comma_separated_args_for_struct_init(args, call_span)?;
+            let has_args = args
+                .iter()
+                .any(|arg| !matches!(arg, Argument::StandaloneComment(_, _)));
+            for (i, extra_field) in extra_fields.iter().enumerate() {
+                if has_args || i > 0 {
+                    self.output.push_str(", ");
+                }
+                self.output.push_str(extra_field);
+            }
             self.output.push_str(" }");
         }
 
@@ -459,27 +924,175 @@ impl CodeGenerator {
         call: &CallExpr,
         call_span: Span,
     ) -> Result<(), VeltranoError> {
+        // Reordered arguments with side effects run first, in source order
+        let (args, temporaries) = self.bind_reordered_arguments(name, &call.args);
+        if !temporaries.is_empty() {
+            self.output.push_str("{ ");
+            for (temporary, expr) in &temporaries {
+                self.output
+                    .push_str(&format!("let {} = ", camel_to_snake_case(temporary)));
+                self.generate_expression(expr)?;
+                self.output.push_str("; ");
+            }
+        }
+
         let snake_name = camel_to_snake_case(name);
         self.output.push_str(&snake_name);
         self.output.push('(');
 
-        // If this function has hidden bump, add bump as first argument
-        if self.local_functions_with_bump.contains(name) {
-            self.output.push_str("bump");
+        // Labeled functions take one bump per label: the arena of the bound
+        // caller lifetime, or of the target lifetime for unbound labels
+        if let Some(lifetime_params) = self.function_lifetime_params.get(name).cloned() {
+            let bindings = self
+                .lifetime_bindings
+                .get(&call.id)
+                .cloned()
+                .unwrap_or_default();
+            let target = self.bump_target.clone();
+            let bump_labels = self.bump_labels(name, &lifetime_params);
+            let bumps: Vec<String> = lifetime_params
+                .iter()
+                .enumerate()
+                .filter(|(_, callee_label)| bump_labels.contains(callee_label))
+                .map(|(i, _)| {
+                    let label = bindings.get(i).cloned().flatten().or(target.clone());
+                    self.bump_for(label.as_ref())
+                })
+                .collect();
+            self.output.push_str(&bumps.join(", "));
+            if !bumps.is_empty() && !call.args.is_empty() {
+                self.output.push_str(", ");
+            }
+        } else if self.local_functions_with_bump.contains(name) {
+            // If this function has hidden bump, add bump as first argument
+            let bump = self.bump_for(self.bump_target.clone().as_ref());
+            self.output.push_str(&bump);
             if !call.args.is_empty() {
                 self.output.push_str(", ");
             }
         }
 
+        let args = self.arguments_in_parameter_order(name, &args);
         self.generate_comma_separated_args_for_function_call_with_multiline(
-            &call.args,
+            &args,
             call.is_multiline,
             call_span,
         )?;
         self.output.push(')');
+        if !temporaries.is_empty() {
+            self.output.push_str(" }");
+        }
         Ok(())
     }
 
+    /// Named arguments are passed in parameter order, so when ones with side effects
+    /// (anything but variables, literals and function references) are written out of
+    /// that order, each of them is bound to a `__name` temporary in source order and
+    /// the temporary is passed instead, along with the positional arguments before them.
+    /// Returns the arguments and the temporaries.
+    fn bind_reordered_arguments(
+        &self,
+        name: &str,
+        args: &[Argument],
+    ) -> (Vec<Argument>, Vec<(String, LocatedExpr)>) {
+        let Some(params) = self.function_params.get(name) else {
+            return (args.to_vec(), Vec::new());
+        };
+        let has_side_effects = |expr: &LocatedExpr| {
+            !matches!(
+                expr.node,
+                Expr::Identifier(_) | Expr::Literal(_) | Expr::FunctionRef(_)
+            )
+        };
+        let reordered: Vec<usize> = args
+            .iter()
+            .filter_map(|arg| match arg {
+                Argument::Named(arg_name, expr, _) if has_side_effects(expr) => {
+                    params.iter().position(|param| param.name == *arg_name)
+                }
+                _ => None,
+            })
+            .collect();
+        if reordered.windows(2).all(|pair| pair[0] < pair[1]) {
+            return (args.to_vec(), Vec::new());
+        }
+
+        // Positional arguments come first, so they are bound as well
+        let mut temporaries = Vec::new();
+        let mut bind = |param_name: &str, expr: &LocatedExpr| {
+            let temporary = format!("_{}", param_name);
+            temporaries.push((temporary.clone(), expr.clone()));
+            Located::new(Expr::Identifier(temporary), expr.span.clone())
+        };
+        let mut positional = params.iter();
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                Argument::Bare(expr, comment) => match positional.next() {
+                    Some(param) if has_side_effects(expr) => {
+                        Argument::Bare(bind(&param.name, expr), comment.clone())
+                    }
+                    _ => arg.clone(),
+                },
+                Argument::Named(arg_name, expr, comment) if has_side_effects(expr) => {
+                    Argument::Named(arg_name.clone(), bind(arg_name, expr), comment.clone())
+                }
+                _ => arg.clone(),
+            })
+            .collect();
+        (args, temporaries)
+    }
+
+    /// Arrange the arguments of a local function call in parameter order,
+    /// resolving names and filling in default values for omitted parameters
+    fn arguments_in_parameter_order(&self, name: &str, args: &[Argument]) -> Vec<Argument> {
+        let Some(params) = self.function_params.get(name) else {
+            return args.to_vec();
+        };
+        let positional = args
+            .iter()
+            .filter(|arg| matches!(arg, Argument::Bare(_, _)))
+            .count();
+        let needs_binding = positional < params.len()
+            || args
+                .iter()
+                .any(|arg| matches!(arg, Argument::Named(..) | Argument::Shorthand(..)));
+        if !needs_binding {
+            return args.to_vec();
+        }
+
+        let mut bare_args = args
+            .iter()
+            .filter(|arg| matches!(arg, Argument::Bare(_, _)));
+        params
+            .iter()
+            .filter_map(|param| {
+                if let Some(arg) = bare_args.next() {
+                    return Some(arg.clone());
+                }
+                args.iter()
+                    .find_map(|arg| match arg {
+                        Argument::Named(arg_name, _, _) if *arg_name == param.name => {
+                            Some(arg.clone())
+                        }
+                        Argument::Shorthand(arg_name, comment) if *arg_name == param.name => {
+                            let span = param.param_type.span.clone();
+                            Some(Argument::Bare(
+                                Located::new(Expr::Identifier(arg_name.clone()), span),
+                                comment.clone(),
+                            ))
+                        }
+                        _ => None,
+                    })
+                    .or_else(|| {
+                        param.default_value.clone().map(|default_value| {
+                            Argument::Bare(default_value, ArgumentComment::default())
+                        })
+                    })
+            })
+            .collect()
+    }
+
     /// Generate code for imported function/constructor calls
     fn generate_imported_function_call(
         &mut self,
@@ -543,9 +1156,32 @@ impl CodeGenerator {
         // First argument is the object
         self.generate_expression(&method_call.object)?;
 
-        // Then the rest of the arguments
-        for arg in &method_call.args {
+        // Then the rest of the arguments, borrowing values passed by reference
+        for (i, arg) in method_call.args.iter().enumerate() {
             self.output.push_str(", ");
+            if resolution.borrowed_args.get(i).copied().unwrap_or(false) {
+                self.output.push('&');
+            }
+            self.generate_expression(arg)?;
+        }
+        self.output.push(')');
+        Ok(())
+    }
+
+    /// Generate code for a call to a member function of a data class
+    fn generate_member_function_call(
+        &mut self,
+        method_call: &MethodCallExpr,
+    ) -> Result<(), VeltranoError> {
+        self.generate_receiver(&method_call.object)?;
+        self.output.push('.');
+        self.output
+            .push_str(&camel_to_snake_case(&method_call.method));
+        self.output.push('(');
+        for (i, arg) in method_call.args.iter().enumerate() {
+            if i > 0 {
+                self.output.push_str(", ");
+            }
             self.generate_expression(arg)?;
         }
         self.output.push(')');
@@ -17,12 +17,15 @@ impl CodeGenerator {
     pub(super) fn generate_comma_separated_params(
         &mut self,
         params: &[Parameter],
-        include_bump: bool,
+        hidden_bump_params: &[String],
     ) {
         let mut first = true;
 
-        if include_bump {
-            self.output.push_str("bump: &'a bumpalo::Bump");
+        for bump_param in hidden_bump_params {
+            if !first {
+                self.output.push_str(", ");
+            }
+            self.output.push_str(bump_param);
             first = false;
         }
 
@@ -34,19 +37,23 @@ impl CodeGenerator {
             let snake_name = camel_to_snake_case(&param.name);
             self.output.push_str(&snake_name);
             self.output.push_str(": ");
-            self.generate_type(&param.param_type.node);
+            self.generate_parameter_type(&param.param_type.node);
             self.generate_inline_comment_as_block(&param.inline_comment);
         }
     }
 
     /// Generate multiline parameters for function declarations
-    pub(super) fn generate_multiline_params(&mut self, params: &[Parameter], include_bump: bool) {
+    pub(super) fn generate_multiline_params(
+        &mut self,
+        params: &[Parameter],
+        hidden_bump_params: &[String],
+    ) {
         self.output.push('\n');
         self.indent_level += 1;
 
-        if include_bump {
+        for bump_param in hidden_bump_params {
             self.indent();
-            self.output.push_str("bump: &'a bumpalo::Bump");
+            self.output.push_str(bump_param);
             if !params.is_empty() {
                 self.output.push(',');
             }
@@ -58,7 +65,7 @@ impl CodeGenerator {
             let snake_name = camel_to_snake_case(&param.name);
             self.output.push_str(&snake_name);
             self.output.push_str(": ");
-            self.generate_type(&param.param_type.node);
+            self.generate_parameter_type(&param.param_type.node);
 
             // Add comma if not the last parameter
             if i < params.len() - 1 {
@@ -0,0 +1,113 @@
+//! Embedded Rust code generation.
+//!
+//! Copies `rust { ... }` blocks and `rust<T>("...")` expressions into the output,
+//! converting references to Veltrano identifiers to their snake_case names.
+
+use super::CodeGenerator;
+use crate::ast::{Program, Stmt, StmtExt};
+use crate::rust_interop::camel_to_snake_case;
+
+impl CodeGenerator {
+    /// Collect every name declared in the program that embedded Rust code may refer to
+    pub(super) fn collect_veltrano_identifiers(&mut self, program: &Program) {
+        for stmt in &program.statements {
+            let _ = stmt.walk(&mut |stmt| {
+                match stmt {
+                    Stmt::FunDecl(fun_decl) => {
+                        self.veltrano_identifiers.insert(fun_decl.name.clone());
+                        for param in &fun_decl.params {
+                            self.veltrano_identifiers.insert(param.name.clone());
+                        }
+                    }
+                    Stmt::VarDecl(var_decl) => {
+                        self.veltrano_identifiers.insert(var_decl.name.clone());
+                    }
+                    Stmt::Destructure(destructure) => {
+                        self.veltrano_identifiers
+                            .extend(destructure.names.iter().cloned());
+                    }
+                    Stmt::DataClass(data_class) => {
+                        for field in &data_class.fields {
+                            self.veltrano_identifiers.insert(field.name.clone());
+                        }
+                    }
+                    _ => {}
+                }
+                Ok::<(), ()>(())
+            });
+        }
+    }
+
+    /// Generate a `rust { ... }` block line by line at the current indentation
+    pub(super) fn generate_rust_block(&mut self, code: &str) {
+        let code = self.convert_embedded_identifiers(code);
+        for line in code.lines() {
+            if !line.is_empty() {
+                self.indent();
+                self.output.push_str(line);
+            }
+            self.output.push('\n');
+        }
+    }
+
+    /// Rename Veltrano identifiers in Rust code, leaving literals and comments untouched
+    pub(super) fn convert_embedded_identifiers(&self, code: &str) -> String {
+        let chars: Vec<char> = code.chars().collect();
+        let mut result = String::with_capacity(code.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            let ch = chars[i];
+            let start = i;
+
+            if ch.is_alphabetic() || ch == '_' {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let identifier: String = chars[start..i].iter().collect();
+                if self.veltrano_identifiers.contains(&identifier) {
+                    result.push_str(&camel_to_snake_case(&identifier));
+                } else {
+                    result.push_str(&identifier);
+                }
+                continue;
+            }
+
+            match ch {
+                '"' => i = skip_quoted(&chars, i, '"'),
+                // Char literals; a quote followed by anything else starts a lifetime
+                '\'' if chars.get(i + 1) == Some(&'\\') || chars.get(i + 2) == Some(&'\'') => {
+                    i = skip_quoted(&chars, i, '\'')
+                }
+                '/' if chars.get(i + 1) == Some(&'/') => {
+                    while i < chars.len() && chars[i] != '\n' {
+                        i += 1;
+                    }
+                }
+                '/' if chars.get(i + 1) == Some(&'*') => {
+                    i += 2;
+                    while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                        i += 1;
+                    }
+                    i = (i + 2).min(chars.len());
+                }
+                _ => i += 1,
+            }
+            result.extend(&chars[start..i]);
+        }
+
+        result
+    }
+}
+
+/// Return the position just past the literal opened by the quote at `start`
+fn skip_quoted(chars: &[char], start: usize, quote: char) -> usize {
+    let mut i = start + 1;
+    while i < chars.len() && chars[i] != quote {
+        if chars[i] == '\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    (i + 1).min(chars.len())
+}
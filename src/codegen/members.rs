@@ -0,0 +1,300 @@
+//! Data class member generation.
+//!
+//! Emits member functions in an inherent `impl` block, implements the
+//! `std::ops` and `std::cmp` traits overloaded by `operator fun` members, and
+//! implements `Display` in Kotlin's `Person(name=Alice, age=30)` format or
+//! through an `override fun toString()`.
+
+use super::CodeGenerator;
+use crate::ast::*;
+use crate::error::VeltranoError;
+use crate::rust_interop::{camel_to_snake_case, RustType};
+use crate::types::VeltranoType;
+
+/// Binary arithmetic operator functions with the trait and trait method they implement
+const ARITHMETIC_TRAITS: &[(&str, &str, &str)] = &[
+    ("plus", "std::ops::Add", "add"),
+    ("minus", "std::ops::Sub", "sub"),
+    ("times", "std::ops::Mul", "mul"),
+    ("div", "std::ops::Div", "div"),
+    ("rem", "std::ops::Rem", "rem"),
+];
+
+/// Name an `override fun toString()` is emitted under, for the `Display` impl to call
+const DISPLAY_STRING_METHOD: &str = "displayString";
+
+impl CodeGenerator {
+    /// Generate the member functions of a data class, the operator traits they overload
+    /// and its `Display` impl; `lifetimes` are the class's lifetime parameters, if it has any
+    pub(super) fn generate_data_class_members(
+        &mut self,
+        data_class: &DataClassStmt,
+        derives: &[String],
+        lifetimes: &[String],
+    ) -> Result<(), VeltranoError> {
+        let generics = if lifetimes.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", lifetimes.join(", "))
+        };
+        let self_type = format!("{}{}", data_class.name, generics);
+
+        // Unlabeled references in members take the class's first lifetime
+        let saved_default_lifetime =
+            std::mem::replace(&mut self.default_lifetime, lifetimes.first().cloned());
+        let result = if data_class.methods.is_empty() {
+            Ok(())
+        } else {
+            self.generate_member_impls(data_class, derives, &generics, &self_type)
+        };
+        self.default_lifetime = saved_default_lifetime;
+        result?;
+
+        self.generate_display_impl(data_class, &generics, &self_type);
+        Ok(())
+    }
+
+    /// Implement `Display` through the `override fun toString()`, or by listing the
+    /// fields like Kotlin; fields without a `Display` impl use their `Debug` format
+    fn generate_display_impl(
+        &mut self,
+        data_class: &DataClassStmt,
+        generics: &str,
+        self_type: &str,
+    ) {
+        let overridden = data_class
+            .methods
+            .iter()
+            .any(|member| member.is_override && member.fun_decl.name == "toString");
+        let body = if overridden {
+            format!(
+                "    f.write_str(&self.{}())",
+                camel_to_snake_case(DISPLAY_STRING_METHOD)
+            )
+        } else {
+            let mut placeholders = Vec::new();
+            let mut values = String::new();
+            for field in &data_class.fields {
+                let mut rust_type = field.field_type.node.to_rust_type(&mut self.trait_checker);
+                while let RustType::Ref { inner, .. } = rust_type {
+                    rust_type = *inner;
+                }
+                let is_display = self
+                    .trait_checker
+                    .type_implements_trait(&rust_type, "Display")
+                    .unwrap_or(false);
+                // Labels use the Veltrano field names, as Kotlin's toString() does
+                let placeholder = if is_display { "{}" } else { "{:?}" };
+                placeholders.push(format!("{}={}", field.name, placeholder));
+                values.push_str(&format!(", self.{}", camel_to_snake_case(&field.name)));
+            }
+            format!(
+                "    write!(f, \"{}({})\"{})",
+                data_class.name,
+                placeholders.join(", "),
+                values
+            )
+        };
+
+        self.generate_trait_impl(
+            data_class,
+            &format!("std::fmt::Display for {}", self_type),
+            generics,
+            &[
+                "fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {".to_string(),
+                body,
+                "}".to_string(),
+            ],
+        );
+    }
+
+    fn generate_member_impls(
+        &mut self,
+        data_class: &DataClassStmt,
+        derives: &[String],
+        generics: &str,
+        self_type: &str,
+    ) -> Result<(), VeltranoError> {
+        self.generate_cfg_attributes(&data_class.annotations);
+        self.output
+            .push_str(&format!("impl{} {} {{\n", generics, self_type));
+        self.indent_level += 1;
+        for member in &data_class.methods {
+            if member.is_override {
+                // Inherent `to_string` would shadow the one `Display` provides
+                let mut fun_decl = member.fun_decl.clone();
+                fun_decl.name = DISPLAY_STRING_METHOD.to_string();
+                self.generate_function(&fun_decl, true)?;
+            } else {
+                self.generate_function(&member.fun_decl, true)?;
+            }
+        }
+        self.indent_level -= 1;
+        self.output.push_str("}\n\n");
+
+        let class_type = VeltranoType::custom(data_class.name.clone());
+        let borrowed_self = self.rendered_type(&class_type);
+        let operator = |name: &str| {
+            data_class
+                .methods
+                .iter()
+                .find(|member| member.is_operator && member.fun_decl.name == name)
+                .map(|member| &member.fun_decl)
+        };
+
+        for (function, trait_name, trait_method) in ARITHMETIC_TRAITS {
+            if let Some(fun_decl) = operator(function) {
+                let param = &fun_decl.params[0];
+                let param_name = camel_to_snake_case(&param.name);
+                let rhs = self.rendered_type(&param.param_type.node);
+                let output = self.rendered_return_type(fun_decl);
+                self.generate_trait_impl(
+                    data_class,
+                    &format!("{}<{}> for {}", trait_name, rhs, borrowed_self),
+                    generics,
+                    &[
+                        format!("type Output = {};", output),
+                        format!(
+                            "fn {}(self, {}: {}) -> {} {{",
+                            trait_method, param_name, rhs, output
+                        ),
+                        format!("    self.{}({})", function, param_name),
+                        "}".to_string(),
+                    ],
+                );
+            }
+        }
+
+        if let Some(fun_decl) = operator("unaryMinus") {
+            let output = self.rendered_return_type(fun_decl);
+            self.generate_trait_impl(
+                data_class,
+                &format!("std::ops::Neg for {}", borrowed_self),
+                generics,
+                &[
+                    format!("type Output = {};", output),
+                    format!("fn neg(self) -> {} {{", output),
+                    "    self.unary_minus()".to_string(),
+                    "}".to_string(),
+                ],
+            );
+        }
+
+        // Comparison traits take `other` by reference; Copy classes pass it on by value
+        let (other_type, other) = if Self::referenced_type(&borrowed_self).is_some() {
+            (borrowed_self.clone(), "other")
+        } else {
+            (format!("&{}", borrowed_self), "*other")
+        };
+
+        // PartialEq comes from `equals`, or from `compareTo` unless it is derived
+        let equality = if operator("equals").is_some() {
+            Some(format!("self.equals({})", other))
+        } else if operator("compareTo").is_some() && !derives.iter().any(|d| d == "PartialEq") {
+            Some(format!("self.compare_to({}) == 0", other))
+        } else {
+            None
+        };
+        if let Some(body) = equality {
+            self.generate_trait_impl(
+                data_class,
+                &format!("PartialEq for {}", self_type),
+                generics,
+                &[
+                    format!("fn eq(&self, other: {}) -> bool {{", other_type),
+                    format!("    {}", body),
+                    "}".to_string(),
+                ],
+            );
+        }
+
+        if operator("compareTo").is_some() {
+            self.generate_trait_impl(
+                data_class,
+                &format!("PartialOrd for {}", self_type),
+                generics,
+                &[
+                    format!(
+                        "fn partial_cmp(&self, other: {}) -> Option<std::cmp::Ordering> {{",
+                        other_type
+                    ),
+                    format!("    Some(self.compare_to({}).cmp(&0))", other),
+                    "}".to_string(),
+                ],
+            );
+        }
+
+        // Index can only be implemented when `get` returns a reference
+        if let Some(fun_decl) = operator("get") {
+            let output = self.rendered_return_type(fun_decl);
+            if let Some(element) = Self::referenced_type(&output) {
+                let param = &fun_decl.params[0];
+                let param_name = camel_to_snake_case(&param.name);
+                let index_type = self.rendered_type(&param.param_type.node);
+                self.generate_trait_impl(
+                    data_class,
+                    &format!("std::ops::Index<{}> for {}", index_type, self_type),
+                    generics,
+                    &[
+                        format!("type Output = {};", element),
+                        format!(
+                            "fn index(&self, {}: {}) -> &{} {{",
+                            param_name, index_type, element
+                        ),
+                        format!("    self.get({})", param_name),
+                        "}".to_string(),
+                    ],
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Emit `impl<generics> header { lines }` with each line indented once
+    fn generate_trait_impl(
+        &mut self,
+        data_class: &DataClassStmt,
+        header: &str,
+        generics: &str,
+        lines: &[String],
+    ) {
+        self.generate_cfg_attributes(&data_class.annotations);
+        self.output
+            .push_str(&format!("impl{} {} {{\n", generics, header));
+        self.indent_level += 1;
+        for line in lines {
+            self.indent();
+            self.output.push_str(line);
+            self.output.push('\n');
+        }
+        self.indent_level -= 1;
+        self.output.push_str("}\n\n");
+    }
+
+    /// Rust spelling of a Veltrano type in the current lifetime context
+    fn rendered_type(&mut self, veltrano_type: &VeltranoType) -> String {
+        let saved_output = std::mem::take(&mut self.output);
+        self.generate_type(veltrano_type);
+        std::mem::replace(&mut self.output, saved_output)
+    }
+
+    fn rendered_return_type(&mut self, fun_decl: &FunDeclStmt) -> String {
+        match &fun_decl.return_type {
+            Some(return_type) => self.rendered_type(&return_type.node),
+            None => "()".to_string(),
+        }
+    }
+
+    /// Type behind a shared reference type such as `&T` or `&'a T`
+    fn referenced_type(rust_type: &str) -> Option<&str> {
+        let inner = rust_type.strip_prefix('&')?;
+        if inner.starts_with("mut ") {
+            return None;
+        }
+        match inner.strip_prefix('\'') {
+            Some(labeled) => labeled.split_once(' ').map(|(_, rest)| rest),
+            None => Some(inner),
+        }
+    }
+}
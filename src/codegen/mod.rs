@@ -5,6 +5,8 @@
 mod comments;
 mod expressions;
 mod formatting;
+mod inline_rust;
+mod members;
 mod statements;
 mod types;
 mod utils;
@@ -15,7 +17,8 @@ use crate::ast::*;
 use crate::config::Config;
 use crate::error::{SourceLocation, VeltranoError};
 use crate::rust_interop::RustInteropRegistry;
-use crate::type_checker::MethodResolution;
+use crate::type_checker::{MethodResolution, OperatorResolution, VeltranoTypeChecker};
+use crate::types::VeltranoType;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
@@ -101,10 +104,30 @@ pub struct CodeGenerator {
     local_functions_with_bump: HashSet<String>, // Functions that need bump parameter
     data_classes_with_lifetime: HashSet<String>, // Track data classes that need lifetime parameters
     data_classes: HashSet<String>,              // Track all data classes
-    generating_bump_function: bool, // Track when generating function with bump parameter
+    data_class_fields: HashMap<String, Vec<DataClassField>>, // Data class fields in declaration order
+    data_class_lifetime_counts: HashMap<String, usize>, // Number of lifetime parameters of each data class
+    data_class_derives: HashMap<String, Vec<String>>,   // Traits derived by each data class
+    hidden_bump_functions: HashSet<String>, // Top-level functions that take a hidden bump parameter
+    has_class_analysis: bool, // Whether the derives and hidden bumps above came from the type checker
+    function_lifetime_params: HashMap<String, Vec<String>>, // Lifetime labels of labeled functions
+    function_bump_labels: HashMap<String, HashSet<String>>, // Labels whose arena each labeled function allocates into
+    used_bump_labels: HashSet<String>, // Labels whose bump parameter the current function body uses
+    function_params: HashMap<String, Vec<Parameter>>, // Parameters of local functions, for named arguments and defaults
+    member_function_params: HashMap<(String, String), Vec<Parameter>>, // (data class, member) -> parameters, for member references
+    default_lifetime: Option<String>, // Lifetime given to unlabeled references in the current context
+    local_lifetime: Option<String>,   // Own lifetime label of the function being generated
+    lifetime_labels: Vec<String>,     // Lifetime labels (each with a bump parameter) in scope
+    bump_target: Option<String>,      // Label whose arena `.bumpRef()` allocates into
+    return_bump_target: Option<String>, // Label of references returned by the current function
+    uses_local_bump: bool,            // Whether a labeled function body needs its own arena
+    in_member_function: bool,         // Whether `this` refers to the receiver of a member function
     trait_checker: RustInteropRegistry, // For trait-based type checking
     config: Config,
     method_resolutions: HashMap<usize, MethodResolution>, // Method call ID -> resolved import
+    node_types: HashMap<usize, VeltranoType>, // Field access/destructuring ID -> operand type
+    lifetime_bindings: HashMap<usize, Vec<Option<String>>>, // Call ID -> caller label per callee label
+    operator_resolutions: HashMap<usize, OperatorResolution>, // Operator expression ID -> resolved `operator fun`
+    veltrano_identifiers: HashSet<String>, // Declared names renamed inside embedded Rust code
 }
 
 impl CodeGenerator {
@@ -117,10 +140,30 @@ impl CodeGenerator {
             local_functions_with_bump: HashSet::new(),
             data_classes_with_lifetime: HashSet::new(),
             data_classes: HashSet::new(),
-            generating_bump_function: false,
+            data_class_fields: HashMap::new(),
+            data_class_lifetime_counts: HashMap::new(),
+            data_class_derives: HashMap::new(),
+            hidden_bump_functions: HashSet::new(),
+            has_class_analysis: false,
+            function_bump_labels: HashMap::new(),
+            used_bump_labels: HashSet::new(),
+            function_lifetime_params: HashMap::new(),
+            function_params: HashMap::new(),
+            member_function_params: HashMap::new(),
+            default_lifetime: None,
+            local_lifetime: None,
+            lifetime_labels: Vec::new(),
+            bump_target: None,
+            return_bump_target: None,
+            uses_local_bump: false,
+            in_member_function: false,
             trait_checker: RustInteropRegistry::new(),
             config,
             method_resolutions: HashMap::new(),
+            node_types: HashMap::new(),
+            lifetime_bindings: HashMap::new(),
+            operator_resolutions: HashMap::new(),
+            veltrano_identifiers: HashSet::new(),
         }
     }
 
@@ -129,41 +172,148 @@ impl CodeGenerator {
         self.method_resolutions = resolutions;
     }
 
+    /// Set operand types of field accesses and destructurings from the type checker
+    pub fn set_node_types(&mut self, node_types: HashMap<usize, VeltranoType>) {
+        self.node_types = node_types;
+    }
+
+    /// Set the caller lifetimes bound by calls to labeled functions from the type checker
+    pub fn set_lifetime_bindings(
+        &mut self,
+        lifetime_bindings: HashMap<usize, Vec<Option<String>>>,
+    ) {
+        self.lifetime_bindings = lifetime_bindings;
+    }
+
+    /// Set the operator expressions resolved through `operator fun` members from the type checker
+    pub fn set_operator_resolutions(
+        &mut self,
+        operator_resolutions: HashMap<usize, OperatorResolution>,
+    ) {
+        self.operator_resolutions = operator_resolutions;
+    }
+
+    /// Set the `#[derive]` list inferred for each data class by the type checker
+    pub fn set_data_class_derives(&mut self, data_class_derives: HashMap<String, Vec<String>>) {
+        self.trait_checker
+            .register_derived_traits(&data_class_derives);
+        self.data_class_derives = data_class_derives;
+        self.has_class_analysis = true;
+    }
+
+    /// Set the top-level functions that take a hidden bump parameter from the type checker
+    pub fn set_hidden_bump_functions(&mut self, hidden_bump_functions: HashSet<String>) {
+        self.hidden_bump_functions = hidden_bump_functions;
+        self.has_class_analysis = true;
+    }
+
     pub fn generate(&mut self, program: &Program) -> Result<String, VeltranoError> {
+        // Without the type checker's results, infer the derives and hidden bumps here
+        if !self.has_class_analysis {
+            let data_classes: Vec<&DataClassStmt> = program
+                .statements
+                .iter()
+                .filter_map(|statement| match statement {
+                    Stmt::DataClass(data_class) => Some(data_class),
+                    _ => None,
+                })
+                .collect();
+            self.data_class_derives = self
+                .trait_checker
+                .register_data_class_derives(&data_classes);
+            self.hidden_bump_functions =
+                VeltranoTypeChecker::hidden_bump_functions(program, &self.data_class_derives);
+        }
+
         // First pass: collect all locally defined function names and data classes with lifetimes
         for stmt in &program.statements {
             match stmt {
                 Stmt::FunDecl(fun_decl) => {
                     self.local_functions.insert(fun_decl.name.clone());
-                    if fun_decl.has_hidden_bump {
+                    self.function_params
+                        .insert(fun_decl.name.clone(), fun_decl.params.clone());
+                    if self.hidden_bump_functions.contains(&fun_decl.name) {
+                        self.local_functions_with_bump.insert(fun_decl.name.clone());
+                    }
+                    if !fun_decl.lifetime_params.is_empty() {
                         self.local_functions_with_bump.insert(fun_decl.name.clone());
+                        self.function_lifetime_params
+                            .insert(fun_decl.name.clone(), fun_decl.lifetime_params.clone());
+                        self.function_bump_labels.insert(
+                            fun_decl.name.clone(),
+                            fun_decl.lifetime_params.iter().cloned().collect(),
+                        );
                     }
                 }
                 Stmt::DataClass(data_class) => {
                     // Track all data classes
                     self.data_classes.insert(data_class.name.clone());
+                    self.data_class_fields
+                        .insert(data_class.name.clone(), data_class.fields.clone());
+                    for member in &data_class.methods {
+                        self.member_function_params.insert(
+                            (data_class.name.clone(), member.fun_decl.name.clone()),
+                            member.fun_decl.params.clone(),
+                        );
+                    }
 
                     // Check if this data class needs lifetime parameters
-                    let needs_lifetime = data_class
-                        .fields
-                        .iter()
-                        .any(|field| self.type_needs_lifetime(&field.field_type.node));
+                    let needs_lifetime = !data_class.lifetime_params.is_empty()
+                        || data_class
+                            .fields
+                            .iter()
+                            .any(|field| self.type_needs_lifetime(&field.field_type.node));
                     if needs_lifetime {
                         self.data_classes_with_lifetime
                             .insert(data_class.name.clone());
+                        self.data_class_lifetime_counts.insert(
+                            data_class.name.clone(),
+                            data_class.lifetime_params.len().max(1),
+                        );
                     }
                 }
                 _ => {}
             }
         }
 
+        self.collect_veltrano_identifiers(program);
+
         // Skip bumpalo import - use fully qualified names instead
 
+        // Labeled functions take a bump only for the labels they allocate into, which
+        // depends on the bumps their callees take: starting from every label, generate
+        // the program until no function drops another one
+        if !self.function_bump_labels.is_empty() {
+            loop {
+                let bump_labels = self.function_bump_labels.clone();
+                let saved_output = std::mem::take(&mut self.output);
+                let result = self.generate_program_items(program);
+                self.output = saved_output;
+                result?;
+                if self.function_bump_labels == bump_labels {
+                    break;
+                }
+            }
+        }
+
         // Second pass: generate code
+        self.generate_program_items(program)?;
+        Ok(self.output.clone())
+    }
+
+    /// Generate the top-level statements; `@Test` functions are gathered into a test module at the end
+    fn generate_program_items(&mut self, program: &Program) -> Result<(), VeltranoError> {
+        let mut tests = Vec::new();
         for stmt in &program.statements {
-            self.generate_statement(stmt)?;
+            match stmt {
+                Stmt::FunDecl(fun_decl) if fun_decl.is_test() => tests.push(fun_decl),
+                _ => self.generate_statement(stmt)?,
+            }
         }
-        Ok(self.output.clone())
+        if !tests.is_empty() {
+            self.generate_test_module(&tests)?;
+        }
+        Ok(())
     }
 
     fn check_function_needs_bump(&self, fun_decl: &FunDeclStmt) -> bool {
@@ -175,13 +325,20 @@ impl CodeGenerator {
         // Also check if this function calls other functions that use bump
         let mut uses_bump = false;
         let _ = fun_decl.body.walk_expressions(&mut |expr| {
-            if let Expr::Call(call) = &expr.node {
-                if let Expr::Identifier(name) = &call.callee.node {
-                    if self.local_functions_with_bump.contains(name) {
-                        uses_bump = true;
-                        return Err(()); // Early exit
-                    }
+            let callee = match &expr.node {
+                Expr::Call(call) => match &call.callee.node {
+                    Expr::Identifier(name) => Some(name),
+                    _ => None,
+                },
+                // References to such functions pass the bump along
+                Expr::FunctionRef(function_ref) if function_ref.receiver.is_none() => {
+                    Some(&function_ref.name)
                 }
+                _ => None,
+            };
+            if callee.is_some_and(|name| self.local_functions_with_bump.contains(name)) {
+                uses_bump = true;
+                return Err(()); // Early exit
             }
             Ok::<(), ()>(())
         });
@@ -2,10 +2,13 @@
 //!
 //! Handles declarations, control flow, data classes, and imports.
 
+use super::types::DEFAULT_LIFETIME;
+use super::utils::INDENT_STR;
 use super::CodeGenerator;
 use crate::ast::*;
 use crate::error::VeltranoError;
 use crate::rust_interop::camel_to_snake_case;
+use crate::types::{TypeConstructor, VeltranoType};
 
 impl CodeGenerator {
     /// Generate code for any statement type
@@ -34,6 +37,16 @@ impl CodeGenerator {
             Stmt::VarDecl(var_decl) => {
                 self.generate_var_declaration(var_decl)?;
             }
+            Stmt::Destructure(destructure) => {
+                self.generate_destructuring_declaration(destructure)?;
+            }
+            Stmt::Assign(assign) => {
+                self.indent();
+                self.generate_expression(&assign.target)?;
+                self.output.push_str(" = ");
+                self.generate_expression(&assign.value)?;
+                self.output.push_str(";\n");
+            }
             Stmt::FunDecl(fun_decl) => {
                 self.generate_function_declaration(fun_decl)?;
             }
@@ -48,7 +61,12 @@ impl CodeGenerator {
                 self.output.push_str("return");
                 if let Some(expr) = expr {
                     self.output.push(' ');
-                    self.generate_expression(expr)?;
+                    // Returned references are allocated in the arena of the returned lifetime
+                    let saved_target =
+                        std::mem::replace(&mut self.bump_target, self.return_bump_target.clone());
+                    let result = self.generate_expression(expr);
+                    self.bump_target = saved_target;
+                    result?;
                 }
                 self.output.push(';');
                 self.output.push('\n');
@@ -63,11 +81,25 @@ impl CodeGenerator {
                 self.indent();
                 self.output.push_str("}\n");
             }
+            Stmt::Unsafe(statements) => {
+                self.indent();
+                self.output.push_str("unsafe {\n");
+                self.indent_level += 1;
+                for stmt in statements {
+                    self.generate_statement(stmt)?;
+                }
+                self.indent_level -= 1;
+                self.indent();
+                self.output.push_str("}\n");
+            }
             Stmt::Comment(comment) => {
                 if self.config.preserve_comments {
                     self.generate_comment(comment);
                 }
             }
+            Stmt::RustBlock(code) => {
+                self.generate_rust_block(code);
+            }
             Stmt::Import(import) => {
                 // Track the import for later use
                 let key = import
@@ -79,7 +111,14 @@ impl CodeGenerator {
                 // Don't generate any Rust code for imports
             }
             Stmt::DataClass(data_class) => {
-                self.generate_data_class(data_class);
+                self.generate_data_class(data_class)?;
+            }
+            Stmt::ExternFun(extern_fun) => {
+                self.generate_extern_function(extern_fun);
+            }
+            Stmt::Annotated(annotated) => {
+                self.generate_rust_attributes(&annotated.annotations);
+                self.generate_statement(&annotated.stmt)?;
             }
         }
         Ok(())
@@ -94,9 +133,18 @@ impl CodeGenerator {
         let snake_name = camel_to_snake_case(&var_decl.name);
         self.output.push_str(&snake_name);
 
+        // Closures passing a bump along have no nameable type, so Rust infers it
+        let is_bump_closure = matches!(
+            var_decl.initializer.as_ref().map(|init| &init.node),
+            Some(Expr::FunctionRef(function_ref))
+                if function_ref.receiver.is_none()
+                    && self.local_functions_with_bump.contains(&function_ref.name)
+        );
         if let Some(type_annotation) = &var_decl.type_annotation {
-            self.output.push_str(": ");
-            self.generate_type(&type_annotation.node);
+            if !is_bump_closure {
+                self.output.push_str(": ");
+                self.generate_type(&type_annotation.node);
+            }
         }
 
         if let Some(initializer) = &var_decl.initializer {
@@ -105,7 +153,16 @@ impl CodeGenerator {
             // Collect all comments from method chain if this is a method call
             let method_chain_comments = self.collect_method_chain_comments(initializer);
 
-            self.generate_expression(initializer)?;
+            // A labeled declared type selects the arena for `.bumpRef()`
+            let target = var_decl
+                .type_annotation
+                .as_ref()
+                .and_then(|type_annotation| type_annotation.node.reference_lifetime())
+                .cloned();
+            let saved_target = std::mem::replace(&mut self.bump_target, target);
+            let result = self.generate_expression(initializer);
+            self.bump_target = saved_target;
+            result?;
             self.output.push(';');
 
             // Generate all method chain comments after semicolon
@@ -130,31 +187,220 @@ impl CodeGenerator {
         Ok(())
     }
 
+    /// Generate code for destructuring declarations as a `let` pattern
+    fn generate_destructuring_declaration(
+        &mut self,
+        destructure: &DestructureStmt,
+    ) -> Result<(), VeltranoError> {
+        self.indent();
+        self.output.push_str("let ");
+
+        let init_type = self.node_types.get(&destructure.id).cloned();
+        let pattern = match init_type {
+            Some(init_type) => self.destructuring_pattern(&destructure.names, &init_type),
+            None => Self::tuple_pattern(
+                destructure
+                    .names
+                    .iter()
+                    .map(|name| Self::binding(name, false)),
+                false,
+            ),
+        };
+        self.output.push_str(&pattern);
+
+        self.output.push_str(" = ");
+        self.generate_expression(&destructure.initializer)?;
+        self.output.push_str(";\n");
+        Ok(())
+    }
+
+    /// Build the Rust pattern for destructuring a value of the given type.
+    /// Values reached through a reference use a `&` pattern, binding each
+    /// component by copy where possible and by `ref` otherwise.
+    fn destructuring_pattern(&mut self, names: &[String], init_type: &VeltranoType) -> String {
+        let (target, borrowed) = match &init_type.constructor {
+            TypeConstructor::Ref | TypeConstructor::Own => (
+                init_type
+                    .inner()
+                    .cloned()
+                    .unwrap_or_else(|| init_type.clone()),
+                init_type.constructor == TypeConstructor::Ref,
+            ),
+            TypeConstructor::Custom(_) => (init_type.clone(), true),
+            _ => (init_type.clone(), false),
+        };
+
+        let prefix = if borrowed { "&" } else { "" };
+        match &target.constructor {
+            TypeConstructor::Custom(class_name) => {
+                let fields = self
+                    .data_class_fields
+                    .get(class_name)
+                    .cloned()
+                    .unwrap_or_default();
+                let mut parts = Vec::new();
+                for (name, field) in names.iter().zip(&fields) {
+                    if name == "_" {
+                        continue;
+                    }
+                    let by_ref = borrowed
+                        && field
+                            .field_type
+                            .node
+                            .borrowed_component_type(&mut self.trait_checker)
+                            .is_some();
+                    let field_name = camel_to_snake_case(&field.name);
+                    let binding = Self::binding(name, by_ref);
+                    if camel_to_snake_case(name) == field_name {
+                        parts.push(binding);
+                    } else {
+                        parts.push(format!("{}: {}", field_name, binding));
+                    }
+                }
+                if parts.len() < fields.len() {
+                    parts.push("..".to_string());
+                }
+                format!("{}{} {{ {} }}", prefix, class_name, parts.join(", "))
+            }
+            _ => {
+                let bindings: Vec<String> = names
+                    .iter()
+                    .zip(&target.args)
+                    .map(|(name, component)| {
+                        let by_ref = borrowed
+                            && component
+                                .borrowed_component_type(&mut self.trait_checker)
+                                .is_some();
+                        Self::binding(name, by_ref)
+                    })
+                    .collect();
+                let partial = bindings.len() < target.args.len();
+                format!(
+                    "{}{}",
+                    prefix,
+                    Self::tuple_pattern(bindings.into_iter(), partial)
+                )
+            }
+        }
+    }
+
+    /// Format a tuple pattern, with a trailing `..` when components are omitted
+    fn tuple_pattern(bindings: impl Iterator<Item = String>, partial: bool) -> String {
+        let mut parts: Vec<String> = bindings.collect();
+        if partial {
+            parts.push("..".to_string());
+        }
+        format!("({})", parts.join(", "))
+    }
+
+    /// Format a single pattern binding (`_` stays a wildcard)
+    fn binding(name: &str, by_ref: bool) -> String {
+        if name == "_" {
+            "_".to_string()
+        } else if by_ref {
+            format!("ref {}", camel_to_snake_case(name))
+        } else {
+            camel_to_snake_case(name)
+        }
+    }
+
     /// Generate code for function declarations
     pub(super) fn generate_function_declaration(
         &mut self,
         fun_decl: &FunDeclStmt,
     ) -> Result<(), VeltranoError> {
+        self.generate_function(fun_decl, false)
+    }
+
+    /// Generate a function; member functions are public and take `&self`
+    pub(super) fn generate_function(
+        &mut self,
+        fun_decl: &FunDeclStmt,
+        is_member: bool,
+    ) -> Result<(), VeltranoError> {
+        self.generate_rust_attributes(&fun_decl.annotations);
+        if fun_decl.is_test() {
+            self.indent();
+            self.output.push_str("#[test]\n");
+        }
+        let snake_name = camel_to_snake_case(&fun_decl.name);
+        let c_export = fun_decl.annotations.iter().find(|a| a.name == "CExport");
+        if let Some(annotation) = c_export {
+            // Exported under the Veltrano name unless a symbol is given
+            let symbol = match annotation.args.first() {
+                Some(AnnotationArg::String(symbol)) => symbol,
+                _ => &fun_decl.name,
+            };
+            self.indent();
+            if *symbol == snake_name {
+                self.output.push_str("#[no_mangle]\n");
+            } else {
+                self.output
+                    .push_str(&format!("#[export_name = \"{}\"]\n", symbol));
+            }
+        }
         self.indent();
+        if is_member || c_export.is_some() {
+            self.output.push_str("pub ");
+        }
+        if fun_decl.is_async {
+            self.output.push_str("async ");
+        }
+        if c_export.is_some() {
+            self.output.push_str("extern \"C\" ");
+        }
         self.output.push_str("fn ");
-        let snake_name = camel_to_snake_case(&fun_decl.name);
         self.output.push_str(&snake_name);
 
-        // Add lifetime parameter if this function has a hidden bump parameter
-        if fun_decl.has_hidden_bump {
+        // Lifetime parameters, each with its own bump allocator parameter
+        let lifetime_params = &fun_decl.lifetime_params;
+        let has_hidden_bump = !is_member && self.hidden_bump_functions.contains(&fun_decl.name);
+        let mut hidden_bump_params: Vec<String> = if !lifetime_params.is_empty() {
+            let lifetimes: Vec<String> = lifetime_params
+                .iter()
+                .map(|label| format!("'{}", label))
+                .collect();
+            self.output.push('<');
+            self.output.push_str(&lifetimes.join(", "));
+            self.output.push('>');
+            self.bump_labels(&fun_decl.name, lifetime_params)
+                .iter()
+                .map(|label| format!("{}: &'{} bumpalo::Bump", Self::bump_name(label), label))
+                .collect()
+        } else if has_hidden_bump {
+            // Add lifetime parameter if this function has a hidden bump parameter
             self.output.push_str("<'a>");
-            self.generating_bump_function = true;
+            vec![format!("bump: &{} bumpalo::Bump", DEFAULT_LIFETIME)]
+        } else {
+            vec![]
+        };
+        if is_member {
+            hidden_bump_params.insert(0, "&self".to_string());
         }
 
+        // Members keep the lifetime of their class's impl block
+        let default_lifetime = if !lifetime_params.is_empty() {
+            lifetime_params.first().map(|label| format!("'{}", label))
+        } else if has_hidden_bump {
+            Some(DEFAULT_LIFETIME.to_string())
+        } else if is_member {
+            self.default_lifetime.clone()
+        } else {
+            None
+        };
+        let saved_default_lifetime =
+            std::mem::replace(&mut self.default_lifetime, default_lifetime);
+        let saved_in_member_function = std::mem::replace(&mut self.in_member_function, is_member);
+
         self.output.push('(');
 
         // Check if we should use multiline formatting for parameters
         let use_multiline = fun_decl.params.iter().any(|p| p.inline_comment.is_some());
 
         if use_multiline && !fun_decl.params.is_empty() {
-            self.generate_multiline_params(&fun_decl.params, fun_decl.has_hidden_bump);
+            self.generate_multiline_params(&fun_decl.params, &hidden_bump_params);
         } else {
-            self.generate_comma_separated_params(&fun_decl.params, fun_decl.has_hidden_bump);
+            self.generate_comma_separated_params(&fun_decl.params, &hidden_bump_params);
         }
 
         self.output.push(')');
@@ -166,39 +412,129 @@ impl CodeGenerator {
 
         self.output.push(' ');
 
-        // Special handling for main function: only initialize bump allocator if needed
-        if fun_decl.name == "main" {
-            self.output.push_str("{\n");
-            self.indent_level += 1;
+        // References in a labeled function's body are inferred by Rust; the
+        // function's own label refers to its local arena
+        let saved_local_lifetime = self.local_lifetime.replace(fun_decl.name.clone());
+        let saved_lifetime_labels =
+            std::mem::replace(&mut self.lifetime_labels, lifetime_params.clone());
+        let saved_return_bump_target = std::mem::replace(
+            &mut self.return_bump_target,
+            fun_decl
+                .return_type
+                .as_ref()
+                .and_then(|return_type| return_type.node.reference_lifetime())
+                .or(lifetime_params.first())
+                .cloned(),
+        );
+        let saved_uses_local_bump = std::mem::replace(&mut self.uses_local_bump, false);
+        let saved_used_bump_labels = std::mem::take(&mut self.used_bump_labels);
+        if !lifetime_params.is_empty() {
+            self.default_lifetime = None;
+        }
 
-            // Check if bump allocation is actually used in the main function body
-            let needs_bump = self.check_function_needs_bump(fun_decl);
-            if needs_bump {
-                self.indent();
-                self.output.push_str("let bump = &bumpalo::Bump::new();\n");
+        // Special handling for main and tests: only initialize bump allocator if needed
+        let result = if fun_decl.is_entry_point() {
+            self.generate_main_body(fun_decl)
+        } else {
+            let body_start = self.output.len();
+            let result = self.generate_statement(&fun_decl.body);
+            if self.uses_local_bump {
+                self.insert_local_bump(body_start);
             }
-
-            // Generate the body content but skip the outer braces since we're handling them
-            if let Stmt::Block(statements) = fun_decl.body.as_ref() {
-                for stmt in statements {
-                    self.generate_statement(stmt)?;
-                }
-            } else {
-                self.generate_statement(&fun_decl.body)?;
+            result
+        };
+
+        self.default_lifetime = saved_default_lifetime;
+        self.in_member_function = saved_in_member_function;
+        self.local_lifetime = saved_local_lifetime;
+        self.lifetime_labels = saved_lifetime_labels;
+        self.return_bump_target = saved_return_bump_target;
+        self.uses_local_bump = saved_uses_local_bump;
+        let used_bump_labels =
+            std::mem::replace(&mut self.used_bump_labels, saved_used_bump_labels);
+        if let Some(bump_labels) = self.function_bump_labels.get_mut(&fun_decl.name) {
+            bump_labels.retain(|label| used_bump_labels.contains(label));
+            if bump_labels.is_empty() {
+                self.local_functions_with_bump.remove(&fun_decl.name);
             }
+        }
+        result
+    }
+
+    /// Generate the body of `main` or a test, creating the bump allocator only if it is used
+    fn generate_main_body(&mut self, fun_decl: &FunDeclStmt) -> Result<(), VeltranoError> {
+        self.output.push_str("{\n");
+        self.indent_level += 1;
 
-            self.indent_level -= 1;
+        // Check if bump allocation is actually used in the main function body
+        let needs_bump = self.check_function_needs_bump(fun_decl);
+        if needs_bump {
             self.indent();
-            self.output.push_str("}\n");
+            self.output.push_str("let bump = &bumpalo::Bump::new();\n");
+        }
+
+        // Generate the body content but skip the outer braces since we're handling them
+        if let Stmt::Block(statements) = fun_decl.body.as_ref() {
+            for stmt in statements {
+                self.generate_statement(stmt)?;
+            }
         } else {
             self.generate_statement(&fun_decl.body)?;
         }
 
-        // Reset bump function flag
-        self.generating_bump_function = false;
+        self.indent_level -= 1;
+        self.indent();
+        self.output.push_str("}\n");
         Ok(())
     }
 
+    /// Create the local arena of a labeled function at the start of its body
+    fn insert_local_bump(&mut self, body_start: usize) {
+        let Some(offset) = self.output[body_start..].find('\n') else {
+            return;
+        };
+        let indent = INDENT_STR.repeat(self.indent_level + 1);
+        self.output.insert_str(
+            body_start + offset + 1,
+            &format!("{}let bump = &bumpalo::Bump::new();\n", indent),
+        );
+    }
+
+    /// Name of the bump allocator parameter for a lifetime label
+    pub(super) fn bump_name(label: &str) -> String {
+        format!("__bump_{}", label)
+    }
+
+    /// The labels of a labeled function that take a bump parameter: those whose
+    /// arena it allocates into, directly or through the functions it calls
+    pub(super) fn bump_labels(&self, name: &str, lifetime_params: &[String]) -> Vec<String> {
+        match self.function_bump_labels.get(name) {
+            Some(bump_labels) => lifetime_params
+                .iter()
+                .filter(|label| bump_labels.contains(*label))
+                .cloned()
+                .collect(),
+            None => lifetime_params.to_vec(),
+        }
+    }
+
+    /// Bump allocator expression for allocating into `label`: its parameter
+    /// when the label is in scope, otherwise the local arena
+    pub(super) fn bump_for(&mut self, label: Option<&String>) -> String {
+        match label {
+            Some(label) if self.lifetime_labels.contains(label) => {
+                self.used_bump_labels.insert(label.clone());
+                Self::bump_name(label)
+            }
+            _ => {
+                if !self.lifetime_labels.is_empty() {
+                    self.uses_local_bump = true;
+                }
+                "bump".to_string()
+            }
+        }
+    }
+
     /// Generate code for if statements
     fn generate_if_statement(&mut self, if_stmt: &IfStmt) -> Result<(), VeltranoError> {
         self.indent();
@@ -234,21 +570,48 @@ impl CodeGenerator {
     }
 
     /// Generate code for data class declarations
-    pub(super) fn generate_data_class(&mut self, data_class: &DataClassStmt) {
-        // Check if any fields are reference types
-        let needs_lifetime = data_class
-            .fields
-            .iter()
-            .any(|field| self.type_needs_lifetime(&field.field_type.node));
-
-        self.indent();
-        self.output.push_str("#[derive(Debug, Clone)]\n");
+    pub(super) fn generate_data_class(
+        &mut self,
+        data_class: &DataClassStmt,
+    ) -> Result<(), VeltranoError> {
+        // Check if the class declares lifetimes or any fields are reference types
+        let needs_lifetime = !data_class.lifetime_params.is_empty()
+            || data_class
+                .fields
+                .iter()
+                .any(|field| self.type_needs_lifetime(&field.field_type.node));
+        let lifetimes: Vec<String> = if data_class.lifetime_params.is_empty() {
+            vec![DEFAULT_LIFETIME.to_string()]
+        } else {
+            data_class
+                .lifetime_params
+                .iter()
+                .map(|label| format!("'{}", label))
+                .collect()
+        };
+
+        // Derives inferred from the field types, along with the @Derive ones
+        let derives = self
+            .data_class_derives
+            .get(&data_class.name)
+            .cloned()
+            .unwrap_or_default();
+
+        if !derives.is_empty() {
+            self.indent();
+            self.output.push_str("#[derive(");
+            self.output.push_str(&derives.join(", "));
+            self.output.push_str(")]\n");
+        }
+        self.generate_rust_attributes(&data_class.annotations);
         self.indent();
         self.output.push_str("pub struct ");
         self.output.push_str(&data_class.name);
 
         if needs_lifetime {
-            self.output.push_str("<'a>");
+            self.output.push('<');
+            self.output.push_str(&lifetimes.join(", "));
+            self.output.push('>');
         }
 
         self.output.push_str(" {\n");
@@ -256,6 +619,7 @@ impl CodeGenerator {
 
         // Generate fields
         for field in &data_class.fields {
+            self.generate_rust_attributes(&field.annotations);
             self.indent();
             self.output.push_str("pub ");
             self.output.push_str(&camel_to_snake_case(&field.name));
@@ -263,7 +627,10 @@ impl CodeGenerator {
 
             // Generate the field type with lifetime if needed
             if needs_lifetime {
+                let saved_default_lifetime =
+                    std::mem::replace(&mut self.default_lifetime, lifetimes.first().cloned());
                 self.generate_data_class_field_type(&field.field_type.node);
+                self.default_lifetime = saved_default_lifetime;
             } else {
                 self.generate_type(&field.field_type.node);
             }
@@ -279,5 +646,102 @@ impl CodeGenerator {
         self.indent_level -= 1;
         self.indent();
         self.output.push_str("}\n\n");
+
+        let class_lifetimes = if needs_lifetime {
+            lifetimes.as_slice()
+        } else {
+            &[]
+        };
+        self.generate_data_class_members(data_class, &derives, class_lifetimes)
+    }
+
+    /// Generate the `#[cfg(test)]` module holding the `@Test` functions
+    pub(super) fn generate_test_module(
+        &mut self,
+        tests: &[&FunDeclStmt],
+    ) -> Result<(), VeltranoError> {
+        self.indent();
+        self.output.push_str("#[cfg(test)]\n");
+        self.indent();
+        self.output.push_str("mod tests {\n");
+        self.indent_level += 1;
+        self.indent();
+        self.output.push_str("use super::*;\n");
+
+        for test in tests {
+            self.output.push('\n');
+            self.generate_function(test, false)?;
+        }
+
+        self.indent_level -= 1;
+        self.indent();
+        self.output.push_str("}\n");
+        Ok(())
+    }
+
+    /// Generate an `extern` block declaring a single foreign function
+    fn generate_extern_function(&mut self, extern_fun: &ExternFunStmt) {
+        self.indent();
+        self.output
+            .push_str(&format!("extern \"{}\" {{\n", extern_fun.abi));
+        self.indent_level += 1;
+
+        // Veltrano names are snake-cased, so keep the foreign symbol name intact
+        let snake_name = camel_to_snake_case(&extern_fun.name);
+        if snake_name != extern_fun.name {
+            self.indent();
+            self.output
+                .push_str(&format!("#[link_name = \"{}\"]\n", extern_fun.name));
+        }
+        self.indent();
+        self.output.push_str("fn ");
+        self.output.push_str(&snake_name);
+        self.output.push('(');
+        self.generate_comma_separated_params(&extern_fun.params, &[]);
+        self.output.push(')');
+        if let Some(return_type) = &extern_fun.return_type {
+            self.output.push_str(" -> ");
+            self.generate_type(&return_type.node);
+        }
+        self.output.push_str(";\n");
+
+        self.indent_level -= 1;
+        self.indent();
+        self.output.push_str("}\n");
+    }
+
+    /// Emit each `@Rust("...")` and `@Cfg(...)` annotation as an attribute line
+    fn generate_rust_attributes(&mut self, annotations: &[Annotation]) {
+        for annotation in annotations {
+            let attribute = match (annotation.name.as_str(), annotation.args.first()) {
+                ("Rust", Some(AnnotationArg::String(attribute))) => attribute.clone(),
+                ("Cfg", Some(predicate)) => Self::cfg_attribute(predicate),
+                _ => continue,
+            };
+            self.indent();
+            self.output.push_str("#[");
+            self.output.push_str(&attribute);
+            self.output.push_str("]\n");
+        }
+    }
+
+    /// Emit only the `@Cfg(...)` annotations, for code generated alongside an item
+    pub(super) fn generate_cfg_attributes(&mut self, annotations: &[Annotation]) {
+        for annotation in annotations.iter().filter(|a| a.name == "Cfg") {
+            if let Some(predicate) = annotation.args.first() {
+                self.indent();
+                self.output.push_str("#[");
+                self.output.push_str(&Self::cfg_attribute(predicate));
+                self.output.push_str("]\n");
+            }
+        }
+    }
+
+    fn cfg_attribute(predicate: &AnnotationArg) -> String {
+        match predicate {
+            AnnotationArg::Identifier(predicate) | AnnotationArg::String(predicate) => {
+                format!("cfg({})", predicate)
+            }
+        }
     }
 }
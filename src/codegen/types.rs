@@ -6,47 +6,72 @@ use super::CodeGenerator;
 use crate::types::{TypeConstructor, VeltranoType};
 
 /// Default lifetime annotation
-const DEFAULT_LIFETIME: &str = "'a";
+pub(super) const DEFAULT_LIFETIME: &str = "'a";
 
 impl CodeGenerator {
     /// Generate Rust type representation from Veltrano type annotation
     pub(super) fn generate_type(&mut self, type_annotation: &VeltranoType) {
+        let type_annotation = self.without_local_lifetime(type_annotation);
+
         // For data class types that need lifetime parameters, we need special handling
         if let TypeConstructor::Custom(name) = &type_annotation.constructor {
             if self.data_classes_with_lifetime.contains(name) {
                 // For naturally referenced custom types with lifetime parameters
+                let lifetime = Self::label_lifetime(&type_annotation)
+                    .or_else(|| self.default_lifetime.clone());
                 self.output.push('&');
-                if self.generating_bump_function {
-                    self.output.push_str(DEFAULT_LIFETIME);
+                if let Some(lifetime) = &lifetime {
+                    self.output.push_str(lifetime);
                     self.output.push(' ');
                 }
                 self.output.push_str(name);
-                if self.generating_bump_function {
-                    self.output.push('<');
-                    self.output.push_str(DEFAULT_LIFETIME);
-                    self.output.push('>');
+                if let Some(lifetime) = &lifetime {
+                    let lifetime_args = self.data_class_lifetime_args(name, lifetime);
+                    self.output.push_str(&lifetime_args);
                 }
                 return;
             }
         }
 
         // Use the new to_rust_type_with_lifetime method
-        let lifetime = if self.generating_bump_function {
-            Some(DEFAULT_LIFETIME.to_string())
-        } else {
-            None
-        };
-
-        let rust_type =
-            type_annotation.to_rust_type_with_lifetime(&mut self.trait_checker, lifetime);
+        let rust_type = type_annotation
+            .to_rust_type_with_lifetime(&mut self.trait_checker, self.default_lifetime.clone());
         self.output.push_str(&rust_type.to_rust_syntax());
     }
 
+    /// Generate the type of a function parameter; function types become generic
+    /// `impl Fn` parameters, which also accept closures
+    pub(super) fn generate_parameter_type(&mut self, param_type: &VeltranoType) {
+        if param_type.constructor != TypeConstructor::Function {
+            return self.generate_type(param_type);
+        }
+        let param_type = self.without_local_lifetime(param_type);
+        let rust_type = param_type
+            .to_rust_type_with_lifetime(&mut self.trait_checker, self.default_lifetime.clone());
+        self.output.push_str("impl Fn");
+        self.output.push_str(&rust_type.function_signature_syntax());
+    }
+
     /// Check if a type needs lifetime parameters (is naturally referenced)
     pub(super) fn type_needs_lifetime(&mut self, veltrano_type: &VeltranoType) -> bool {
         match &veltrano_type.constructor {
             // Reference types always need lifetimes
             TypeConstructor::Ref | TypeConstructor::MutRef => true,
+            // Tuples and arrays need lifetimes if any of their elements do
+            TypeConstructor::Tuple | TypeConstructor::Array(_) => veltrano_type
+                .args
+                .iter()
+                .any(|element| self.type_needs_lifetime(element)),
+            // Naturally referenced wrappers and collections are borrowed
+            TypeConstructor::Box
+            | TypeConstructor::Rc
+            | TypeConstructor::Arc
+            | TypeConstructor::RefCell
+            | TypeConstructor::Cell
+            | TypeConstructor::HashMap
+            | TypeConstructor::BTreeMap
+            | TypeConstructor::HashSet
+            | TypeConstructor::Dyn(_) => true,
             // Use trait checking for base types
             _ if veltrano_type.args.is_empty() => {
                 !veltrano_type.implements_copy(&mut self.trait_checker)
@@ -56,39 +81,58 @@ impl CodeGenerator {
         }
     }
 
-    /// Generate type annotation for data class fields (with lifetime 'a)
+    /// Generate type annotation for data class fields (with the class's first lifetime)
     pub(super) fn generate_data_class_field_type(&mut self, type_annotation: &VeltranoType) {
-        // For data class fields, we always need lifetime 'a for reference types
-        // Special handling for custom types with lifetime parameters
-        if let TypeConstructor::Custom(name) = &type_annotation.constructor {
-            if self.data_classes_with_lifetime.contains(name) {
-                self.output.push_str("&'a ");
-                self.output.push_str(name);
-                self.output.push_str("<'a>");
-                return;
-            }
-        }
-
         // For owned custom types in data class fields
         if let TypeConstructor::Own = &type_annotation.constructor {
             if let Some(inner) = type_annotation.inner() {
                 if let TypeConstructor::Custom(name) = &inner.constructor {
                     self.output.push_str(name);
                     if self.data_classes_with_lifetime.contains(name) {
-                        self.output.push('<');
-                        self.output.push_str(DEFAULT_LIFETIME);
-                        self.output.push('>');
+                        let lifetime = Self::label_lifetime(inner)
+                            .or_else(|| self.default_lifetime.clone())
+                            .unwrap_or_else(|| DEFAULT_LIFETIME.to_string());
+                        let lifetime_args = self.data_class_lifetime_args(name, &lifetime);
+                        self.output.push_str(&lifetime_args);
                     }
                     return;
                 }
             }
         }
 
-        // For all other types, use the standard conversion with lifetime
-        let rust_type = type_annotation.to_rust_type_with_lifetime(
-            &mut self.trait_checker,
-            Some(DEFAULT_LIFETIME.to_string()),
-        );
-        self.output.push_str(&rust_type.to_rust_syntax());
+        // For all other types, use the standard conversion with the class lifetime
+        self.generate_type(type_annotation);
+    }
+
+    /// Rust lifetime written for this type's own label, if it has one
+    fn label_lifetime(veltrano_type: &VeltranoType) -> Option<String> {
+        veltrano_type
+            .lifetime
+            .as_ref()
+            .map(|label| format!("'{}", label))
+    }
+
+    /// Lifetime arguments of a data class type, all set to `lifetime`
+    fn data_class_lifetime_args(&self, name: &str, lifetime: &str) -> String {
+        let count = self
+            .data_class_lifetime_counts
+            .get(name)
+            .copied()
+            .unwrap_or(1);
+        format!("<{}>", vec![lifetime; count].join(", "))
+    }
+
+    /// Replace the label of the current function's own lifetime with `'_`, which Rust infers
+    fn without_local_lifetime(&self, veltrano_type: &VeltranoType) -> VeltranoType {
+        match &self.local_lifetime {
+            Some(local) => veltrano_type.map_lifetimes(&|label| {
+                if label == local {
+                    Some("_".to_string())
+                } else {
+                    Some(label.to_string())
+                }
+            }),
+            None => veltrano_type.clone(),
+        }
     }
 }
@@ -1,11 +1,11 @@
 //! Code generation utilities.
 //!
-//! Common helpers for indentation and macro detection.
+//! Common helpers for indentation.
 
 use super::CodeGenerator;
 
 /// String used for one level of indentation
-const INDENT_STR: &str = "    ";
+pub(super) const INDENT_STR: &str = "    ";
 
 impl CodeGenerator {
     /// Adds the current indentation level to the output
@@ -14,12 +14,4 @@ impl CodeGenerator {
             self.output.push_str(INDENT_STR);
         }
     }
-
-    /// Checks if a given name is a Rust macro
-    pub(super) fn is_rust_macro(&self, name: &str) -> bool {
-        matches!(
-            name,
-            "println" | "print" | "panic" | "assert" | "debug_assert"
-        )
-    }
 }
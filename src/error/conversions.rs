@@ -151,6 +151,15 @@ impl From<TypeCheckError> for VeltranoError {
                 ),
             )
             .with_span(Span::single(location)),
+            TypeCheckError::InvalidArguments {
+                function,
+                reason,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Invalid arguments for '{}': {}", function, reason),
+            )
+            .with_span(Span::single(location)),
             TypeCheckError::VariableNotFound { name, location } => VeltranoError::new(
                 ErrorKind::UndefinedVariable,
                 format!("Variable '{}' not found", name),
@@ -192,6 +201,305 @@ impl From<TypeCheckError> for VeltranoError {
                 format!("Invalid import {}.{}: {}", type_name, method_name, reason),
             )
             .with_span(Span::single(location)),
+            TypeCheckError::IndexingNotSupported {
+                object_type,
+                index_type,
+                location,
+            } => {
+                if object_type.element_type().is_none() {
+                    VeltranoError::new(
+                        ErrorKind::TypeError,
+                        format!("Type {:?} cannot be indexed", object_type),
+                    )
+                    .with_span(Span::single(location))
+                    .with_note(
+                        "Only Vec, Array and Slice values, and data classes with `operator fun get`, support `xs[i]`",
+                    )
+                } else {
+                    VeltranoError::new(
+                        ErrorKind::TypeMismatch,
+                        format!("Index must be USize, found {:?}", index_type),
+                    )
+                    .with_span(Span::single(location))
+                    .with_help("Integer literals can be used directly; convert other integer types to USize explicitly")
+                }
+            }
+            TypeCheckError::CannotInferElementType { function, location } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Cannot infer the element type of an empty {}()", function),
+            )
+            .with_span(Span::single(location))
+            .with_help("Add a type annotation, e.g. `val xs: Own<Vec<I64>> = vecOf()`"),
+            TypeCheckError::InvalidDestructuring {
+                target_type,
+                reason,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Cannot destructure value of type {:?}: {}",
+                    target_type, reason
+                ),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Destructuring declarations work on tuples (Pair, Triple) and data classes"
+                    .to_string(),
+            ),
+            TypeCheckError::DuplicateDefinition {
+                kind,
+                name,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Duplicate definition of {} '{}'", kind, name),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Rename one of the definitions, or put them under mutually exclusive conditions \
+                 such as @Cfg(unix) and @Cfg(\"not(unix)\")"
+                    .to_string(),
+            ),
+            TypeCheckError::CfgSignatureMismatch {
+                kind,
+                name,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Definitions of {} '{}' under different @Cfg conditions have different signatures",
+                    kind, name
+                ),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Uses are checked against a single definition, so give every variant the same \
+                 parameters, fields and return type, or rename one of them"
+                    .to_string(),
+            ),
+            TypeCheckError::NotFfiSafe {
+                function,
+                item,
+                rust_type,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "{} of C function '{}' has type {}, which is not FFI-safe",
+                    item, function, rust_type
+                ),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Str, String, data classes and Ref<T> are Rust references with no C equivalent; \
+                 use integers, Bool or raw pointers to sized values (Ptr<T>, MutPtr<T>) instead"
+                    .to_string(),
+            ),
+            TypeCheckError::InvalidAnnotation {
+                annotation,
+                reason,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Invalid annotation @{}: {}", annotation, reason),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Use @Derive(...) on data classes, @Test or @CExport on top-level functions, @Cfg(...) for conditional compilation, or @Rust(\"...\") to emit a raw Rust attribute"
+                    .to_string(),
+            ),
+            TypeCheckError::BinaryOperatorNotSupported {
+                operator,
+                left_type,
+                right_type,
+                location,
+            } => {
+                let error = VeltranoError::new(
+                    ErrorKind::TypeError,
+                    format!(
+                        "Operator '{}' is not supported between {:?} and {:?}",
+                        operator.symbol(),
+                        left_type,
+                        right_type
+                    ),
+                )
+                .with_span(Span::single(location));
+                match (left_type.data_class_name(), operator.operator_function_name()) {
+                    (Some(class_name), Some(function)) => error.with_help(format!(
+                        "Declare `operator fun {}(...)` in data class {}",
+                        function, class_name
+                    )),
+                    _ => error,
+                }
+            }
+            TypeCheckError::InvalidOperatorFunction {
+                class_name,
+                function,
+                reason,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Invalid operator function '{}.{}': {}",
+                    class_name, function, reason
+                ),
+            )
+            .with_span(Span::single(location)),
+            TypeCheckError::InvalidOverride {
+                class_name,
+                function,
+                reason,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Invalid override '{}.{}': {}", class_name, function, reason),
+            )
+            .with_span(Span::single(location)),
+            TypeCheckError::InvalidLifetimeLabel {
+                label,
+                reason,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Invalid lifetime label @{}: {}", label, reason),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Declare lifetime labels on the function (fun<@a> name(...)) or data class (data class Name<@a>(...))"
+                    .to_string(),
+            ),
+            TypeCheckError::LifetimeMismatch {
+                expected,
+                actual,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Lifetime mismatch: expected a reference with lifetime @{}, found @{}",
+                    expected, actual
+                ),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Copy the value into the expected lifetime, e.g. with .clone().bumpRef()"
+                    .to_string(),
+            ),
+            TypeCheckError::UnsafeCallOutsideUnsafeBlock { function, span } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Call to unsafe function '{}' outside an unsafe block",
+                    function
+                ),
+            )
+            .with_span(span)
+            .with_help("Wrap the call in `unsafe { ... }`".to_string()),
+            TypeCheckError::InvalidAwait { reason, location } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Invalid .await(): {}", reason),
+            )
+            .with_span(Span::single(location))
+            .with_help(
+                "Await the result of a `suspend fun` or an imported async function from inside a `suspend fun`"
+                    .to_string(),
+            ),
+            TypeCheckError::InvalidDeref {
+                reason,
+                suggestion,
+                location,
+            } => {
+                let error = VeltranoError::new(
+                    ErrorKind::TypeError,
+                    format!("Invalid .deref(): {}", reason),
+                )
+                .with_span(Span::single(location));
+                match suggestion {
+                    Some(suggestion) => error.with_help(suggestion),
+                    None => error,
+                }
+            }
+            TypeCheckError::UseAfterMove {
+                name,
+                moved_at,
+                used_at,
+                in_loop,
+                suggestion,
+            } => {
+                let move_label = if in_loop {
+                    "value moved here, in the previous iteration of the loop"
+                } else {
+                    "value moved here"
+                };
+                VeltranoError::new(
+                    ErrorKind::TypeError,
+                    format!("Use of moved value '{}'", name),
+                )
+                .with_span(used_at)
+                .with_related(moved_at, move_label)
+                .with_help(suggestion)
+            }
+            TypeCheckError::DanglingReference {
+                target,
+                borrowed_at,
+                returned_at,
+                suggestion,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Cannot return a reference to {}", target),
+            )
+            .with_span(returned_at)
+            .with_related(
+                borrowed_at,
+                "borrowed here, but dropped when the function returns",
+            )
+            .with_help(suggestion),
+            TypeCheckError::ConflictingBorrow {
+                place,
+                first_mutable,
+                first_at,
+                second_mutable,
+                second_at,
+                first_used_at,
+            } => {
+                let kind = |mutable| if mutable { "mutable" } else { "shared" };
+                let first_label = match first_used_at {
+                    Some(used_at) => format!(
+                        "first {} borrow here, still used at {}",
+                        kind(first_mutable),
+                        used_at.start
+                    ),
+                    None => format!("first {} borrow here", kind(first_mutable)),
+                };
+                VeltranoError::new(
+                    ErrorKind::TypeError,
+                    format!(
+                        "Cannot borrow '{}' as {} because it is already borrowed as {}",
+                        place,
+                        kind(second_mutable),
+                        kind(first_mutable)
+                    ),
+                )
+                .with_span(second_at)
+                .with_related(first_at, first_label)
+                .with_help(format!(
+                    "A MutRef must be the only reference to a value while it is in use, so no other Ref or MutRef of '{}' can be created until the last use of the first borrow",
+                    place
+                ))
+            }
+            TypeCheckError::InvalidAssignment { reason, location } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Invalid assignment: {}", reason),
+            )
+            .with_span(Span::single(location))
+            .with_help("Write through a mutable reference with `target.deref() = value`".to_string()),
+            TypeCheckError::InvalidFunctionReference {
+                name,
+                reason,
+                location,
+            } => VeltranoError::new(
+                ErrorKind::TypeError,
+                format!("Cannot reference function '{}': {}", name, reason),
+            )
+            .with_span(Span::single(location)),
             _ => {
                 // For any remaining error types, use Debug formatting
                 VeltranoError::new(ErrorKind::TypeError, format!("{:?}", err))
@@ -79,6 +79,7 @@ impl fmt::Display for Span {
 #[derive(Debug, Clone)]
 pub struct ErrorContext {
     pub span: Option<Span>,
+    pub related: Option<(Span, String)>, // A second location involved in the error, with its label
     pub note: Option<String>,
     pub help: Option<String>,
 }
@@ -87,6 +88,7 @@ impl ErrorContext {
     pub fn new() -> Self {
         Self {
             span: None,
+            related: None,
             note: None,
             help: None,
         }
@@ -104,7 +106,7 @@ impl Default for ErrorContext {
 pub struct VeltranoError {
     pub kind: ErrorKind,
     pub message: String,
-    pub context: ErrorContext,
+    pub context: Box<ErrorContext>, // Boxed to keep `Result`s holding an error small
 }
 
 impl VeltranoError {
@@ -112,7 +114,7 @@ impl VeltranoError {
         Self {
             kind,
             message: message.into(),
-            context: ErrorContext::new(),
+            context: Box::default(),
         }
     }
 
@@ -121,6 +123,11 @@ impl VeltranoError {
         self
     }
 
+    pub fn with_related(mut self, span: Span, label: impl Into<String>) -> Self {
+        self.context.related = Some((span, label.into()));
+        self
+    }
+
     pub fn with_note(mut self, note: impl Into<String>) -> Self {
         self.context.note = Some(note.into());
         self
@@ -218,6 +225,10 @@ impl fmt::Display for VeltranoError {
         }
 
         // Additional context
+        if let Some((span, label)) = &self.context.related {
+            write!(f, "\nnote: {}: {}", span, label)?;
+        }
+
         if let Some(note) = &self.context.note {
             write!(f, "\nnote: {}", note)?;
         }
@@ -299,6 +310,21 @@ impl<'a> ErrorFormatter<'a> {
         }
 
         // Additional context
+        if let Some((span, label)) = &self.error.context.related {
+            let note_label = if self.use_color {
+                "note".blue().bold()
+            } else {
+                "note".into()
+            };
+            output.push_str(&format!(
+                "\n{}: {}:{}: {}\n",
+                note_label, span.start.line, span.start.column, label
+            ));
+            if let Some(snippet) = self.extract_snippet(span) {
+                output.push_str(&snippet);
+            }
+        }
+
         if let Some(note) = &self.error.context.note {
             let note_label = if self.use_color {
                 "note".blue().bold()
@@ -32,6 +32,7 @@ pub enum TokenType {
     Identifier(String),
     IntLiteral(i64),
     StringLiteral(String),
+    RustBlock(String), // Raw contents of a `rust { ... }` block
 
     // Operators
     Plus,
@@ -54,10 +55,14 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Colon,
+    ColonColon, // Function reference marker: `::add`, `Person::greet`
     Comma,
     Dot,
     Arrow,
+    At, // Annotation marker
 
     // Comments (with content, preceding whitespace, and context)
     LineComment(String, String, CommentContext), // (content, preceding_whitespace, context)
@@ -104,6 +109,7 @@ impl Lexer {
 
     pub fn tokenize(&mut self) -> Vec<Token> {
         let mut tokens = Vec::new();
+        self.skip_shebang();
 
         while !self.is_at_end() {
             let whitespace = self.collect_whitespace();
@@ -193,9 +199,19 @@ impl Lexer {
                 }
                 TokenType::RightBrace
             }
-            ':' => TokenType::Colon,
+            '[' => TokenType::LeftBracket,
+            ']' => TokenType::RightBracket,
+            ':' => {
+                if self.peek() == Some(':') {
+                    self.advance();
+                    TokenType::ColonColon
+                } else {
+                    TokenType::Colon
+                }
+            }
             ',' => TokenType::Comma,
             '.' => TokenType::Dot,
+            '@' => TokenType::At,
             '+' => TokenType::Plus,
             '-' => {
                 if self.peek() == Some('>') {
@@ -286,7 +302,11 @@ impl Lexer {
             }
             _ if ch.is_ascii_alphabetic() || ch == '_' => {
                 let identifier = self.read_identifier(ch);
-                self.keyword_or_identifier(identifier)
+                if identifier == "rust" && self.skip_to_rust_block_brace() {
+                    TokenType::RustBlock(self.read_rust_block())
+                } else {
+                    self.keyword_or_identifier(identifier)
+                }
             }
             _ => return None, // Invalid character
         };
@@ -319,7 +339,13 @@ impl Lexer {
     }
 
     fn read_string(&mut self) -> String {
-        let value = self.read_while(|ch| ch != '"');
+        // Escape sequences are kept verbatim, so `\"` does not end the literal
+        let mut escaped = false;
+        let value = self.read_while(|ch| {
+            let closing = ch == '"' && !escaped;
+            escaped = ch == '\\' && !escaped;
+            !closing
+        });
 
         if !self.is_at_end() {
             self.advance(); // Consume closing quote
@@ -328,6 +354,85 @@ impl Lexer {
         value
     }
 
+    /// Consume the spaces and opening brace after `rust` if a raw block follows
+    fn skip_to_rust_block_brace(&mut self) -> bool {
+        let offset = self.input[self.position..]
+            .iter()
+            .take_while(|&&ch| ch == ' ' || ch == '\t')
+            .count();
+        if self.input.get(self.position + offset) != Some(&'{') {
+            return false;
+        }
+        for _ in 0..=offset {
+            self.advance();
+        }
+        true
+    }
+
+    /// Read the raw Rust code up to the matching closing brace, removing common indentation
+    fn read_rust_block(&mut self) -> String {
+        let mut code = String::new();
+        let mut depth = 1;
+
+        while let Some(ch) = self.advance() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                // Braces inside literals and comments don't count
+                '"' => {
+                    code.push('"');
+                    code.push_str(&self.read_string());
+                    code.push('"');
+                    continue;
+                }
+                '\'' if self.peek() == Some('\\') || self.peek_next() == Some('\'') => {
+                    code.push('\'');
+                    code.push_str(&self.read_char_literal());
+                    continue;
+                }
+                '/' if self.peek() == Some('/') => {
+                    code.push('/');
+                    code.push_str(&self.read_line_comment());
+                    continue;
+                }
+                '/' if self.peek() == Some('*') => {
+                    self.advance();
+                    code.push_str("/*");
+                    code.push_str(&self.read_block_comment());
+                    code.push_str("*/");
+                    continue;
+                }
+                '\n' => {
+                    self.line += 1;
+                    self.column = 1;
+                }
+                _ => {}
+            }
+            code.push(ch);
+        }
+
+        remove_common_indentation(&code)
+    }
+
+    /// Read the rest of a Rust char literal, including the closing quote
+    fn read_char_literal(&mut self) -> String {
+        let mut escaped = false;
+        let mut value = self.read_while(|ch| {
+            let closing = ch == '\'' && !escaped;
+            escaped = ch == '\\' && !escaped;
+            !closing
+        });
+        if self.advance().is_some() {
+            value.push('\'');
+        }
+        value
+    }
+
     fn read_number(&mut self, first_digit: char) -> i64 {
         let mut value = String::from(first_digit);
         value.push_str(&self.read_while(|c| c.is_ascii_digit()));
@@ -397,6 +502,16 @@ impl Lexer {
         }
     }
 
+    /// Skip a `#!/usr/bin/env veltrano` line at the very start of a script,
+    /// leaving its newline so line numbers stay intact
+    fn skip_shebang(&mut self) {
+        if self.position == 0 && self.input.starts_with(&['#', '!']) {
+            while self.peek().is_some_and(|ch| ch != '\n') {
+                self.advance();
+            }
+        }
+    }
+
     fn is_at_end(&self) -> bool {
         self.position >= self.input.len()
     }
@@ -452,3 +567,28 @@ impl Lexer {
         value
     }
 }
+
+/// Trim surrounding blank lines and the indentation shared by all non-blank lines
+fn remove_common_indentation(code: &str) -> String {
+    let lines: Vec<&str> = code.lines().collect();
+    let start = lines.iter().position(|line| !line.trim().is_empty());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+    let (start, end) = match (start, end) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return String::new(),
+    };
+
+    let lines = &lines[start..=end];
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -8,6 +8,7 @@ pub mod error;
 pub mod lexer;
 pub mod parser;
 pub mod rust_interop;
+pub mod test_runner;
 pub mod type_checker;
 pub mod types;
 
@@ -8,14 +8,17 @@ mod error;
 mod lexer;
 mod parser;
 mod rust_interop;
+mod test_runner;
 mod type_checker;
 mod types;
 
 use std::env;
 use std::fs;
 use std::io::IsTerminal;
+use std::path::Path;
 use std::process;
 
+use ast::Program;
 use codegen::CodeGenerator;
 use config::Config;
 use error::ErrorFormatter;
@@ -29,6 +32,10 @@ fn print_help(program_name: &str) {
     println!();
     println!("USAGE:");
     println!("    {} [OPTIONS] <input.vl>", program_name);
+    println!("    {} test [OPTIONS] <input.vl>", program_name);
+    println!();
+    println!("COMMANDS:");
+    println!("    test                     Run the file's @Test functions with `cargo test`");
     println!();
     println!("OPTIONS:");
     println!("    -h, --help               Print help information");
@@ -36,6 +43,7 @@ fn print_help(program_name: &str) {
     println!("    --preserve-comments      Preserve comments in generated Rust code");
     println!("    --debug                  Enable debug output for troubleshooting");
     println!("    --fail-fast              Stop at first error instead of collecting all errors");
+    println!("    --script                 Wrap top-level statements in a generated `fn main()`");
     println!();
     println!("ARGS:");
     println!("    <input.vl>               The Veltrano source file to transpile");
@@ -49,6 +57,8 @@ fn print_help(program_name: &str) {
         "    {} --preserve-comments examples/fibonacci.vl",
         program_name
     );
+    println!("    {} --script experiment.vl", program_name);
+    println!("    {} test math.vl", program_name);
     println!("    VELTRANO_DEBUG=1 {} input.vl", program_name);
 }
 
@@ -63,12 +73,15 @@ fn main() {
     let mut preserve_comments = false;
     let mut debug_mode = false;
     let mut fail_fast = false;
+    let mut script = false;
     let mut input_file = None;
 
     // Check if we should use color (default: auto-detect)
     let use_color = std::io::stderr().is_terminal();
 
-    let mut i = 1;
+    // `test` is a command only when an input file follows it, so a file named `test` still transpiles
+    let run_tests = args[1] == "test" && args[2..].iter().any(|arg| !arg.starts_with('-'));
+    let mut i = if run_tests { 2 } else { 1 };
     while i < args.len() {
         match args[i].as_str() {
             "--version" | "-v" => {
@@ -91,6 +104,10 @@ fn main() {
                 fail_fast = true;
                 i += 1;
             }
+            "--script" => {
+                script = true;
+                i += 1;
+            }
             _ => {
                 if input_file.is_none() {
                     input_file = Some(&args[i]);
@@ -126,11 +143,64 @@ fn main() {
         }
     };
 
+    // Files with a shebang line are scripts
+    let script = script || source_code.starts_with("#!");
+    let config = Config { preserve_comments };
+    let (program, rust_code) = transpile(
+        input_file,
+        source_code,
+        config,
+        script,
+        fail_fast,
+        use_color,
+    );
+
+    if run_tests {
+        let name = Path::new(input_file)
+            .file_stem()
+            .map_or("main".into(), |stem| stem.to_string_lossy());
+        match test_runner::run_tests(&program, &rust_code, &name) {
+            Ok(report) => {
+                print!("{}", report.output);
+                process::exit(if report.success { 0 } else { 1 });
+            }
+            Err(err) => {
+                eprintln!("Error running tests in '{}': {}", input_file, err);
+                process::exit(1);
+            }
+        }
+    }
+
+    let output_file = if input_file.ends_with(".vl") {
+        format!("{}.rs", &input_file[..input_file.len() - 3])
+    } else {
+        format!("{}.rs", input_file)
+    };
+
+    match fs::write(&output_file, rust_code) {
+        Ok(_) => println!(
+            "Successfully transpiled '{}' to '{}'",
+            input_file, output_file
+        ),
+        Err(err) => {
+            eprintln!("Error writing output file '{}': {}", output_file, err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse, type check and generate Rust code, exiting on the first failing phase
+fn transpile(
+    input_file: &str,
+    source_code: String,
+    config: Config,
+    script: bool,
+    fail_fast: bool,
+    use_color: bool,
+) -> (Program, String) {
     // Store a reference to source code for error formatting
     let source_ref = source_code.clone();
 
-    let config = Config { preserve_comments };
-
     let mut lexer = Lexer::with_config(source_code, config.clone());
     let all_tokens = lexer.tokenize();
 
@@ -159,6 +229,21 @@ fn main() {
         program
     };
 
+    let program = if script {
+        match program.into_script() {
+            Ok(program) => program,
+            Err(err) => {
+                let formatter = ErrorFormatter::new(&err, &source_ref)
+                    .with_filename(input_file)
+                    .with_color(use_color);
+                eprintln!("{}", formatter.format());
+                process::exit(1);
+            }
+        }
+    } else {
+        program
+    };
+
     // Type checking phase
     let mut type_checker = VeltranoTypeChecker::new();
     if let Err(errors) = type_checker.check_program_unified(&program) {
@@ -181,28 +266,16 @@ fn main() {
         veltrano::debug_println!("  ID {}: {:?}.{}", id, res.rust_type, res.method_name);
     }
     codegen.set_method_resolutions(resolutions);
-    let rust_code = match codegen.generate(&program) {
-        Ok(code) => code,
+    codegen.set_node_types(type_checker.get_node_types().clone());
+    codegen.set_lifetime_bindings(type_checker.get_lifetime_bindings().clone());
+    codegen.set_operator_resolutions(type_checker.get_operator_resolutions().clone());
+    codegen.set_data_class_derives(type_checker.get_data_class_derives().clone());
+    codegen.set_hidden_bump_functions(type_checker.get_hidden_bump_functions().clone());
+    match codegen.generate(&program) {
+        Ok(rust_code) => (program, rust_code),
         Err(err) => {
             eprintln!("Code generation error: {}", err);
             process::exit(1);
         }
-    };
-
-    let output_file = if input_file.ends_with(".vl") {
-        format!("{}.rs", &input_file[..input_file.len() - 3])
-    } else {
-        format!("{}.rs", input_file)
-    };
-
-    match fs::write(&output_file, rust_code) {
-        Ok(_) => println!(
-            "Successfully transpiled '{}' to '{}'",
-            input_file, output_file
-        ),
-        Err(err) => {
-            eprintln!("Error writing output file '{}': {}", output_file, err);
-            process::exit(1);
-        }
     }
 }
@@ -24,6 +24,7 @@ impl Parser {
                 | TokenType::While
                 | TokenType::Return
                 | TokenType::Data
+                | TokenType::At
                 | TokenType::Import => return,
                 _ => {}
             }
@@ -4,12 +4,14 @@
 //! - Primary expressions (literals, identifiers)
 //! - Unary and binary operations  
 //! - Function and method calls
-//! - Field access
+//! - Field access and indexing
 
+use super::utils::unescape_string;
 use super::Parser;
 use crate::ast::{
-    Argument, ArgumentComment, BinaryExpr, BinaryOp, CallExpr, Expr, FieldAccessExpr, LiteralExpr,
-    MethodCallExpr, ParenthesizedExpr, UnaryExpr, UnaryOp,
+    Argument, ArgumentComment, BinaryExpr, BinaryOp, CallExpr, Expr, FieldAccessExpr,
+    FunctionRefExpr, IndexExpr, LiteralExpr, MethodCallExpr, ParenthesizedExpr, RustCodeExpr,
+    TupleExpr, UnaryExpr, UnaryOp,
 };
 use crate::ast::{Located, LocatedExpr};
 use crate::error::{SourceLocation, Span, VeltranoError};
@@ -53,7 +55,7 @@ impl Parser {
 
     fn comparison(&mut self) -> Result<LocatedExpr, VeltranoError> {
         self.parse_binary_expression(
-            Self::term,
+            Self::infix_pair,
             &[
                 TokenType::Greater,
                 TokenType::GreaterEqual,
@@ -70,6 +72,24 @@ impl Parser {
         )
     }
 
+    /// Parse `a to b`, which builds the pair `(a, b)` as in Kotlin
+    fn infix_pair(&mut self) -> Result<LocatedExpr, VeltranoError> {
+        let mut expr = self.term()?;
+        while matches!(&self.peek().token_type, TokenType::Identifier(name) if name == "to") {
+            self.advance();
+            self.skip_newlines_and_comments();
+            let second = self.term()?;
+            let span = Span::new(expr.span.start.clone(), second.span.end.clone());
+            expr = Located::new(
+                Expr::Tuple(TupleExpr {
+                    elements: vec![expr, second],
+                }),
+                span,
+            );
+        }
+        Ok(expr)
+    }
+
     fn term(&mut self) -> Result<LocatedExpr, VeltranoError> {
         self.parse_binary_expression(
             Self::factor,
@@ -109,8 +129,14 @@ impl Parser {
             let operator = UnaryOp::Minus;
             let operand = Box::new(self.unary()?); // Right associative
             let end_span = operand.span.end.clone();
+            let id = self.next_call_id;
+            self.next_call_id += 1;
             return Ok(Located::new(
-                Expr::Unary(UnaryExpr { operator, operand }),
+                Expr::Unary(UnaryExpr {
+                    operator,
+                    operand,
+                    id,
+                }),
                 Span::new(SourceLocation::new(start_line, start_column), end_span),
             ));
         }
@@ -131,6 +157,8 @@ impl Parser {
                 expr = self.parse_function_call(expr)?;
             } else if self.match_token(&TokenType::Dot) {
                 expr = self.parse_member_access(expr)?;
+            } else if self.match_token(&TokenType::LeftBracket) {
+                expr = self.parse_index_access(expr)?;
             } else if let TokenType::LineComment(_, _, _) = &self.peek().token_type {
                 if !self.handle_method_chain_comment(&mut expr) {
                     break;
@@ -463,6 +491,14 @@ impl Parser {
             return Ok(expr);
         }
 
+        if let Some(expr) = self.try_parse_rust_code()? {
+            return Ok(expr);
+        }
+
+        if let Some(expr) = self.try_parse_function_reference()? {
+            return Ok(expr);
+        }
+
         if let Some(expr) = self.try_parse_identifier_or_unit()? {
             return Ok(expr);
         }
@@ -535,6 +571,78 @@ impl Parser {
         }
     }
 
+    /// Try to parse an inline Rust expression: `rust<Type>("code")`
+    fn try_parse_rust_code(&mut self) -> Result<Option<LocatedExpr>, VeltranoError> {
+        let is_rust_code = matches!(&self.peek().token_type, TokenType::Identifier(name) if name == "rust")
+            && matches!(
+                self.tokens
+                    .get(self.current + 1)
+                    .map(|token| &token.token_type),
+                Some(TokenType::Less)
+            );
+        if !is_rust_code {
+            return Ok(None);
+        }
+
+        let start_token = self.advance();
+        let start_loc = SourceLocation::new(start_token.line, start_token.column);
+        self.advance(); // consume '<'
+        let result_type = self.parse_type()?;
+        self.consume(&TokenType::Greater, "Expected '>' after inline Rust type")?;
+        self.consume(&TokenType::LeftParen, "Expected '(' after 'rust<Type>'")?;
+
+        let code = match &self.peek().token_type {
+            TokenType::StringLiteral(code) => unescape_string(code),
+            _ => return Err(self.unexpected_token("string literal with Rust code")),
+        };
+        self.advance();
+
+        let end_token = self.consume(
+            &TokenType::RightParen,
+            "Expected ')' after inline Rust code",
+        )?;
+        let end_loc = SourceLocation::new(end_token.line, end_token.column);
+
+        Ok(Some(self.located_expr_with_span(
+            Expr::RustCode(RustCodeExpr { code, result_type }),
+            start_loc,
+            end_loc,
+        )))
+    }
+
+    /// Try to parse a function reference: `::add` or `Person::greet`
+    fn try_parse_function_reference(&mut self) -> Result<Option<LocatedExpr>, VeltranoError> {
+        let receiver = match &self.peek().token_type {
+            TokenType::ColonColon => None,
+            TokenType::Identifier(name)
+                if matches!(
+                    self.tokens
+                        .get(self.current + 1)
+                        .map(|token| &token.token_type),
+                    Some(TokenType::ColonColon)
+                ) =>
+            {
+                Some(name.clone())
+            }
+            _ => return Ok(None),
+        };
+
+        let start_token = self.advance();
+        let start_loc = SourceLocation::new(start_token.line, start_token.column);
+        if receiver.is_some() {
+            self.advance(); // consume '::'
+        }
+        let name = self.consume_identifier("Expected function name after '::'")?;
+        let end_token = self.previous();
+        let end_loc = SourceLocation::new(end_token.line, end_token.column);
+
+        Ok(Some(self.located_expr_with_span(
+            Expr::FunctionRef(FunctionRefExpr { receiver, name }),
+            start_loc,
+            end_loc,
+        )))
+    }
+
     /// Try to parse identifier or Unit literal
     fn try_parse_identifier_or_unit(&mut self) -> Result<Option<LocatedExpr>, VeltranoError> {
         if let TokenType::Identifier(name) = &self.peek().token_type {
@@ -564,6 +672,11 @@ impl Parser {
 
         let expr = self.expression()?;
 
+        // A comma after the first expression makes this a tuple literal
+        if self.check(&TokenType::Comma) {
+            return self.parse_tuple_expression(expr, start_loc);
+        }
+
         // Capture comments before closing paren
         let close_paren_comment = self.capture_comment_sequence();
 
@@ -579,6 +692,27 @@ impl Parser {
         Ok(self.located_expr_with_span(paren_expr, start_loc, end_loc))
     }
 
+    /// Parse the remaining elements of a tuple literal after its first element
+    fn parse_tuple_expression(
+        &mut self,
+        first: LocatedExpr,
+        start_loc: SourceLocation,
+    ) -> Result<LocatedExpr, VeltranoError> {
+        let mut elements = vec![first];
+
+        while self.match_token(&TokenType::Comma) {
+            self.skip_newlines_and_comments();
+            elements.push(self.expression()?);
+            self.skip_newlines_and_comments();
+        }
+
+        let end_token =
+            self.consume(&TokenType::RightParen, "Expected ')' after tuple elements")?;
+        let end_loc = SourceLocation::new(end_token.line, end_token.column);
+
+        Ok(self.located_expr_with_span(Expr::Tuple(TupleExpr { elements }), start_loc, end_loc))
+    }
+
     fn parse_binary_expression<F, M>(
         &mut self,
         next: F,
@@ -651,6 +785,8 @@ impl Parser {
             let right = next(self)?;
             let start_span = expr.span.start.clone();
             let end_span = right.span.end.clone();
+            let id = self.next_call_id;
+            self.next_call_id += 1;
             expr = Located::new(
                 Expr::Binary(BinaryExpr {
                     left: Box::new(expr),
@@ -658,6 +794,7 @@ impl Parser {
                     operator,
                     comment_after_operator,
                     right: Box::new(right),
+                    id,
                 }),
                 Span::new(start_span, end_span),
             );
@@ -668,27 +805,116 @@ impl Parser {
 
     /// Parses member access (method call or field access)
     fn parse_member_access(&mut self, object: LocatedExpr) -> Result<LocatedExpr, VeltranoError> {
+        // Positional tuple element access (.0, .1, ...)
+        if let TokenType::IntLiteral(index) = &self.peek().token_type {
+            // `1.2` is not a tuple access; floating-point literals are not supported
+            if matches!(object.node, Expr::Literal(LiteralExpr::Int(_))) {
+                return Err(
+                    self.syntax_error("Expected field or method name after '.'".to_string())
+                );
+            }
+            let field = index.to_string();
+            self.advance();
+            return Ok(self.field_access_expr(object, field));
+        }
+
         let field_or_method = self.consume_identifier("Expected field or method name after '.'")?;
 
         // Check if this is a method call (has parentheses) or field access
-        if self.check(&TokenType::LeftParen) {
+        if self.check(&TokenType::LeftParen) && field_or_method == "copy" {
+            self.parse_copy_call(object)
+        } else if self.check(&TokenType::LeftParen) {
             self.parse_method_call(object, field_or_method)
         } else {
-            // Field access
-            let start_span = object.span.start.clone();
-            let end_span = Span::single(SourceLocation::new(
-                self.previous().line,
-                self.previous().column,
-            ))
-            .end;
-            Ok(Located::new(
-                Expr::FieldAccess(FieldAccessExpr {
-                    object: Box::new(object),
-                    field: field_or_method,
-                }),
-                Span::new(start_span, end_span),
-            ))
+            Ok(self.field_access_expr(object, field_or_method))
+        }
+    }
+
+    /// Parses an index expression `object[index]` after the opening bracket
+    fn parse_index_access(&mut self, object: LocatedExpr) -> Result<LocatedExpr, VeltranoError> {
+        let index = self.expression()?;
+        let end_token = self.consume(&TokenType::RightBracket, "Expected ']' after index")?;
+        let end_loc = SourceLocation::new(end_token.line, end_token.column);
+
+        let id = self.next_call_id;
+        self.next_call_id += 1;
+
+        let start_loc = object.span.start.clone();
+        Ok(self.located_expr_with_span(
+            Expr::Index(IndexExpr {
+                object: Box::new(object),
+                index: Box::new(index),
+                id,
+            }),
+            start_loc,
+            end_loc,
+        ))
+    }
+
+    /// Builds a field access expression ending at the previous token
+    fn field_access_expr(&mut self, object: LocatedExpr, field: String) -> LocatedExpr {
+        let id = self.next_call_id;
+        self.next_call_id += 1;
+
+        let start_span = object.span.start.clone();
+        let end_span = Span::single(SourceLocation::new(
+            self.previous().line,
+            self.previous().column,
+        ))
+        .end;
+        Located::new(
+            Expr::FieldAccess(FieldAccessExpr {
+                object: Box::new(object),
+                field,
+                id,
+            }),
+            Span::new(start_span, end_span),
+        )
+    }
+
+    /// Parses a `copy` method call, which may take named and shorthand arguments:
+    /// `object.copy(field = value, .field)` copies a data class with overrides
+    fn parse_copy_call(&mut self, object: LocatedExpr) -> Result<LocatedExpr, VeltranoError> {
+        self.advance(); // consume '('
+
+        let mut arguments = Vec::new();
+        let mut is_multiline = self.check(&TokenType::Newline);
+        if !self.check(&TokenType::RightParen) {
+            arguments = self.parse_function_arguments(&mut is_multiline)?;
+        }
+
+        self.skip_newlines_and_comments();
+        self.consume(&TokenType::RightParen, "Expected ')' after copy arguments")?;
+
+        let id = self.next_call_id;
+        self.next_call_id += 1;
+
+        let (mut args, mut named_args) = (Vec::new(), Vec::new());
+        for argument in arguments {
+            match argument {
+                Argument::Bare(expr, _) => args.push(expr),
+                other => named_args.push(other),
+            }
         }
+
+        let start_span = object.span.start.clone();
+        let end_span = Span::single(SourceLocation::new(
+            self.previous().line,
+            self.previous().column,
+        ))
+        .end;
+        Ok(Located::new(
+            Expr::MethodCall(MethodCallExpr {
+                object: Box::new(object),
+                method: "copy".to_string(),
+                args,
+                inline_comment: None,
+                id,
+                named_args,
+                is_multiline,
+            }),
+            Span::new(start_span, end_span),
+        ))
     }
 
     /// Parses a method call
@@ -746,6 +972,8 @@ impl Parser {
                 args,
                 inline_comment: comment,
                 id,
+                named_args: Vec::new(),
+                is_multiline: false,
             }),
             Span::new(start_span, end_span),
         ))
@@ -6,34 +6,198 @@
 //! - Block statements
 //! - Expression statements
 
+use super::utils::unescape_string;
 use super::Parser;
-use crate::ast::{CommentContext, CommentStmt};
 use crate::ast::{
-    DataClassField, DataClassStmt, FunDeclStmt, IfStmt, ImportStmt, Parameter, Stmt, VarDeclStmt,
-    WhileStmt,
+    AnnotatedStmt, Annotation, AnnotationArg, AssignStmt, DataClassField, DataClassStmt,
+    DestructureStmt, ExternFunStmt, FunDeclStmt, IfStmt, ImportStmt, Located, LocatedExpr,
+    MemberFunction, Parameter, Stmt, VarDeclStmt, WhileStmt,
 };
-use crate::error::{ErrorKind, VeltranoError};
+use crate::ast::{CommentContext, CommentStmt};
+use crate::error::{ErrorKind, SourceLocation, VeltranoError};
 use crate::lexer::TokenType;
+use crate::types::VeltranoType;
 use nonempty::NonEmpty;
 
 impl Parser {
     pub(super) fn declaration(&mut self) -> Result<NonEmpty<Stmt>, VeltranoError> {
-        if self.match_token(&TokenType::Fun) {
-            Ok(NonEmpty::singleton(self.function_declaration()?))
+        if let TokenType::RustBlock(code) = &self.peek().token_type {
+            let code = code.clone();
+            self.advance();
+            self.consume_newline()?;
+            Ok(NonEmpty::singleton(Stmt::RustBlock(code)))
+        } else if self.check(&TokenType::At) {
+            self.annotated_declaration()
+        } else if self.match_token(&TokenType::Fun) {
+            Ok(NonEmpty::singleton(self.function_declaration(Vec::new())?))
+        } else if self.check_async_function() {
+            Ok(NonEmpty::singleton(
+                self.async_function_declaration(Vec::new())?,
+            ))
+        } else if self.check_extern_function() {
+            Ok(NonEmpty::singleton(self.extern_function_declaration()?))
         } else if self.match_token(&TokenType::Val) {
             self.var_declaration()
         } else if self.match_token(&TokenType::Import) {
             Ok(NonEmpty::singleton(self.import_declaration()?))
         } else if self.match_token(&TokenType::Data) {
-            Ok(NonEmpty::singleton(self.data_class_declaration()?))
+            Ok(NonEmpty::singleton(
+                self.data_class_declaration(Vec::new())?,
+            ))
         } else {
             self.statement()
         }
     }
 
-    fn function_declaration(&mut self) -> Result<Stmt, VeltranoError> {
+    fn annotated_declaration(&mut self) -> Result<NonEmpty<Stmt>, VeltranoError> {
+        let annotations = self.annotations()?;
+
+        if self.match_token(&TokenType::Fun) {
+            Ok(NonEmpty::singleton(self.function_declaration(annotations)?))
+        } else if self.check_async_function() {
+            Ok(NonEmpty::singleton(
+                self.async_function_declaration(annotations)?,
+            ))
+        } else if self.match_token(&TokenType::Data) {
+            Ok(NonEmpty::singleton(
+                self.data_class_declaration(annotations)?,
+            ))
+        } else if self.check(&TokenType::Import) || self.is_at_end() {
+            Err(self
+                .syntax_error(
+                    "Annotations can only be applied to declarations and statements".to_string(),
+                )
+                .with_help("Place the annotation directly before the annotated item"))
+        } else {
+            // Other statements keep their annotations in a wrapper; trailing
+            // comments parsed along with the statement stay unannotated
+            let NonEmpty { head, tail } = self.declaration()?;
+            Ok(NonEmpty {
+                head: Stmt::Annotated(AnnotatedStmt {
+                    annotations,
+                    stmt: Box::new(head),
+                }),
+                tail,
+            })
+        }
+    }
+
+    /// Parse an optional `= expr` default value of a parameter or field
+    fn default_value(&mut self) -> Result<Option<LocatedExpr>, VeltranoError> {
+        if self.match_token(&TokenType::Equal) {
+            Ok(Some(self.expression()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parse a sequence of `@Name` or `@Name(arg, ...)` annotations
+    fn annotations(&mut self) -> Result<Vec<Annotation>, VeltranoError> {
+        let mut annotations = Vec::new();
+
+        while self.match_token(&TokenType::At) {
+            let at_token = self.previous();
+            let location = SourceLocation::new(at_token.line, at_token.column);
+            let name = self.consume_identifier("Expected annotation name after '@'")?;
+
+            let mut args = Vec::new();
+            if self.match_token(&TokenType::LeftParen) {
+                while !self.check(&TokenType::RightParen) {
+                    let arg = match &self.peek().token_type {
+                        TokenType::Identifier(name) => AnnotationArg::Identifier(name.clone()),
+                        TokenType::StringLiteral(value) => {
+                            AnnotationArg::String(unescape_string(value))
+                        }
+                        _ => {
+                            return Err(self.unexpected_token(
+                                "identifier or string literal as annotation argument",
+                            ))
+                        }
+                    };
+                    self.advance();
+                    args.push(arg);
+
+                    if !self.match_token(&TokenType::Comma) {
+                        break;
+                    }
+                }
+                self.consume(
+                    &TokenType::RightParen,
+                    "Expected ')' after annotation arguments",
+                )?;
+            }
+
+            annotations.push(Annotation {
+                name,
+                args,
+                location,
+            });
+
+            // Annotations may sit on their own lines above the declaration
+            self.skip_newlines_only();
+        }
+
+        Ok(annotations)
+    }
+
+    /// Whether the next tokens start a `suspend fun` or `async fun` declaration
+    fn check_async_function(&self) -> bool {
+        matches!(&self.peek().token_type, TokenType::Identifier(name) if name == "suspend" || name == "async")
+            && self
+                .tokens
+                .get(self.current + 1)
+                .is_some_and(|token| token.token_type == TokenType::Fun)
+    }
+
+    fn async_function_declaration(
+        &mut self,
+        annotations: Vec<Annotation>,
+    ) -> Result<Stmt, VeltranoError> {
+        self.advance(); // consume 'suspend' or 'async'
+        self.advance(); // consume 'fun'
+        let mut declaration = self.function_declaration(annotations)?;
+        if let Stmt::FunDecl(fun_decl) = &mut declaration {
+            fun_decl.is_async = true;
+        }
+        Ok(declaration)
+    }
+
+    fn function_declaration(
+        &mut self,
+        annotations: Vec<Annotation>,
+    ) -> Result<Stmt, VeltranoError> {
+        // fun<@a, @b> name(...)
+        let lifetime_params = self.parse_lifetime_params()?;
+        let location = SourceLocation::new(self.peek().line, self.peek().column);
         let name = self.consume_identifier("Expected function name")?;
+        let params = self.parameter_list()?;
+        let return_type = self.return_type()?;
+
+        // Skip any newlines and comments before the opening brace
+        self.skip_newlines_and_comments();
 
+        self.consume(&TokenType::LeftBrace, "Expected '{' before function body")?;
+
+        // Set context flag before parsing function body
+        let was_in_function_body = self.in_function_body;
+        self.in_function_body = true;
+        let body = Box::new(self.block_statement()?);
+        self.in_function_body = was_in_function_body;
+
+        Ok(Stmt::FunDecl(FunDeclStmt {
+            name: name.clone(),
+            params,
+            return_type,
+            body,
+            annotations,
+            lifetime_params,
+            is_async: false, // Set by async_function_declaration
+            location,
+        }))
+    }
+
+    /// Parse a parenthesized parameter list `(name: Type = default, ...)`
+    fn parameter_list(&mut self) -> Result<Vec<Parameter>, VeltranoError> {
         self.consume(&TokenType::LeftParen, "Expected '(' after function name")?;
 
         let mut params = Vec::new();
@@ -64,6 +228,7 @@ impl Parser {
                 self.advance(); // consume the colon
 
                 let param_type = self.parse_type()?;
+                let default_value = self.default_value()?;
 
                 // Capture comment immediately after the parameter type
                 let inline_comment = self.skip_newlines_and_capture_comment();
@@ -71,6 +236,7 @@ impl Parser {
                 params.push(Parameter {
                     name: param_name,
                     param_type,
+                    default_value,
                     inline_comment,
                 });
 
@@ -98,33 +264,54 @@ impl Parser {
 
         self.consume(&TokenType::RightParen, "Expected ')' after parameters")?;
 
-        let return_type = if self.match_token(&TokenType::Colon) {
-            Some(self.parse_type()?)
-        } else {
-            None
-        };
+        Ok(params)
+    }
 
-        // Skip any newlines and comments before the opening brace
-        self.skip_newlines_and_comments();
+    /// Parse an optional `: Type` return type
+    fn return_type(&mut self) -> Result<Option<Located<VeltranoType>>, VeltranoError> {
+        if self.match_token(&TokenType::Colon) {
+            Ok(Some(self.parse_type()?))
+        } else {
+            Ok(None)
+        }
+    }
 
-        self.consume(&TokenType::LeftBrace, "Expected '{' before function body")?;
+    /// Whether the next tokens start an `extern "ABI" fun` declaration
+    fn check_extern_function(&self) -> bool {
+        matches!(&self.peek().token_type, TokenType::Identifier(name) if name == "extern")
+            && self
+                .tokens
+                .get(self.current + 1)
+                .is_some_and(|token| matches!(token.token_type, TokenType::StringLiteral(_)))
+    }
 
-        // Set context flag before parsing function body
-        let was_in_function_body = self.in_function_body;
-        self.in_function_body = true;
-        let body = Box::new(self.block_statement()?);
-        self.in_function_body = was_in_function_body;
+    fn extern_function_declaration(&mut self) -> Result<Stmt, VeltranoError> {
+        // extern "C" fun name(params): Type
+        let location = SourceLocation::new(self.peek().line, self.peek().column);
+        self.advance(); // consume 'extern'
+        let TokenType::StringLiteral(abi) = self.advance().token_type.clone() else {
+            unreachable!("check_extern_function requires an ABI string");
+        };
+        self.consume(&TokenType::Fun, "Expected 'fun' after extern ABI")?;
+        let name = self.consume_identifier("Expected function name")?;
+        let params = self.parameter_list()?;
+        let return_type = self.return_type()?;
+        self.consume_newline()?;
 
-        Ok(Stmt::FunDecl(FunDeclStmt {
-            name: name.clone(),
+        Ok(Stmt::ExternFun(ExternFunStmt {
+            abi,
+            name,
             params,
             return_type,
-            body,
-            has_hidden_bump: false, // Will be set by analyze_bump_usage
+            location,
         }))
     }
 
     fn var_declaration(&mut self) -> Result<NonEmpty<Stmt>, VeltranoError> {
+        if self.match_token(&TokenType::LeftParen) {
+            return self.destructuring_declaration();
+        }
+
         let name = self.consume_identifier("Expected variable name")?;
 
         let type_annotation = if self.match_token(&TokenType::Colon) {
@@ -161,6 +348,50 @@ impl Parser {
         }
     }
 
+    fn destructuring_declaration(&mut self) -> Result<NonEmpty<Stmt>, VeltranoError> {
+        // val (a, b, ...) = expr
+        let mut names =
+            vec![self.consume_identifier("Expected variable name in destructuring declaration")?];
+        while self.match_token(&TokenType::Comma) {
+            names.push(
+                self.consume_identifier("Expected variable name in destructuring declaration")?,
+            );
+        }
+        self.consume(
+            &TokenType::RightParen,
+            "Expected ')' after destructuring variables",
+        )?;
+        self.consume(
+            &TokenType::Equal,
+            "Expected '=' after destructuring declaration",
+        )?;
+        let initializer = self.expression()?;
+
+        let inline_comment = self.consume_newline()?;
+
+        let id = self.next_call_id;
+        self.next_call_id += 1;
+
+        let destructure = Stmt::Destructure(DestructureStmt {
+            names,
+            initializer,
+            id,
+        });
+
+        // Add inline comment as a separate statement if present
+        if let Some((content, whitespace)) = inline_comment {
+            let comment = Stmt::Comment(CommentStmt {
+                content,
+                is_block_comment: false,
+                preceding_whitespace: whitespace,
+                context: CommentContext::EndOfLine,
+            });
+            Ok(NonEmpty::from((destructure, vec![comment])))
+        } else {
+            Ok(NonEmpty::singleton(destructure))
+        }
+    }
+
     fn import_declaration(&mut self) -> Result<Stmt, VeltranoError> {
         // Capture the location of the import keyword
         let import_token = self.previous();
@@ -187,10 +418,15 @@ impl Parser {
         }))
     }
 
-    fn data_class_declaration(&mut self) -> Result<Stmt, VeltranoError> {
+    fn data_class_declaration(
+        &mut self,
+        annotations: Vec<Annotation>,
+    ) -> Result<Stmt, VeltranoError> {
         // data class ClassName(val field1: Type1, val field2: Type2, ...)
         self.consume(&TokenType::Class, "Expected 'class' after 'data'")?;
+        let location = SourceLocation::new(self.peek().line, self.peek().column);
         let name = self.consume_identifier("Expected data class name after 'data class'")?;
+        let lifetime_params = self.parse_lifetime_params()?;
 
         self.consume(&TokenType::LeftParen, "Expected '(' after data class name")?;
 
@@ -200,11 +436,17 @@ impl Parser {
                 // Skip any newlines and comments before parsing the field
                 self.skip_newlines_and_comments();
 
+                let field_annotations = self.annotations()?;
+                if !field_annotations.is_empty() {
+                    self.skip_newlines_and_comments();
+                }
+
                 // Each field starts with 'val'
                 self.consume(&TokenType::Val, "Expected 'val' before field name")?;
                 let field_name = self.consume_identifier("Expected field name after 'val'")?;
                 self.consume(&TokenType::Colon, "Expected ':' after field name")?;
                 let field_type = self.parse_type()?;
+                let default_value = self.default_value()?;
 
                 // Capture comment immediately after the field type
                 let inline_comment = self.skip_newlines_and_capture_comment();
@@ -212,7 +454,9 @@ impl Parser {
                 fields.push(DataClassField {
                     name: field_name,
                     field_type,
+                    default_value,
                     inline_comment,
+                    annotations: field_annotations,
                 });
 
                 if !self.match_token(&TokenType::Comma) {
@@ -240,9 +484,65 @@ impl Parser {
             &TokenType::RightParen,
             "Expected ')' after data class fields",
         )?;
+
+        let methods = if self.check(&TokenType::LeftBrace) {
+            self.data_class_body()?
+        } else {
+            Vec::new()
+        };
         self.consume_newline()?;
 
-        Ok(Stmt::DataClass(DataClassStmt { name, fields }))
+        Ok(Stmt::DataClass(DataClassStmt {
+            name,
+            fields,
+            annotations,
+            lifetime_params,
+            methods,
+            location,
+        }))
+    }
+
+    /// Parse the `{ ... }` body of a data class, which holds member functions
+    fn data_class_body(&mut self) -> Result<Vec<MemberFunction>, VeltranoError> {
+        self.consume(&TokenType::LeftBrace, "Expected '{' before data class body")?;
+
+        let mut methods = Vec::new();
+        loop {
+            self.skip_newlines_and_comments();
+            if self.match_token(&TokenType::RightBrace) {
+                break;
+            }
+
+            let annotations = self.annotations()?;
+            let location = SourceLocation::new(self.peek().line, self.peek().column);
+
+            // `operator` and `override` are only keywords in front of a member function
+            let modifier = match &self.peek().token_type {
+                TokenType::Identifier(name) if name == "operator" || name == "override" => {
+                    Some(name.clone())
+                }
+                _ => None,
+            };
+            if modifier.is_some() {
+                self.advance();
+            }
+
+            if !self.match_token(&TokenType::Fun) {
+                return Err(self
+                    .unexpected_token("'fun', 'operator fun' or 'override fun' in data class body")
+                    .with_help("Data class bodies can only contain member functions"));
+            }
+            if let Stmt::FunDecl(fun_decl) = self.function_declaration(annotations)? {
+                methods.push(MemberFunction {
+                    is_operator: modifier.as_deref() == Some("operator"),
+                    is_override: modifier.as_deref() == Some("override"),
+                    fun_decl,
+                    location,
+                });
+            }
+        }
+
+        Ok(methods)
     }
 
     pub(super) fn statement(&mut self) -> Result<NonEmpty<Stmt>, VeltranoError> {
@@ -254,6 +554,13 @@ impl Parser {
             Ok(NonEmpty::singleton(self.return_statement()?))
         } else if self.match_token(&TokenType::LeftBrace) {
             Ok(NonEmpty::singleton(self.block_statement()?))
+        } else if self.check_unsafe_block() {
+            self.advance(); // consume 'unsafe'
+            self.advance(); // consume '{'
+            let Stmt::Block(statements) = self.block_statement()? else {
+                unreachable!("block_statement always returns a block");
+            };
+            Ok(NonEmpty::singleton(Stmt::Unsafe(statements)))
         } else {
             self.expression_statement()
         }
@@ -334,6 +641,16 @@ impl Parser {
         Ok(Stmt::Return(value))
     }
 
+    /// Whether the next tokens start an `unsafe { ... }` block; `unsafe` is only a
+    /// keyword in front of a block
+    fn check_unsafe_block(&self) -> bool {
+        matches!(&self.peek().token_type, TokenType::Identifier(name) if name == "unsafe")
+            && self
+                .tokens
+                .get(self.current + 1)
+                .is_some_and(|token| token.token_type == TokenType::LeftBrace)
+    }
+
     pub(super) fn block_statement(&mut self) -> Result<Stmt, VeltranoError> {
         let mut statements = Vec::new();
 
@@ -365,10 +682,17 @@ impl Parser {
 
     fn expression_statement(&mut self) -> Result<NonEmpty<Stmt>, VeltranoError> {
         let expr = self.expression()?;
+        let expr_stmt = if self.match_token(&TokenType::Equal) {
+            let value = self.expression()?;
+            Stmt::Assign(AssignStmt {
+                target: expr,
+                value,
+            })
+        } else {
+            Stmt::Expression(expr)
+        };
         let inline_comment = self.consume_newline()?;
 
-        let expr_stmt = Stmt::Expression(expr);
-
         // Add inline comment as a separate statement if present
         if let Some((content, whitespace)) = inline_comment {
             let comment = Stmt::Comment(CommentStmt {
@@ -2,8 +2,13 @@
 //!
 //! This module contains all type parsing logic including:
 //! - Primitive types (integers, bool, char, etc.)
-//! - Reference types (Ref, Own, MutRef)
+//! - Reference types (Ref, Own, MutRef) and raw pointers (Ptr, MutPtr)
 //! - Container types (Box, Vec, Array, Option, Result)
+//! - Shared and interior-mutable types (Rc, Arc, RefCell, Cell)
+//! - Map and set types (HashMap, BTreeMap, HashSet)
+//! - Tuple types ((A, B), Pair, Triple)
+//! - Function types ((A, B) -> R)
+//! - Trait object and impl Trait types (Dyn<Display>, Impl<Iterator<I64>>)
 //! - Custom types
 
 use super::Parser;
@@ -23,11 +28,17 @@ impl Parser {
     }
 
     fn parse_type_inner(&mut self) -> Result<VeltranoType, VeltranoError> {
+        if self.match_token(&TokenType::LeftParen) {
+            return self.parse_tuple_type();
+        }
+
         if let TokenType::Identifier(type_name) = &self.peek().token_type {
             let type_name = type_name.clone();
             self.advance();
 
-            match type_name.as_str() {
+            // An optional lifetime label follows the type name: `Str@a`, `Ref@a<Str@b>`
+            let lifetime = self.parse_lifetime_label()?;
+            let parsed = match type_name.as_str() {
                 // Signed integers
                 "I32" => Ok(VeltranoType::i32()),
                 "I64" => Ok(VeltranoType::i64()),
@@ -48,17 +59,60 @@ impl Parser {
                 "Own" => self.parse_own_type(),
                 "MutRef" => self.parse_mutref_type(),
                 "Box" => self.parse_box_type(),
+                "Rc" => self.parse_wrapper_type("Rc", VeltranoType::rc),
+                "Arc" => self.parse_wrapper_type("Arc", VeltranoType::arc),
+                "RefCell" => self.parse_wrapper_type("RefCell", VeltranoType::ref_cell),
+                "Cell" => self.parse_wrapper_type("Cell", VeltranoType::cell),
+                "Ptr" => self.parse_ptr_type(),
+                "MutPtr" => self.parse_mut_ptr_type(),
                 "Vec" => self.parse_vec_type(),
                 "Array" => self.parse_array_type(),
                 "Option" => self.parse_option_type(),
                 "Result" => self.parse_result_type(),
+                "HashMap" => self.parse_map_type("HashMap", VeltranoType::hash_map),
+                "BTreeMap" => self.parse_map_type("BTreeMap", VeltranoType::btree_map),
+                "HashSet" => self.parse_wrapper_type("HashSet", VeltranoType::hash_set),
+                "Dyn" => self.parse_trait_bound_type("Dyn", VeltranoType::dyn_trait),
+                "Impl" => self.parse_trait_bound_type("Impl", VeltranoType::impl_trait),
+                "Pair" => self.parse_fixed_tuple_type("Pair", 2),
+                "Triple" => self.parse_fixed_tuple_type("Triple", 3),
                 _ => Ok(VeltranoType::custom(type_name)), // naturally referenced
-            }
+            };
+            parsed.map(|parsed_type| parsed_type.with_lifetime(lifetime))
         } else {
             Err(self.syntax_error("Expected type name".to_string()))
         }
     }
 
+    /// Parse an optional `@label` lifetime label
+    fn parse_lifetime_label(&mut self) -> Result<Option<String>, VeltranoError> {
+        if self.match_token(&TokenType::At) {
+            let label = self.consume_identifier("Expected lifetime label name after '@'")?;
+            Ok(Some(label))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parse lifetime parameter declarations `<@a, @b>` if present
+    pub(super) fn parse_lifetime_params(&mut self) -> Result<Vec<String>, VeltranoError> {
+        let mut params = Vec::new();
+        if self.match_token(&TokenType::Less) {
+            loop {
+                self.consume(&TokenType::At, "Expected '@' before lifetime parameter")?;
+                params.push(self.consume_identifier("Expected lifetime parameter name after '@'")?);
+                if !self.match_token(&TokenType::Comma) {
+                    break;
+                }
+            }
+            self.consume(
+                &TokenType::Greater,
+                "Expected '>' after lifetime parameters",
+            )?;
+        }
+        Ok(params)
+    }
+
     fn parse_ref_type(&mut self) -> Result<VeltranoType, VeltranoError> {
         self.consume(&TokenType::Less, "Expected '<' after Ref")?;
         let inner_type = self.parse_type()?;
@@ -89,6 +143,32 @@ impl Parser {
         Ok(VeltranoType::boxed(inner_type.node))
     }
 
+    /// Parse a single-parameter wrapper such as `Rc<T>` or `RefCell<T>`
+    fn parse_wrapper_type(
+        &mut self,
+        name: &str,
+        wrap: fn(VeltranoType) -> VeltranoType,
+    ) -> Result<VeltranoType, VeltranoError> {
+        self.consume(&TokenType::Less, &format!("Expected '<' after {}", name))?;
+        let inner_type = self.parse_type()?;
+        self.consume(&TokenType::Greater, "Expected '>' after type parameter")?;
+        Ok(wrap(inner_type.node))
+    }
+
+    fn parse_ptr_type(&mut self) -> Result<VeltranoType, VeltranoError> {
+        self.consume(&TokenType::Less, "Expected '<' after Ptr")?;
+        let inner_type = self.parse_type()?;
+        self.consume(&TokenType::Greater, "Expected '>' after type parameter")?;
+        Ok(VeltranoType::ptr(inner_type.node))
+    }
+
+    fn parse_mut_ptr_type(&mut self) -> Result<VeltranoType, VeltranoError> {
+        self.consume(&TokenType::Less, "Expected '<' after MutPtr")?;
+        let inner_type = self.parse_type()?;
+        self.consume(&TokenType::Greater, "Expected '>' after type parameter")?;
+        Ok(VeltranoType::mut_ptr(inner_type.node))
+    }
+
     fn parse_vec_type(&mut self) -> Result<VeltranoType, VeltranoError> {
         self.consume(&TokenType::Less, "Expected '<' after Vec")?;
         let inner_type = self.parse_type()?;
@@ -127,4 +207,93 @@ impl Parser {
         self.consume(&TokenType::Greater, "Expected '>' after Result error type")?;
         Ok(VeltranoType::result(ok_type.node, err_type.node))
     }
+
+    /// Parse a map type such as `HashMap<K, V>`
+    fn parse_map_type(
+        &mut self,
+        name: &str,
+        map: fn(VeltranoType, VeltranoType) -> VeltranoType,
+    ) -> Result<VeltranoType, VeltranoError> {
+        self.consume(&TokenType::Less, &format!("Expected '<' after {}", name))?;
+        let key_type = self.parse_type()?;
+        self.consume(&TokenType::Comma, "Expected ',' after map key type")?;
+        let value_type = self.parse_type()?;
+        self.consume(&TokenType::Greater, "Expected '>' after map value type")?;
+        Ok(map(key_type.node, value_type.node))
+    }
+
+    /// Parse a trait bound type such as `Dyn<Display>` or `Impl<Iterator<I64>>`
+    fn parse_trait_bound_type(
+        &mut self,
+        name: &str,
+        bound: fn(&str, Vec<VeltranoType>) -> VeltranoType,
+    ) -> Result<VeltranoType, VeltranoError> {
+        self.consume(&TokenType::Less, &format!("Expected '<' after {}", name))?;
+        let TokenType::Identifier(trait_name) = &self.peek().token_type else {
+            return Err(self.syntax_error(format!("Expected a trait name after {}<", name)));
+        };
+        let trait_name = trait_name.clone();
+        self.advance();
+
+        let mut trait_args = Vec::new();
+        if self.match_token(&TokenType::Less) {
+            trait_args.push(self.parse_type()?.node);
+            while self.match_token(&TokenType::Comma) {
+                trait_args.push(self.parse_type()?.node);
+            }
+            self.consume(
+                &TokenType::Greater,
+                "Expected '>' after trait type arguments",
+            )?;
+        }
+        self.consume(&TokenType::Greater, "Expected '>' after trait")?;
+        Ok(bound(&trait_name, trait_args))
+    }
+
+    /// Parse a tuple type `(T1, T2, ...)` or a function type `(T1, T2) -> R`
+    /// after the opening parenthesis
+    fn parse_tuple_type(&mut self) -> Result<VeltranoType, VeltranoError> {
+        let mut elements = Vec::new();
+        if !self.check(&TokenType::RightParen) {
+            elements.push(self.parse_type()?.node);
+            while self.match_token(&TokenType::Comma) {
+                elements.push(self.parse_type()?.node);
+            }
+        }
+        self.consume(
+            &TokenType::RightParen,
+            "Expected ')' after tuple element types",
+        )?;
+
+        if self.match_token(&TokenType::Arrow) {
+            let return_type = self.parse_type()?;
+            return Ok(VeltranoType::function(elements, return_type.node));
+        }
+
+        if elements.len() < 2 {
+            return Err(self.syntax_error(
+                "Tuple types need at least two elements; use the element type directly".to_string(),
+            ));
+        }
+        Ok(VeltranoType::tuple(elements))
+    }
+
+    /// Parse `Pair<A, B>` / `Triple<A, B, C>`, which are aliases for tuple types
+    fn parse_fixed_tuple_type(
+        &mut self,
+        name: &str,
+        arity: usize,
+    ) -> Result<VeltranoType, VeltranoError> {
+        self.consume(&TokenType::Less, &format!("Expected '<' after {}", name))?;
+        let mut elements = vec![self.parse_type()?.node];
+        for _ in 1..arity {
+            self.consume(
+                &TokenType::Comma,
+                &format!("Expected ',' between {} type parameters", name),
+            )?;
+            elements.push(self.parse_type()?.node);
+        }
+        self.consume(&TokenType::Greater, "Expected '>' after type parameters")?;
+        Ok(VeltranoType::tuple(elements))
+    }
 }
@@ -224,3 +224,17 @@ pub struct RustTypeSignature {
     pub lifetimes: Vec<String>,                 // ["'a"]
     pub bounds: Vec<String>,                    // Trait bounds like "T: Clone"
 }
+
+impl RustTypeSignature {
+    /// The return type of an `async fn` declared to return this type
+    pub fn into_future(self) -> Self {
+        Self {
+            raw: format!("impl Future<Output = {}>", self.raw),
+            parsed: self
+                .parsed
+                .map(|output| super::types::RustType::Future(Box::new(output))),
+            lifetimes: self.lifetimes,
+            bounds: self.bounds,
+        }
+    }
+}
@@ -29,6 +29,8 @@ pub use parser::RustTypeParser;
 #[allow(unused_imports)]
 pub use registry::DynamicRustRegistry;
 pub use registry::RustInteropRegistry;
+#[allow(unused_imports)]
+pub use registry::{OperatorImpl, OperatorImplMatch};
 /// Exposed for testing only. Not part of the stable public API.
 #[doc(hidden)]
 #[allow(unused_imports)]
@@ -42,7 +44,7 @@ pub use stdlib_querier::StdLibQuerier;
 #[doc(hidden)]
 #[allow(unused_imports)]
 pub use syn_querier::SynQuerier;
-pub use types::{RustType, SelfKind};
+pub use types::{associated_type_name, trait_path, RustType, SelfKind};
 pub use utils::camel_to_snake_case;
 
 use crate::error::VeltranoError;
@@ -63,7 +65,7 @@ pub enum ExternItem {
         _self_kind: SelfKind,
         _params: Vec<(String, RustType)>,
         _return_type: RustType,
-        _is_unsafe: bool,
+        is_unsafe: bool,
     },
     _Type {
         name: String,
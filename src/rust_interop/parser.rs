@@ -21,6 +21,14 @@ impl RustTypeParser {
             });
         }
 
+        // Handle raw pointers
+        if let Some(rest) = trimmed.strip_prefix("*const ") {
+            return Ok(RustType::ConstPtr(Box::new(Self::parse(rest)?)));
+        }
+        if let Some(rest) = trimmed.strip_prefix("*mut ") {
+            return Ok(RustType::MutPtr(Box::new(Self::parse(rest)?)));
+        }
+
         if let Some(rest) = trimmed.strip_prefix("&") {
             // Check for lifetime
             let (lifetime, rest) = if rest.starts_with('\'') {
@@ -42,6 +50,48 @@ impl RustTypeParser {
             });
         }
 
+        // Handle impl Future<Output = T>, as returned by async functions
+        let compact = trimmed.replace(' ', "");
+        if let Some(output) = compact
+            .strip_prefix("impl")
+            .map(|s| s.strip_prefix("std::future::").unwrap_or(s))
+            .and_then(|s| s.strip_prefix("Future<Output="))
+            .and_then(|s| s.strip_suffix('>'))
+        {
+            return Ok(RustType::Future(Box::new(Self::parse(output)?)));
+        }
+
+        // Handle dyn Trait and impl Trait; the trait argument is given directly
+        // (`Into<T>`) or through its associated type (`Iterator<Item = T>`)
+        let bound = trimmed
+            .strip_prefix("dyn ")
+            .map(|rest| (true, rest))
+            .or_else(|| trimmed.strip_prefix("impl ").map(|rest| (false, rest)));
+        if let Some((is_dyn, rest)) = bound {
+            let (path, generics) = match rest.split_once('<') {
+                Some((path, args)) => {
+                    let arg = args
+                        .strip_suffix('>')
+                        .ok_or_else(|| format!("Invalid trait bound: {}", rest))?;
+                    let arg = arg.split_once('=').map_or(arg, |(_, ty)| ty);
+                    (path.trim(), vec![Self::parse(arg)?])
+                }
+                None => (rest.trim(), vec![]),
+            };
+            let trait_name = path.rsplit("::").next().unwrap_or(path).to_string();
+            return Ok(if is_dyn {
+                RustType::Dyn {
+                    trait_name,
+                    generics,
+                }
+            } else {
+                RustType::Impl {
+                    trait_name,
+                    generics,
+                }
+            });
+        }
+
         // Handle Box<T>
         if let Some(inner) = trimmed
             .strip_prefix("Box<")
@@ -50,6 +100,28 @@ impl RustTypeParser {
             return Ok(RustType::Box(Box::new(Self::parse(inner)?)));
         }
 
+        // Handle Rc<T>, Arc<T>, RefCell<T> and Cell<T>, with or without their std path
+        let wrappers = [
+            (
+                "std::rc::",
+                "Rc<",
+                RustType::Rc as fn(Box<RustType>) -> RustType,
+            ),
+            ("std::sync::", "Arc<", RustType::Arc),
+            ("std::cell::", "RefCell<", RustType::RefCell),
+            ("std::cell::", "Cell<", RustType::Cell),
+        ];
+        for (path, prefix, wrap) in wrappers {
+            if let Some(inner) = trimmed
+                .strip_prefix(path)
+                .unwrap_or(trimmed)
+                .strip_prefix(prefix)
+                .and_then(|s| s.strip_suffix('>'))
+            {
+                return Ok(wrap(Box::new(Self::parse(inner)?)));
+            }
+        }
+
         // Handle Vec<T>
         if let Some(inner) = trimmed
             .strip_prefix("Vec<")
@@ -71,33 +143,43 @@ impl RustTypeParser {
             .strip_prefix("Result<")
             .and_then(|s| s.strip_suffix('>'))
         {
-            // Split by comma, but need to handle nested generics
-            let mut depth = 0;
-            let mut split_pos = None;
-            for (i, ch) in inner.chars().enumerate() {
-                match ch {
-                    '<' => depth += 1,
-                    '>' => depth -= 1,
-                    ',' if depth == 0 => {
-                        split_pos = Some(i);
-                        break;
-                    }
-                    _ => {}
-                }
-            }
+            let (ok_type, err_type) = Self::split_type_pair(inner)
+                .ok_or_else(|| "Invalid Result type: missing error type".to_string())?;
+            return Ok(RustType::Result {
+                ok: Box::new(Self::parse(ok_type)?),
+                err: Box::new(Self::parse(err_type)?),
+            });
+        }
 
-            if let Some(pos) = split_pos {
-                let ok_type = inner[..pos].trim();
-                let err_type = inner[pos + 1..].trim();
-                return Ok(RustType::Result {
-                    ok: Box::new(Self::parse(ok_type)?),
-                    err: Box::new(Self::parse(err_type)?),
+        // Handle HashMap<K, V> and BTreeMap<K, V>, with or without their std path
+        let unqualified = trimmed
+            .strip_prefix("std::collections::")
+            .unwrap_or(trimmed);
+        for prefix in ["HashMap<", "BTreeMap<"] {
+            if let Some(inner) = unqualified
+                .strip_prefix(prefix)
+                .and_then(|s| s.strip_suffix('>'))
+            {
+                let (key_type, value_type) = Self::split_type_pair(inner)
+                    .ok_or_else(|| format!("Invalid map type '{}': missing value type", trimmed))?;
+                let key = Box::new(Self::parse(key_type)?);
+                let value = Box::new(Self::parse(value_type)?);
+                return Ok(if prefix == "HashMap<" {
+                    RustType::HashMap { key, value }
+                } else {
+                    RustType::BTreeMap { key, value }
                 });
-            } else {
-                return Err("Invalid Result type: missing error type".to_string());
             }
         }
 
+        // Handle HashSet<T>
+        if let Some(inner) = unqualified
+            .strip_prefix("HashSet<")
+            .and_then(|s| s.strip_suffix('>'))
+        {
+            return Ok(RustType::HashSet(Box::new(Self::parse(inner)?)));
+        }
+
         // Handle basic types
         match trimmed {
             "i32" => Ok(RustType::I32),
@@ -126,4 +208,19 @@ impl RustTypeParser {
             }
         }
     }
+
+    /// Split the arguments of a two-parameter generic such as `Result<T, E>`
+    /// at its top-level comma
+    fn split_type_pair(inner: &str) -> Option<(&str, &str)> {
+        let mut depth = 0;
+        for (i, ch) in inner.char_indices() {
+            match ch {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => return Some((inner[..i].trim(), inner[i + 1..].trim())),
+                _ => {}
+            }
+        }
+        None
+    }
 }
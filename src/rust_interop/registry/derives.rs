@@ -0,0 +1,185 @@
+//! Derived traits of data classes.
+//!
+//! Infers which traits each data class can `#[derive]` from its field types
+//! and registers them, so that trait queries on the class (most importantly
+//! the `Copy` check that makes it a value type) see its derives. Data classes
+//! also implement `Display`, and through it `ToString`, which the code
+//! generator always emits for them.
+
+use super::static_registry::RustInteropRegistry;
+use crate::ast::{AnnotationArg, DataClassStmt};
+use crate::rust_interop::types::RustType;
+use crate::types::{TypeConstructor, VeltranoType};
+use std::collections::HashMap;
+
+/// Traits derived when every field implements them, in `#[derive]` order
+const INFERRED_TRAITS: &[&str] = &["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash"];
+
+/// Inferred traits listed ahead of the `@Derive` ones
+const LEADING_TRAITS: &[&str] = &["Debug", "Clone"];
+
+/// Traits a user-declared `operator fun equals`/`compareTo` implements by hand
+const EQUALITY_TRAITS: &[&str] = &["PartialEq", "Eq", "Hash"];
+
+impl RustInteropRegistry {
+    /// Infer and register the derives of all data classes, returning each class's
+    /// `#[derive]` list: `Debug` and `Clone` when the fields allow them, the
+    /// `@Derive` traits, then the other inferred ones
+    pub fn register_data_class_derives(
+        &mut self,
+        data_classes: &[&DataClassStmt],
+    ) -> HashMap<String, Vec<String>> {
+        let mut derives = HashMap::new();
+        for data_class in data_classes {
+            let mut traits = Vec::new();
+            let defines_equals = data_class
+                .methods
+                .iter()
+                .any(|member| member.is_operator && member.fun_decl.name == "equals");
+            for annotation in data_class.annotations.iter().filter(|a| a.name == "Derive") {
+                for arg in &annotation.args {
+                    if let AnnotationArg::Identifier(trait_name) = arg {
+                        // A user-defined `equals` is emitted as the PartialEq impl
+                        if defines_equals && trait_name == "PartialEq" {
+                            self.register_local_traits(
+                                &data_class.name,
+                                std::slice::from_ref(trait_name),
+                            );
+                        } else if !traits.contains(trait_name) {
+                            traits.push(trait_name.clone());
+                        }
+                    }
+                }
+            }
+            self.register_local_traits(&data_class.name, &traits);
+            self.register_local_traits(
+                &data_class.name,
+                &["Display".to_string(), "ToString".to_string()],
+            );
+            derives.insert(data_class.name.clone(), traits);
+        }
+
+        // Fields may be other data classes, so repeat until no class gains a trait
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for data_class in data_classes {
+                for trait_name in INFERRED_TRAITS {
+                    let traits = &derives[&data_class.name];
+                    if traits.iter().any(|t| t == trait_name)
+                        || !self.can_derive(data_class, trait_name)
+                    {
+                        continue;
+                    }
+                    let trait_name = trait_name.to_string();
+                    self.register_local_traits(&data_class.name, std::slice::from_ref(&trait_name));
+                    if let Some(traits) = derives.get_mut(&data_class.name) {
+                        traits.push(trait_name);
+                    }
+                    changed = true;
+                }
+            }
+        }
+
+        for traits in derives.values_mut() {
+            traits.sort_by_key(|t| {
+                LEADING_TRAITS
+                    .iter()
+                    .position(|leading| leading == t)
+                    .unwrap_or(LEADING_TRAITS.len())
+            });
+        }
+        derives
+    }
+
+    /// Register derives inferred earlier, given as each class's `#[derive]` list
+    pub fn register_derived_traits(&mut self, derives: &HashMap<String, Vec<String>>) {
+        for (class_name, traits) in derives {
+            self.register_local_traits(class_name, traits);
+            self.register_local_traits(
+                class_name,
+                &["Display".to_string(), "ToString".to_string()],
+            );
+        }
+    }
+
+    fn can_derive(&mut self, data_class: &DataClassStmt, trait_name: &str) -> bool {
+        let field_types: Vec<&VeltranoType> = data_class
+            .fields
+            .iter()
+            .map(|field| &field.field_type.node)
+            .collect();
+
+        if trait_name == "Copy" {
+            // Only classes of plain values become Copy; references would need lifetimes
+            return data_class.lifetime_params.is_empty()
+                && field_types.iter().all(|field_type| {
+                    !Self::contains_reference(field_type) && field_type.implements_copy(self)
+                });
+        }
+
+        let overrides_equality = data_class.methods.iter().any(|member| {
+            member.is_operator && matches!(member.fun_decl.name.as_str(), "equals" | "compareTo")
+        });
+        if overrides_equality && EQUALITY_TRAITS.contains(&trait_name) {
+            return false;
+        }
+
+        field_types
+            .iter()
+            .all(|field_type| self.field_implements_trait(field_type, trait_name, false))
+    }
+
+    /// Whether a field type implements a trait that wrappers and collections forward
+    /// from their elements; `owned` marks the value of an `Own<T>` or the pointee
+    /// of a `Box`, which isn't behind a reference
+    fn field_implements_trait(
+        &mut self,
+        field_type: &VeltranoType,
+        trait_name: &str,
+        owned: bool,
+    ) -> bool {
+        // Shared references can always be cloned, mutable ones never
+        if trait_name == "Clone" && !owned {
+            match field_type.to_rust_type(self) {
+                RustType::Ref { .. } => return true,
+                RustType::MutRef { .. } => return false,
+                _ => {}
+            }
+        }
+        match &field_type.constructor {
+            TypeConstructor::Generic(_, constraints) => constraints.iter().any(|c| c == trait_name),
+            TypeConstructor::Own | TypeConstructor::Box => field_type
+                .args
+                .iter()
+                .all(|arg| self.field_implements_trait(arg, trait_name, true)),
+            TypeConstructor::Ref
+            | TypeConstructor::MutRef
+            | TypeConstructor::Vec
+            | TypeConstructor::Option
+            | TypeConstructor::Result
+            | TypeConstructor::Array(_)
+            | TypeConstructor::Slice
+            | TypeConstructor::Tuple => field_type
+                .args
+                .iter()
+                .all(|arg| self.field_implements_trait(arg, trait_name, false)),
+            _ => {
+                // Naturally referenced types are queried for the type behind the reference
+                let mut rust_type = field_type.to_rust_type(self);
+                while let RustType::Ref { inner, .. } = rust_type {
+                    rust_type = *inner;
+                }
+                self.type_implements_trait(&rust_type, trait_name)
+                    .unwrap_or(false)
+            }
+        }
+    }
+
+    fn contains_reference(field_type: &VeltranoType) -> bool {
+        matches!(
+            field_type.constructor,
+            TypeConstructor::Ref | TypeConstructor::MutRef
+        ) || field_type.args.iter().any(Self::contains_reference)
+    }
+}
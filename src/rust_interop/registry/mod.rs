@@ -2,8 +2,11 @@
 //!
 //! Provides static and dynamic registries for type information.
 
+mod derives;
 mod dynamic_registry;
+mod operators;
 mod static_registry;
 
 pub use dynamic_registry::DynamicRustRegistry;
+pub use operators::{OperatorImpl, OperatorImplMatch};
 pub use static_registry::RustInteropRegistry;
@@ -0,0 +1,209 @@
+//! Standard library operator trait implementations.
+//!
+//! Answers which `std::ops` and `std::cmp` impls a type has, with their `Rhs`
+//! and `Output` types, and how the operands of a binary operator must be
+//! borrowed to use one of them.
+
+use super::static_registry::RustInteropRegistry;
+use crate::rust_interop::types::RustType;
+
+/// Arithmetic traits whose impls for numeric primitives also take `&Rhs`
+const ARITHMETIC_TRAITS: &[&str] = &["Add", "Sub", "Mul", "Div", "Rem"];
+
+/// Comparison traits, whose methods always return `bool`
+const COMPARISON_TRAITS: &[&str] = &["PartialEq", "PartialOrd"];
+
+/// An `impl Trait<Rhs> for Self` with the `Output` type of its operator method
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperatorImpl {
+    pub rhs: RustType,
+    pub output: RustType,
+}
+
+/// How a binary operator resolves to an operator trait impl
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperatorImplMatch {
+    /// The left operand is borrowed to become the impl's `Self`
+    pub borrow_left: bool,
+    /// The right operand is borrowed to become the impl's `Rhs`
+    pub borrow_right: bool,
+    pub output: RustType,
+}
+
+impl RustInteropRegistry {
+    /// Known impls of the operator trait `trait_name` (e.g. `Add` or `PartialEq`) for `self_type`
+    pub fn query_operator_impls(
+        &self,
+        trait_name: &str,
+        self_type: &RustType,
+    ) -> Vec<OperatorImpl> {
+        let self_type = erase_lifetimes(self_type);
+
+        if COMPARISON_TRAITS.contains(&trait_name) {
+            return comparison_rhs_types(trait_name, &self_type)
+                .into_iter()
+                .map(|rhs| OperatorImpl {
+                    rhs,
+                    output: RustType::Bool,
+                })
+                .collect();
+        }
+
+        if !ARITHMETIC_TRAITS.contains(&trait_name) {
+            return Vec::new();
+        }
+        match &self_type {
+            // impl Add<P> for P, impl Add<&P> for P and the same for &P
+            primitive if is_numeric(primitive) => numeric_arithmetic_impls(primitive),
+            RustType::Ref { inner, .. } if is_numeric(inner) => numeric_arithmetic_impls(inner),
+            // impl Add<&str> for String
+            RustType::String if trait_name == "Add" => vec![OperatorImpl {
+                rhs: reference(RustType::Str),
+                output: RustType::String,
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Resolve `left <op> right` to an impl of the operator trait `trait_name`.
+    ///
+    /// Prefers an impl taking the operands as they are, then one reached by
+    /// borrowing the right operand, then by deref-coercing it (which Rust only
+    /// does when the impl is the only one for `Self`), and finally repeats
+    /// these with the left operand borrowed.
+    pub fn resolve_operator_impl(
+        &self,
+        trait_name: &str,
+        left: &RustType,
+        right: &RustType,
+    ) -> Option<OperatorImplMatch> {
+        let left = erase_lifetimes(left);
+        let right = erase_lifetimes(right);
+
+        let mut candidates = vec![(false, left.clone())];
+        if !matches!(left, RustType::Ref { .. }) {
+            candidates.push((true, reference(left)));
+        }
+
+        for (borrow_left, self_type) in candidates {
+            let impls = self.query_operator_impls(trait_name, &self_type);
+            let found = |borrow_right: bool, output: &RustType| {
+                Some(OperatorImplMatch {
+                    borrow_left,
+                    borrow_right,
+                    output: output.clone(),
+                })
+            };
+
+            if let Some(found_impl) = impls.iter().find(|i| i.rhs == right) {
+                return found(false, &found_impl.output);
+            }
+            if let Some(found_impl) = impls.iter().find(|i| i.rhs == reference(right.clone())) {
+                return found(true, &found_impl.output);
+            }
+            if let [only_impl] = impls.as_slice() {
+                if derefs_to(&right, &only_impl.rhs) {
+                    return found(false, &only_impl.output);
+                }
+                if derefs_to(&reference(right.clone()), &only_impl.rhs) {
+                    return found(true, &only_impl.output);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// `Rhs` types of the `PartialEq`/`PartialOrd` impls for `self_type`
+fn comparison_rhs_types(trait_name: &str, self_type: &RustType) -> Vec<RustType> {
+    let is_eq = trait_name == "PartialEq";
+    match self_type {
+        primitive if is_numeric(primitive) => vec![primitive.clone()],
+        RustType::Bool | RustType::Char | RustType::Unit => vec![self_type.clone()],
+        RustType::String if is_eq => {
+            vec![RustType::String, RustType::Str, reference(RustType::Str)]
+        }
+        RustType::Str if is_eq => vec![RustType::Str, RustType::String],
+        RustType::String | RustType::Str => vec![self_type.clone()],
+        RustType::Vec(element) | RustType::Option(element) => {
+            if comparison_rhs_types(trait_name, element).contains(element) {
+                vec![self_type.clone()]
+            } else {
+                Vec::new()
+            }
+        }
+        // impl PartialEq<&B> for &A where A: PartialEq<B>
+        RustType::Ref { inner, .. } => {
+            let mut rhs_types: Vec<RustType> = comparison_rhs_types(trait_name, inner)
+                .into_iter()
+                .map(reference)
+                .collect();
+            // impl PartialEq<String> for &str
+            if is_eq && **inner == RustType::Str {
+                rhs_types.push(RustType::String);
+            }
+            rhs_types
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn numeric_arithmetic_impls(primitive: &RustType) -> Vec<OperatorImpl> {
+    vec![
+        OperatorImpl {
+            rhs: primitive.clone(),
+            output: primitive.clone(),
+        },
+        OperatorImpl {
+            rhs: reference(primitive.clone()),
+            output: primitive.clone(),
+        },
+    ]
+}
+
+fn is_numeric(rust_type: &RustType) -> bool {
+    matches!(
+        rust_type,
+        RustType::I32
+            | RustType::I64
+            | RustType::ISize
+            | RustType::U32
+            | RustType::U64
+            | RustType::USize
+    )
+}
+
+/// Whether deref coercion turns `from` into `to`, as in `&String` to `&str`
+fn derefs_to(from: &RustType, to: &RustType) -> bool {
+    match (from, to) {
+        (RustType::Ref { inner: from, .. }, RustType::Ref { inner: to, .. }) => {
+            matches!(
+                (from.as_ref(), to.as_ref()),
+                (RustType::String, RustType::Str)
+            ) || matches!(from.as_ref(), RustType::Vec(element)
+                    if matches!(to.as_ref(), RustType::Slice { inner } if inner == element))
+                || matches!(from.as_ref(), RustType::Box(boxed) if boxed == to)
+        }
+        _ => false,
+    }
+}
+
+fn reference(inner: RustType) -> RustType {
+    RustType::Ref {
+        lifetime: None,
+        inner: Box::new(inner),
+    }
+}
+
+/// Drop lifetimes from references, which do not affect which impl applies
+fn erase_lifetimes(rust_type: &RustType) -> RustType {
+    match rust_type {
+        RustType::Ref { inner, .. } => reference(erase_lifetimes(inner)),
+        RustType::MutRef { inner, .. } => RustType::MutRef {
+            lifetime: None,
+            inner: Box::new(erase_lifetimes(inner)),
+        },
+        other => other.clone(),
+    }
+}
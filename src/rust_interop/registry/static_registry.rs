@@ -5,13 +5,14 @@
 use super::dynamic_registry::DynamicRustRegistry;
 use crate::error::VeltranoError;
 use crate::rust_interop::{cache::*, types::*, utils::camel_to_snake_case, ExternItem};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Registry for external Rust items
 #[derive(Debug)]
 pub struct RustInteropRegistry {
     items: HashMap<String, ExternItem>,
     dynamic_registry: DynamicRustRegistry,
+    local_trait_impls: HashMap<String, HashSet<String>>, // Traits derived by local data classes
 }
 
 impl RustInteropRegistry {
@@ -19,6 +20,7 @@ impl RustInteropRegistry {
         let mut registry = Self {
             items: HashMap::new(),
             dynamic_registry: DynamicRustRegistry::new(),
+            local_trait_impls: HashMap::new(),
         };
         registry.register_stdlib();
         registry
@@ -63,7 +65,7 @@ impl RustInteropRegistry {
             _self_kind: SelfKind::None, // static method
             _params: vec![],
             _return_type: RustType::String,
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         self.register(ExternItem::Method {
@@ -78,7 +80,7 @@ impl RustInteropRegistry {
                 },
             )],
             _return_type: RustType::Unit,
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         // Vec methods
@@ -91,7 +93,7 @@ impl RustInteropRegistry {
                 name: "Vec".to_string(),
                 generics: vec![RustType::Generic("T".to_string())],
             },
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         self.register(ExternItem::Method {
@@ -100,7 +102,7 @@ impl RustInteropRegistry {
             _self_kind: SelfKind::MutRef(None),
             _params: vec![("value".to_string(), RustType::Generic("T".to_string()))],
             _return_type: RustType::Unit,
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         self.register(ExternItem::Method {
@@ -109,9 +111,95 @@ impl RustInteropRegistry {
             _self_kind: SelfKind::Ref(None),
             _params: vec![],
             _return_type: RustType::USize,
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
+        // Map and set methods, generic over the key K, value V and element T
+        let generic = |name: &str| RustType::Generic(name.to_string());
+        let borrowed = |inner: RustType| RustType::Ref {
+            lifetime: None,
+            inner: Box::new(inner),
+        };
+        for map in ["std::collections::HashMap", "std::collections::BTreeMap"] {
+            let methods = [
+                (
+                    "get",
+                    SelfKind::Ref(None),
+                    vec![("k", borrowed(generic("K")))],
+                    RustType::Option(Box::new(borrowed(generic("V")))),
+                ),
+                (
+                    "contains_key",
+                    SelfKind::Ref(None),
+                    vec![("k", borrowed(generic("K")))],
+                    RustType::Bool,
+                ),
+                (
+                    "insert",
+                    SelfKind::MutRef(None),
+                    vec![("k", generic("K")), ("v", generic("V"))],
+                    RustType::Option(Box::new(generic("V"))),
+                ),
+                (
+                    "remove",
+                    SelfKind::MutRef(None),
+                    vec![("k", borrowed(generic("K")))],
+                    RustType::Option(Box::new(generic("V"))),
+                ),
+                ("len", SelfKind::Ref(None), vec![], RustType::USize),
+                ("is_empty", SelfKind::Ref(None), vec![], RustType::Bool),
+            ];
+            for (method_name, self_kind, params, return_type) in methods {
+                self.register(ExternItem::Method {
+                    type_name: map.to_string(),
+                    method_name: method_name.to_string(),
+                    _self_kind: self_kind,
+                    _params: params
+                        .into_iter()
+                        .map(|(name, typ)| (name.to_string(), typ))
+                        .collect(),
+                    _return_type: return_type,
+                    is_unsafe: false,
+                });
+            }
+        }
+
+        let set_methods = [
+            (
+                "contains",
+                SelfKind::Ref(None),
+                vec![("value", borrowed(generic("T")))],
+                RustType::Bool,
+            ),
+            (
+                "insert",
+                SelfKind::MutRef(None),
+                vec![("value", generic("T"))],
+                RustType::Bool,
+            ),
+            (
+                "remove",
+                SelfKind::MutRef(None),
+                vec![("value", borrowed(generic("T")))],
+                RustType::Bool,
+            ),
+            ("len", SelfKind::Ref(None), vec![], RustType::USize),
+            ("is_empty", SelfKind::Ref(None), vec![], RustType::Bool),
+        ];
+        for (method_name, self_kind, params, return_type) in set_methods {
+            self.register(ExternItem::Method {
+                type_name: "std::collections::HashSet".to_string(),
+                method_name: method_name.to_string(),
+                _self_kind: self_kind,
+                _params: params
+                    .into_iter()
+                    .map(|(name, typ)| (name.to_string(), typ))
+                    .collect(),
+                _return_type: return_type,
+                is_unsafe: false,
+            });
+        }
+
         // Option methods
         self.register(ExternItem::Method {
             type_name: "Option".to_string(),
@@ -119,7 +207,7 @@ impl RustInteropRegistry {
             _self_kind: SelfKind::Ref(None),
             _params: vec![],
             _return_type: RustType::Bool,
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         self.register(ExternItem::Method {
@@ -128,7 +216,7 @@ impl RustInteropRegistry {
             _self_kind: SelfKind::Value,
             _params: vec![],
             _return_type: RustType::Generic("T".to_string()),
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         // Result methods
@@ -138,7 +226,7 @@ impl RustInteropRegistry {
             _self_kind: SelfKind::Ref(None),
             _params: vec![],
             _return_type: RustType::Bool,
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         self.register(ExternItem::Method {
@@ -147,7 +235,7 @@ impl RustInteropRegistry {
             _self_kind: SelfKind::Value,
             _params: vec![],
             _return_type: RustType::Generic("T".to_string()),
-            _is_unsafe: false,
+            is_unsafe: false,
         });
 
         self.register(ExternItem::Method {
@@ -159,7 +247,7 @@ impl RustInteropRegistry {
                 name: "Option".to_string(),
                 generics: vec![RustType::Generic("T".to_string())],
             },
-            _is_unsafe: false,
+            is_unsafe: false,
         });
     }
 
@@ -225,6 +313,7 @@ impl RustInteropRegistry {
                 .collect(),
             return_type: method.return_type.parsed.clone().unwrap_or(RustType::Unit),
             _trait_name: None,
+            is_unsafe: method.is_unsafe,
         })
     }
 
@@ -240,6 +329,97 @@ impl RustInteropRegistry {
             return Ok(true);
         }
 
+        // Local data classes implement the traits they derive
+        if let RustType::Custom { name, generics } = rust_type {
+            if let Some(traits) = self.local_trait_impls.get(name) {
+                return Ok(generics.is_empty() && traits.contains(trait_name));
+            }
+        }
+
+        // Anonymous futures of async functions are only known to implement Future
+        if let RustType::Future(_) = rust_type {
+            return Ok(trait_name == "Future");
+        }
+
+        // Function pointers are plain values; comparing them is unreliable in Rust
+        if let RustType::Function { .. } = rust_type {
+            return Ok(matches!(trait_name, "Clone" | "Copy" | "Debug"));
+        }
+
+        // Trait objects and impl Trait only promise their trait and what it implies
+        if let RustType::Dyn {
+            trait_name: bound, ..
+        }
+        | RustType::Impl {
+            trait_name: bound, ..
+        } = rust_type
+        {
+            return Ok(bound == trait_name
+                || matches!(
+                    (bound.as_str(), trait_name),
+                    ("Display", "ToString") | ("Iterator", "IntoIterator")
+                ));
+        }
+
+        // Boxes are never Copy; other traits come from the boxed value
+        if let RustType::Box(inner) = rust_type {
+            return match trait_name {
+                "Copy" => Ok(false),
+                _ => self.type_implements_trait(inner, trait_name),
+            };
+        }
+
+        // Shared pointers clone the pointer; other traits come from the shared value
+        if let RustType::Rc(inner) | RustType::Arc(inner) = rust_type {
+            return match trait_name {
+                "Clone" => Ok(true),
+                "Copy" => Ok(false),
+                _ => self.type_implements_trait(inner, trait_name),
+            };
+        }
+
+        // Collections implement what all of their keys, values or elements implement;
+        // only ordered maps are ordered and hashable
+        let collection_args = match rust_type {
+            RustType::HashMap { key, value } | RustType::BTreeMap { key, value } => {
+                Some(vec![key.as_ref(), value.as_ref()])
+            }
+            RustType::HashSet(inner) => Some(vec![inner.as_ref()]),
+            _ => None,
+        };
+        if let Some(args) = collection_args {
+            let forwarded = match trait_name {
+                "Clone" | "Debug" | "Default" | "PartialEq" | "Eq" => true,
+                "PartialOrd" | "Ord" | "Hash" => matches!(rust_type, RustType::BTreeMap { .. }),
+                _ => false,
+            };
+            if !forwarded {
+                return Ok(false);
+            }
+            for arg in args {
+                if !self.type_implements_trait(arg, trait_name)? {
+                    return Ok(false);
+                }
+            }
+            return Ok(true);
+        }
+
+        // Cells forward comparison, Debug and Clone; Cell only does so for Copy contents
+        if let RustType::RefCell(inner) | RustType::Cell(inner) = rust_type {
+            if !matches!(
+                trait_name,
+                "Clone" | "Debug" | "Default" | "PartialEq" | "Eq" | "PartialOrd" | "Ord"
+            ) {
+                return Ok(false);
+            }
+            if matches!(rust_type, RustType::Cell(_))
+                && !self.type_implements_trait(inner, "Copy")?
+            {
+                return Ok(false);
+            }
+            return self.type_implements_trait(inner, trait_name);
+        }
+
         // Convert to string only at the lowest level
         let type_path = rust_type.to_rust_syntax();
 
@@ -247,25 +427,70 @@ impl RustInteropRegistry {
         let implements = match type_path.as_str() {
             // Primitive types that implement Copy and Clone
             "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
-            | "u128" | "usize" | "f32" | "f64" | "bool" | "char" => {
+            | "u128" | "usize" | "bool" | "char" => {
+                matches!(
+                    trait_name,
+                    "Clone"
+                        | "Copy"
+                        | "Debug"
+                        | "Display"
+                        | "ToString"
+                        | "PartialEq"
+                        | "Eq"
+                        | "PartialOrd"
+                        | "Ord"
+                        | "Hash"
+                )
+            }
+            // Floats have no total order or equality
+            "f32" | "f64" => {
                 matches!(
                     trait_name,
-                    "Clone" | "Copy" | "Debug" | "Display" | "ToString"
+                    "Clone"
+                        | "Copy"
+                        | "Debug"
+                        | "Display"
+                        | "ToString"
+                        | "PartialEq"
+                        | "PartialOrd"
                 )
             }
             // String types
             "String" | "std::string::String" => {
                 matches!(
                     trait_name,
-                    "Clone" | "Debug" | "Display" | "ToString" | "Into"
+                    "Clone"
+                        | "Debug"
+                        | "Display"
+                        | "ToString"
+                        | "Into"
+                        | "PartialEq"
+                        | "Eq"
+                        | "PartialOrd"
+                        | "Ord"
+                        | "Hash"
                 )
             }
             "&str" | "str" => {
-                matches!(trait_name, "Debug" | "Display" | "ToString" | "Into")
+                matches!(
+                    trait_name,
+                    "Debug"
+                        | "Display"
+                        | "ToString"
+                        | "Into"
+                        | "PartialEq"
+                        | "Eq"
+                        | "PartialOrd"
+                        | "Ord"
+                        | "Hash"
+                )
             }
             // Unit type
             "()" => {
-                matches!(trait_name, "Clone" | "Copy" | "Debug")
+                matches!(
+                    trait_name,
+                    "Clone" | "Copy" | "Debug" | "PartialEq" | "Eq" | "PartialOrd" | "Ord" | "Hash"
+                )
             }
             // For other types, we don't have knowledge yet
             _ => false,
@@ -274,6 +499,14 @@ impl RustInteropRegistry {
         Ok(implements)
     }
 
+    /// Record the traits implemented by a locally declared type, such as a data class's derives
+    pub fn register_local_traits(&mut self, type_name: &str, traits: &[String]) {
+        self.local_trait_impls
+            .entry(type_name.to_string())
+            .or_default()
+            .extend(traits.iter().cloned());
+    }
+
     /// Check if a trait exists
     pub fn trait_exists(&mut self, trait_name: &str) -> bool {
         // Check known standard library traits
@@ -296,6 +529,7 @@ impl RustInteropRegistry {
             "Ord",
             "Hash",
             "Iterator",
+            "Future",
             "IntoIterator",
             "FromIterator",
             "Read",
@@ -409,7 +643,7 @@ impl RustInteropRegistry {
                 _self_kind,
                 _params,
                 _return_type,
-                _is_unsafe: _,
+                is_unsafe,
             } = extern_item
             {
                 return Some(ImportedMethodInfo {
@@ -418,6 +652,7 @@ impl RustInteropRegistry {
                     _parameters: _params.iter().map(|(_, typ)| typ.clone()).collect(),
                     return_type: _return_type.clone(),
                     _trait_name: None,
+                    is_unsafe: *is_unsafe,
                 });
             }
         }
@@ -464,6 +699,7 @@ impl RustInteropRegistry {
                         _parameters: self.convert_parameters(&method.parameters),
                         return_type: self.convert_rust_type_signature(&method.return_type),
                         _trait_name: None, // Inherent method
+                        is_unsafe: method.is_unsafe,
                     }));
                 }
             }
@@ -556,6 +792,7 @@ impl RustInteropRegistry {
                             self.convert_rust_type_signature(&method.return_type)
                         },
                         _trait_name: Some(trait_name.to_string()),
+                        is_unsafe: method.is_unsafe,
                     }));
                 }
             }
@@ -698,6 +935,7 @@ impl RustInteropRegistry {
                                 self.convert_rust_type_signature(&method.return_type)
                             },
                             _trait_name: Some(trait_name.clone()),
+                            is_unsafe: method.is_unsafe,
                         }));
                     }
                 }
@@ -215,14 +215,17 @@ impl RustdocQuerier {
             })
             .collect();
 
-        // Convert return type
+        // Convert return type; async functions return a future of it
         let return_type_str = func.sig.output.unwrap_or_else(|| "()".to_string());
-        let return_type = RustTypeSignature {
+        let mut return_type = RustTypeSignature {
             raw: return_type_str.clone(),
             parsed: RustTypeParser::parse(&return_type_str).ok(),
             lifetimes: vec![],
             bounds: vec![],
         };
+        if func.header.is_async {
+            return_type = return_type.into_future();
+        }
 
         // Convert generics
         let generics = func
@@ -479,14 +482,17 @@ impl RustdocQuerier {
             })
             .collect();
 
-        // Convert return type
+        // Convert return type; async functions return a future of it
         let return_type_str = method.sig.output.unwrap_or_else(|| "()".to_string());
-        let return_type = RustTypeSignature {
+        let mut return_type = RustTypeSignature {
             raw: return_type_str.clone(),
             parsed: RustTypeParser::parse(&return_type_str).ok(),
             lifetimes: vec![],
             bounds: vec![],
         };
+        if method.header.is_async {
+            return_type = return_type.into_future();
+        }
 
         // Convert generics
         let generics = method
@@ -600,7 +606,6 @@ struct RustdocFunctionSignature {
 struct RustdocFunctionHeader {
     is_const: bool,
     is_unsafe: bool,
-    #[allow(dead_code)]
     is_async: bool,
 }
 
@@ -125,6 +125,52 @@ impl StdLibQuerier {
         };
         crate_info.traits.insert("Into".to_string(), into_trait);
 
+        // Add Iterator trait; `Item` is bound from the trait arguments by the caller
+        let iterator_trait = TraitInfo {
+            name: "Iterator".to_string(),
+            path: RustPath::Type(RustTypePath(
+                RustModulePath("std".into(), vec!["iter".to_string()]),
+                vec!["Iterator".to_string()],
+            )),
+            methods: vec![
+                MethodInfo {
+                    name: "next".to_string(),
+                    self_kind: SelfKind::MutRef(None),
+                    generics: vec![],
+                    parameters: vec![],
+                    return_type: RustTypeSignature {
+                        raw: "Option<Self::Item>".to_string(),
+                        parsed: Some(RustType::Option(Box::new(RustType::Generic(
+                            "Item".to_string(),
+                        )))),
+                        lifetimes: vec![],
+                        bounds: vec![],
+                    },
+                    is_unsafe: false,
+                    is_const: false,
+                },
+                MethodInfo {
+                    name: "count".to_string(),
+                    self_kind: SelfKind::Value,
+                    generics: vec![],
+                    parameters: vec![],
+                    return_type: RustTypeSignature {
+                        raw: "usize".to_string(),
+                        parsed: Some(RustType::USize),
+                        lifetimes: vec![],
+                        bounds: vec![],
+                    },
+                    is_unsafe: false,
+                    is_const: false,
+                },
+            ],
+            associated_types: vec!["Item".to_string()],
+            where_clause: None,
+        };
+        crate_info
+            .traits
+            .insert("Iterator".to_string(), iterator_trait);
+
         // Add trait implementations for common types
         for typ in &[
             "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
@@ -241,20 +287,44 @@ impl StdLibQuerier {
             )),
             kind: TypeKind::Struct, // Primitive types are treated as structs
             generics: vec![],
-            methods: vec![MethodInfo {
-                name: "abs".to_string(),
-                self_kind: SelfKind::Value, // Takes self by value
-                generics: vec![],
-                parameters: vec![],
-                return_type: RustTypeSignature {
-                    raw: "i64".to_string(),
-                    parsed: Some(RustType::I64),
-                    lifetimes: vec![],
-                    bounds: vec![],
+            methods: vec![
+                MethodInfo {
+                    name: "abs".to_string(),
+                    self_kind: SelfKind::Value, // Takes self by value
+                    generics: vec![],
+                    parameters: vec![],
+                    return_type: RustTypeSignature {
+                        raw: "i64".to_string(),
+                        parsed: Some(RustType::I64),
+                        lifetimes: vec![],
+                        bounds: vec![],
+                    },
+                    is_unsafe: false,
+                    is_const: false,
                 },
-                is_unsafe: false,
-                is_const: false,
-            }],
+                MethodInfo {
+                    name: "unchecked_add".to_string(),
+                    self_kind: SelfKind::Value,
+                    generics: vec![],
+                    parameters: vec![Parameter {
+                        name: "rhs".to_string(),
+                        param_type: RustTypeSignature {
+                            raw: "i64".to_string(),
+                            parsed: Some(RustType::I64),
+                            lifetimes: vec![],
+                            bounds: vec![],
+                        },
+                    }],
+                    return_type: RustTypeSignature {
+                        raw: "i64".to_string(),
+                        parsed: Some(RustType::I64),
+                        lifetimes: vec![],
+                        bounds: vec![],
+                    },
+                    is_unsafe: true, // Overflow is undefined behavior
+                    is_const: true,
+                },
+            ],
             fields: vec![],
             variants: vec![],
         };
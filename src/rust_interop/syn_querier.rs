@@ -341,7 +341,7 @@ impl SynQuerier {
             ),
             generics: self.extract_generics(&func.sig.generics),
             parameters: self.extract_parameters(&func.sig),
-            return_type: self.extract_return_type(&func.sig.output),
+            return_type: self.extract_return_type(&func.sig),
             is_unsafe: func.sig.unsafety.is_some(),
             is_const: func.sig.constness.is_some(),
             documentation: self.extract_doc_comments(&func.attrs),
@@ -455,7 +455,7 @@ impl SynQuerier {
                         self_kind: self.extract_self_kind(&method.sig),
                         generics: self.extract_generics(&method.sig.generics),
                         parameters: self.extract_parameters(&method.sig),
-                        return_type: self.extract_return_type(&method.sig.output),
+                        return_type: self.extract_return_type(&method.sig),
                         is_unsafe: method.sig.unsafety.is_some(),
                         is_const: method.sig.constness.is_some(),
                     });
@@ -542,7 +542,7 @@ impl SynQuerier {
                         self_kind: self.extract_self_kind(&method.sig),
                         generics: self.extract_generics(&method.sig.generics),
                         parameters: self.extract_parameters(&method.sig),
-                        return_type: self.extract_return_type(&method.sig.output),
+                        return_type: self.extract_return_type(&method.sig),
                         is_unsafe: method.sig.unsafety.is_some(),
                         is_const: method.sig.constness.is_some(),
                     })
@@ -632,8 +632,8 @@ impl SynQuerier {
         }
     }
 
-    fn extract_return_type(&self, output: &syn::ReturnType) -> RustTypeSignature {
-        match output {
+    fn extract_return_type(&self, sig: &syn::Signature) -> RustTypeSignature {
+        let output = match &sig.output {
             syn::ReturnType::Default => RustTypeSignature {
                 raw: "()".to_string(),
                 parsed: Some(RustType::Unit),
@@ -641,6 +641,11 @@ impl SynQuerier {
                 bounds: vec![],
             },
             syn::ReturnType::Type(_, ty) => self.syn_type_to_signature(ty),
+        };
+        if sig.asyncness.is_some() {
+            output.into_future()
+        } else {
+            output
         }
     }
 
@@ -31,13 +31,30 @@ pub enum RustType {
         inner: Box<RustType>,
     },
 
+    // Raw pointers
+    ConstPtr(Box<RustType>),
+    MutPtr(Box<RustType>),
+
     // Smart pointers
     Box(Box<RustType>),
     Rc(Box<RustType>),
     Arc(Box<RustType>),
 
+    // Interior mutability
+    RefCell(Box<RustType>),
+    Cell(Box<RustType>),
+
     // Generic types
     Vec(Box<RustType>),
+    HashMap {
+        key: Box<RustType>,
+        value: Box<RustType>,
+    },
+    BTreeMap {
+        key: Box<RustType>,
+        value: Box<RustType>,
+    },
+    HashSet(Box<RustType>),
     Option(Box<RustType>),
     Result {
         ok: Box<RustType>,
@@ -49,6 +66,15 @@ pub enum RustType {
         inner: Box<RustType>,
     },
 
+    // Fixed-size array type
+    Array {
+        inner: Box<RustType>,
+        size: usize,
+    },
+
+    // Tuple type
+    Tuple(Vec<RustType>),
+
     // Custom types
     Custom {
         name: String,
@@ -57,6 +83,27 @@ pub enum RustType {
 
     // Generic parameter
     Generic(String),
+
+    // Anonymous future returned by an `async fn`: impl Future<Output = T>
+    Future(Box<RustType>),
+
+    // Function pointer: fn(A, B) -> R
+    Function {
+        parameters: Vec<RustType>,
+        return_type: Box<RustType>,
+    },
+
+    // Trait object: dyn Trait (unsized, used behind a reference or pointer)
+    Dyn {
+        trait_name: String,
+        generics: Vec<RustType>,
+    },
+
+    // Some type implementing a trait: impl Trait
+    Impl {
+        trait_name: String,
+        generics: Vec<RustType>,
+    },
 }
 
 /// How a method takes self
@@ -76,6 +123,7 @@ pub struct ImportedMethodInfo {
     pub _parameters: Vec<RustType>,
     pub return_type: RustType, // The actual parsed return type from Rust
     pub _trait_name: Option<String>, // Which trait this method comes from (if any)
+    pub is_unsafe: bool,       // Calls must be inside an `unsafe` block
 }
 
 impl RustType {
@@ -108,10 +156,27 @@ impl RustType {
                     format!("&mut {}", inner.to_rust_syntax())
                 }
             }
+            RustType::ConstPtr(inner) => format!("*const {}", inner.to_rust_syntax()),
+            RustType::MutPtr(inner) => format!("*mut {}", inner.to_rust_syntax()),
             RustType::Box(inner) => format!("Box<{}>", inner.to_rust_syntax()),
-            RustType::Rc(inner) => format!("Rc<{}>", inner.to_rust_syntax()),
-            RustType::Arc(inner) => format!("Arc<{}>", inner.to_rust_syntax()),
+            RustType::Rc(inner) => format!("std::rc::Rc<{}>", inner.to_rust_syntax()),
+            RustType::Arc(inner) => format!("std::sync::Arc<{}>", inner.to_rust_syntax()),
+            RustType::RefCell(inner) => format!("std::cell::RefCell<{}>", inner.to_rust_syntax()),
+            RustType::Cell(inner) => format!("std::cell::Cell<{}>", inner.to_rust_syntax()),
             RustType::Vec(inner) => format!("Vec<{}>", inner.to_rust_syntax()),
+            RustType::HashMap { key, value } => format!(
+                "std::collections::HashMap<{}, {}>",
+                key.to_rust_syntax(),
+                value.to_rust_syntax()
+            ),
+            RustType::BTreeMap { key, value } => format!(
+                "std::collections::BTreeMap<{}, {}>",
+                key.to_rust_syntax(),
+                value.to_rust_syntax()
+            ),
+            RustType::HashSet(inner) => {
+                format!("std::collections::HashSet<{}>", inner.to_rust_syntax())
+            }
             RustType::Option(inner) => format!("Option<{}>", inner.to_rust_syntax()),
             RustType::Result { ok, err } => {
                 format!("Result<{}, {}>", ok.to_rust_syntax(), err.to_rust_syntax())
@@ -135,6 +200,51 @@ impl RustType {
             RustType::Slice { inner } => {
                 format!("[{}]", inner.to_rust_syntax())
             }
+            RustType::Array { inner, size } => {
+                format!("[{}; {}]", inner.to_rust_syntax(), size)
+            }
+            RustType::Tuple(elements) => format!(
+                "({})",
+                elements
+                    .iter()
+                    .map(|e| e.to_rust_syntax())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            RustType::Future(output) => format!(
+                "impl std::future::Future<Output = {}>",
+                output.to_rust_syntax()
+            ),
+            RustType::Function { .. } => format!("fn{}", self.function_signature_syntax()),
+            RustType::Dyn {
+                trait_name,
+                generics,
+            } => format!("dyn {}", trait_bound_syntax(trait_name, generics)),
+            RustType::Impl {
+                trait_name,
+                generics,
+            } => format!("impl {}", trait_bound_syntax(trait_name, generics)),
+        }
+    }
+
+    /// Parameter list and return type of a function type, as in `(i64) -> bool`;
+    /// `fn` or `impl Fn` goes in front of it
+    pub fn function_signature_syntax(&self) -> String {
+        let RustType::Function {
+            parameters,
+            return_type,
+        } = self
+        else {
+            return String::new();
+        };
+        let parameters = parameters
+            .iter()
+            .map(|p| p.to_rust_syntax())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match **return_type {
+            RustType::Unit => format!("({})", parameters),
+            _ => format!("({}) -> {}", parameters, return_type.to_rust_syntax()),
         }
     }
 
@@ -176,11 +286,29 @@ impl RustType {
                 Ok(VeltranoType::mut_ref(inner_type))
             }
 
+            // Raw pointers
+            RustType::ConstPtr(inner) => Ok(VeltranoType::ptr(inner.to_veltrano_type()?)),
+            RustType::MutPtr(inner) => Ok(VeltranoType::mut_ptr(inner.to_veltrano_type()?)),
+
             // Smart pointers (owned)
             RustType::Box(inner) => {
-                let inner_type = inner.to_veltrano_type()?;
+                let inner_type = inner.to_pointee_veltrano_type()?;
                 Ok(VeltranoType::own(VeltranoType::boxed(inner_type)))
             }
+            RustType::Rc(inner) => Ok(VeltranoType::own(VeltranoType::rc(
+                inner.to_pointee_veltrano_type()?,
+            ))),
+            RustType::Arc(inner) => Ok(VeltranoType::own(VeltranoType::arc(
+                inner.to_pointee_veltrano_type()?,
+            ))),
+
+            // Cells (owned, like smart pointers)
+            RustType::RefCell(inner) => Ok(VeltranoType::own(VeltranoType::ref_cell(
+                inner.to_veltrano_type()?,
+            ))),
+            RustType::Cell(inner) => Ok(VeltranoType::own(VeltranoType::cell(
+                inner.to_veltrano_type()?,
+            ))),
 
             // Generic types (owned containers)
             RustType::Vec(inner) => {
@@ -196,6 +324,17 @@ impl RustType {
                 let err_type = err.to_veltrano_type()?;
                 Ok(VeltranoType::own(VeltranoType::result(ok_type, err_type)))
             }
+            RustType::HashMap { key, value } => Ok(VeltranoType::own(VeltranoType::hash_map(
+                key.to_veltrano_type()?,
+                value.to_veltrano_type()?,
+            ))),
+            RustType::BTreeMap { key, value } => Ok(VeltranoType::own(VeltranoType::btree_map(
+                key.to_veltrano_type()?,
+                value.to_veltrano_type()?,
+            ))),
+            RustType::HashSet(inner) => Ok(VeltranoType::own(VeltranoType::hash_set(
+                inner.to_veltrano_type()?,
+            ))),
 
             // Custom types
             RustType::Custom { name, generics } => {
@@ -207,6 +346,21 @@ impl RustType {
                             "$T".to_string(),
                         ))))
                     }
+                    // Maps and sets without generics -> HashMap<$K, $V> etc.
+                    "std::collections::HashMap" | "std::collections::BTreeMap"
+                        if generics.is_empty() =>
+                    {
+                        let key = VeltranoType::custom("$K".to_string());
+                        let value = VeltranoType::custom("$V".to_string());
+                        Ok(VeltranoType::own(if name.ends_with("HashMap") {
+                            VeltranoType::hash_map(key, value)
+                        } else {
+                            VeltranoType::btree_map(key, value)
+                        }))
+                    }
+                    "std::collections::HashSet" if generics.is_empty() => Ok(VeltranoType::own(
+                        VeltranoType::hash_set(VeltranoType::custom("$T".to_string())),
+                    )),
                     _ => Ok(VeltranoType::custom(name.clone())),
                 }
             }
@@ -220,7 +374,105 @@ impl RustType {
                 Ok(VeltranoType::slice(inner_type))
             }
 
-            _ => Err(format!("Unsupported Rust type for conversion: {:?}", self)),
+            // Array type (owned, like tuples)
+            RustType::Array { inner, size } => {
+                let inner_type = inner.to_veltrano_type()?;
+                Ok(VeltranoType::array(inner_type, *size))
+            }
+
+            // Tuple type (always owned, elements converted individually)
+            RustType::Tuple(elements) => Ok(VeltranoType::tuple(
+                elements
+                    .iter()
+                    .map(|e| e.to_veltrano_type())
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+
+            // Futures are owned values typed by their output
+            RustType::Future(output) => Ok(VeltranoType::future(output.to_veltrano_type()?)),
+
+            // Trait objects are naturally referenced, so a bare `dyn Trait` is owned
+            RustType::Dyn {
+                trait_name,
+                generics,
+            } => Ok(VeltranoType::own(VeltranoType::dyn_trait(
+                trait_name,
+                veltrano_trait_args(generics)?,
+            ))),
+            RustType::Impl {
+                trait_name,
+                generics,
+            } => Ok(VeltranoType::impl_trait(
+                trait_name,
+                veltrano_trait_args(generics)?,
+            )),
+
+            // Function pointers are owned values typed by their signature
+            RustType::Function {
+                parameters,
+                return_type,
+            } => Ok(VeltranoType::function(
+                parameters
+                    .iter()
+                    .map(|p| p.to_veltrano_type())
+                    .collect::<Result<Vec<_>, _>>()?,
+                return_type.to_veltrano_type()?,
+            )),
+        }
+    }
+
+    /// Convert the Rust type of a value behind a pointer (Box, Rc, ...); a
+    /// `dyn Trait` pointee is the trait object itself
+    fn to_pointee_veltrano_type(&self) -> Result<VeltranoType, String> {
+        match self {
+            RustType::Dyn {
+                trait_name,
+                generics,
+            } => Ok(VeltranoType::dyn_trait(
+                trait_name,
+                veltrano_trait_args(generics)?,
+            )),
+            _ => self.to_veltrano_type(),
         }
     }
 }
+
+/// Rust path of a trait named in a Veltrano type, e.g. `Display` is `std::fmt::Display`
+pub fn trait_path(trait_name: &str) -> String {
+    match trait_name {
+        "Display" | "Debug" => format!("std::fmt::{}", trait_name),
+        "Hash" => "std::hash::Hash".to_string(),
+        _ => trait_name.to_string(),
+    }
+}
+
+/// Associated type bound by a trait's type argument, as in `Iterator<Item = T>`
+pub fn associated_type_name(trait_name: &str) -> Option<&'static str> {
+    match trait_name {
+        "Iterator" | "IntoIterator" => Some("Item"),
+        "Future" => Some("Output"),
+        _ => None,
+    }
+}
+
+/// `Trait<..>` part of `dyn Trait<..>` or `impl Trait<..>`
+fn trait_bound_syntax(trait_name: &str, generics: &[RustType]) -> String {
+    let path = trait_path(trait_name);
+    if generics.is_empty() {
+        return path;
+    }
+    let generics = generics
+        .iter()
+        .map(|g| g.to_rust_syntax())
+        .collect::<Vec<_>>()
+        .join(", ");
+    match associated_type_name(trait_name) {
+        Some(associated) => format!("{}<{} = {}>", path, associated, generics),
+        None => format!("{}<{}>", path, generics),
+    }
+}
+
+/// Veltrano type arguments of a trait bound
+fn veltrano_trait_args(generics: &[RustType]) -> Result<Vec<VeltranoType>, String> {
+    generics.iter().map(|g| g.to_veltrano_type()).collect()
+}
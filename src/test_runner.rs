@@ -0,0 +1,121 @@
+//! Running `@Test` functions
+//!
+//! The generated Rust code is placed in a scratch Cargo project and run with
+//! `cargo test`; the test harness output is reported with the Veltrano
+//! function names instead of the generated `tests::snake_case` paths.
+
+use crate::ast::{Program, Stmt};
+use crate::rust_interop::camel_to_snake_case;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Result of running the tests of one Veltrano file
+#[derive(Debug)]
+pub struct TestReport {
+    /// Test harness output, with test paths replaced by Veltrano names
+    pub output: String,
+    /// Whether every test passed
+    pub success: bool,
+}
+
+/// Map from generated test paths (`tests::adds_numbers`) to Veltrano names
+pub fn test_names(program: &Program) -> HashMap<String, String> {
+    program
+        .statements
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::FunDecl(fun_decl) if fun_decl.is_test() => Some((
+                format!("tests::{}", camel_to_snake_case(&fun_decl.name)),
+                fun_decl.name.clone(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replace generated test paths in harness output with Veltrano names
+pub fn rename_tests(output: &str, names: &HashMap<String, String>) -> String {
+    let mut renamed = String::with_capacity(output.len());
+    let mut rest = output;
+
+    while let Some(start) = rest.find("tests::") {
+        renamed.push_str(&rest[..start]);
+        // Part of a longer name such as the crate name `veltrano_tests::`
+        let in_identifier = renamed
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        if in_identifier {
+            renamed.push_str("tests::");
+            rest = &rest[start + "tests::".len()..];
+            continue;
+        }
+        let path_len = rest[start + "tests::".len()..]
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .map_or(rest.len() - start, |end| "tests::".len() + end);
+        let path = &rest[start..start + path_len];
+        renamed.push_str(names.get(path).map_or(path, String::as_str));
+        rest = &rest[start + path_len..];
+    }
+
+    renamed.push_str(rest);
+    renamed
+}
+
+/// Compile the generated code with `cargo test` and run its tests
+pub fn run_tests(program: &Program, rust_code: &str, name: &str) -> Result<TestReport, String> {
+    let names = test_names(program);
+    if names.is_empty() {
+        return Err("no @Test functions found".to_string());
+    }
+
+    // Keyed by process so that concurrent runs on files of the same name don't clobber each other
+    let project_dir =
+        std::env::temp_dir().join(format!("veltrano_test_{}_{}", name, std::process::id()));
+    write_project(&project_dir, rust_code)
+        .map_err(|e| format!("Failed to create test project: {}", e))?;
+
+    let output = Command::new("cargo")
+        .args(["test", "--lib", "--color", "never"])
+        .current_dir(&project_dir)
+        .output();
+    let _ = fs::remove_dir_all(&project_dir);
+    let output = output.map_err(|e| format!("Failed to run cargo test: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.contains("test result:") {
+        // The generated code did not build, so no test ran
+        return Err(format!(
+            "Compiling the generated tests failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(TestReport {
+        output: rename_tests(&stdout, &names),
+        success: output.status.success(),
+    })
+}
+
+/// Write a library crate holding the generated code, depending on bumpalo
+fn write_project(project_dir: &Path, rust_code: &str) -> std::io::Result<()> {
+    fs::create_dir_all(project_dir.join("src"))?;
+    fs::write(
+        project_dir.join("Cargo.toml"),
+        r#"[package]
+name = "veltrano_tests"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bumpalo = "3.0"
+"#,
+    )?;
+    // Functions only used by the tests are otherwise reported as unused
+    fs::write(
+        project_dir.join("src/lib.rs"),
+        format!("#![allow(dead_code)]\n\n{}", rust_code),
+    )
+}
@@ -0,0 +1,199 @@
+//! Annotation validation
+//!
+//! Checks that annotations are known, attached to a declaration that supports
+//! them and given well-formed arguments before codegen passes them to Rust.
+
+use super::cfg::CfgPredicate;
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{Annotation, AnnotationArg};
+
+/// Standard library traits supported by `#[derive(...)]`
+const DERIVABLE_TRAITS: &[&str] = &[
+    "Clone",
+    "Copy",
+    "Debug",
+    "Default",
+    "PartialEq",
+    "Eq",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+];
+
+/// Declaration kinds that annotations can be attached to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum AnnotationTarget {
+    Function,
+    MemberFunction,
+    DataClass,
+    Field,
+    Statement,
+}
+
+impl AnnotationTarget {
+    fn describe(self) -> &'static str {
+        match self {
+            AnnotationTarget::Function => "functions",
+            AnnotationTarget::MemberFunction => "member functions",
+            AnnotationTarget::DataClass => "data classes",
+            AnnotationTarget::Field => "data class fields",
+            AnnotationTarget::Statement => "statements",
+        }
+    }
+}
+
+impl VeltranoTypeChecker {
+    /// Validate the annotations attached to a declaration
+    pub(super) fn check_annotations(
+        &mut self,
+        annotations: &[Annotation],
+        target: AnnotationTarget,
+    ) -> Result<(), TypeCheckError> {
+        for annotation in annotations {
+            match annotation.name.as_str() {
+                "Derive" => self.check_derive_annotation(annotation, target)?,
+                "Rust" => check_rust_annotation(annotation)?,
+                "CExport" => check_c_export_annotation(annotation, target)?,
+                "Test" => check_test_annotation(annotation, target)?,
+                "Cfg" => check_cfg_annotation(annotation, target)?,
+                _ => return Err(invalid_annotation(annotation, "unknown annotation")),
+            }
+        }
+        Ok(())
+    }
+
+    /// `@Derive(Trait, ...)` lists derivable standard traits on a data class
+    fn check_derive_annotation(
+        &mut self,
+        annotation: &Annotation,
+        target: AnnotationTarget,
+    ) -> Result<(), TypeCheckError> {
+        if target != AnnotationTarget::DataClass {
+            return Err(invalid_annotation(
+                annotation,
+                &format!("cannot be applied to {}", target.describe()),
+            ));
+        }
+        if annotation.args.is_empty() {
+            return Err(invalid_annotation(
+                annotation,
+                "expected at least one trait name",
+            ));
+        }
+
+        for arg in &annotation.args {
+            let trait_name = match arg {
+                AnnotationArg::Identifier(name) => name,
+                AnnotationArg::String(_) => {
+                    return Err(invalid_annotation(
+                        annotation,
+                        "trait names must not be quoted",
+                    ))
+                }
+            };
+            if !self.trait_checker.trait_exists(trait_name) {
+                return Err(invalid_annotation(
+                    annotation,
+                    &format!("unknown trait '{}'", trait_name),
+                ));
+            }
+            if !DERIVABLE_TRAITS.contains(&trait_name.as_str()) {
+                return Err(invalid_annotation(
+                    annotation,
+                    &format!("trait '{}' cannot be derived", trait_name),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `@Rust("...")` carries the contents of a single Rust attribute
+fn check_rust_annotation(annotation: &Annotation) -> Result<(), TypeCheckError> {
+    match annotation.args.as_slice() {
+        [AnnotationArg::String(attribute)] if !attribute.trim().is_empty() => Ok(()),
+        _ => Err(invalid_annotation(
+            annotation,
+            "expected a single string with the attribute contents",
+        )),
+    }
+}
+
+/// `@CExport` or `@CExport("symbol")` exports a function under a C symbol name
+fn check_c_export_annotation(
+    annotation: &Annotation,
+    target: AnnotationTarget,
+) -> Result<(), TypeCheckError> {
+    if target != AnnotationTarget::Function {
+        return Err(invalid_annotation(
+            annotation,
+            &format!("cannot be applied to {}", target.describe()),
+        ));
+    }
+    match annotation.args.as_slice() {
+        [] => Ok(()),
+        [AnnotationArg::String(symbol)] if is_c_identifier(symbol) => Ok(()),
+        _ => Err(invalid_annotation(
+            annotation,
+            "expected no arguments or a single string with a valid C symbol name",
+        )),
+    }
+}
+
+/// `@Test` marks a function as a test and takes no arguments
+fn check_test_annotation(
+    annotation: &Annotation,
+    target: AnnotationTarget,
+) -> Result<(), TypeCheckError> {
+    if target != AnnotationTarget::Function {
+        return Err(invalid_annotation(
+            annotation,
+            &format!("cannot be applied to {}", target.describe()),
+        ));
+    }
+    if !annotation.args.is_empty() {
+        return Err(invalid_annotation(annotation, "expected no arguments"));
+    }
+    Ok(())
+}
+
+/// `@Cfg(test)` or `@Cfg("feature = \"json\"")` holds a Rust `cfg` predicate
+fn check_cfg_annotation(
+    annotation: &Annotation,
+    target: AnnotationTarget,
+) -> Result<(), TypeCheckError> {
+    if matches!(
+        target,
+        AnnotationTarget::MemberFunction | AnnotationTarget::Field
+    ) {
+        return Err(invalid_annotation(
+            annotation,
+            &format!("cannot be applied to {}", target.describe()),
+        ));
+    }
+    match annotation.args.as_slice() {
+        [AnnotationArg::Identifier(predicate) | AnnotationArg::String(predicate)]
+            if CfgPredicate::parse(predicate).is_some() =>
+        {
+            Ok(())
+        }
+        _ => Err(invalid_annotation(
+            annotation,
+            "expected a single cfg predicate such as `test` or `\"not(unix)\"`",
+        )),
+    }
+}
+
+fn is_c_identifier(symbol: &str) -> bool {
+    let mut chars = symbol.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+pub(super) fn invalid_annotation(annotation: &Annotation, reason: &str) -> TypeCheckError {
+    TypeCheckError::InvalidAnnotation {
+        annotation: annotation.name.clone(),
+        reason: reason.to_string(),
+        location: annotation.location.clone(),
+    }
+}
@@ -0,0 +1,119 @@
+//! Binding call arguments to parameters
+//!
+//! Matches positional, named and shorthand arguments of function and data
+//! class constructor calls to the declared parameters, leaving parameters
+//! with default values unbound when they are omitted.
+
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{Argument, LocatedExpr};
+use crate::error::{SourceLocation, Span};
+
+/// The argument given for a parameter
+pub(super) enum BoundArgument<'a> {
+    /// An expression, passed by position or by name
+    Expr(&'a LocatedExpr),
+    /// A `.name` shorthand passing the variable of the same name
+    Variable(&'a str),
+    /// Omitted; the parameter's default value is used
+    Default,
+}
+
+/// A parameter of the called function or data class
+pub(super) struct ParameterSpec<'a> {
+    pub name: &'a str,
+    pub has_default: bool,
+}
+
+impl VeltranoTypeChecker {
+    /// Bind the arguments of a call to `parameters`, in parameter order.
+    /// `unknown_name` builds the error for a named argument that matches no parameter.
+    pub(super) fn bind_arguments<'a>(
+        function: &str,
+        parameters: &[ParameterSpec],
+        args: &[&'a Argument],
+        span: &Span,
+        unknown_name: impl Fn(&str, &Span) -> TypeCheckError,
+    ) -> Result<Vec<BoundArgument<'a>>, TypeCheckError> {
+        let location = SourceLocation::new(span.start_line(), span.start_column());
+        let mut bound: Vec<Option<BoundArgument<'a>>> = parameters.iter().map(|_| None).collect();
+        let mut named_seen = false;
+        let mut positional_count = 0;
+
+        for arg in args {
+            let (index, argument) = match arg {
+                Argument::Bare(expr, _) => {
+                    if named_seen {
+                        return Err(TypeCheckError::InvalidArguments {
+                            function: function.to_string(),
+                            reason: "positional arguments must come before named arguments"
+                                .to_string(),
+                            location: expr.span.start.clone(),
+                        });
+                    }
+                    positional_count += 1;
+                    if positional_count > parameters.len() {
+                        return Err(TypeCheckError::ArgumentCountMismatch {
+                            function: function.to_string(),
+                            expected: parameters.len(),
+                            actual: args.len(),
+                            location,
+                        });
+                    }
+                    (positional_count - 1, BoundArgument::Expr(expr))
+                }
+                Argument::Named(name, expr, _) => {
+                    named_seen = true;
+                    let index = Self::parameter_index(parameters, name)
+                        .ok_or_else(|| unknown_name(name, &expr.span))?;
+                    (index, BoundArgument::Expr(expr))
+                }
+                Argument::Shorthand(name, _) => {
+                    named_seen = true;
+                    let index = Self::parameter_index(parameters, name)
+                        .ok_or_else(|| unknown_name(name, span))?;
+                    (index, BoundArgument::Variable(name))
+                }
+                Argument::StandaloneComment(_, _) => continue,
+            };
+
+            if bound[index].is_some() {
+                return Err(TypeCheckError::InvalidArguments {
+                    function: function.to_string(),
+                    reason: format!(
+                        "parameter '{}' is given more than once",
+                        parameters[index].name
+                    ),
+                    location,
+                });
+            }
+            bound[index] = Some(argument);
+        }
+
+        parameters
+            .iter()
+            .zip(bound)
+            .map(|(parameter, argument)| match argument {
+                Some(argument) => Ok(argument),
+                None if parameter.has_default => Ok(BoundArgument::Default),
+                // Plain positional calls keep reporting a count mismatch
+                None if !named_seen => Err(TypeCheckError::ArgumentCountMismatch {
+                    function: function.to_string(),
+                    expected: parameters.len(),
+                    actual: args.len(),
+                    location: location.clone(),
+                }),
+                None => Err(TypeCheckError::InvalidArguments {
+                    function: function.to_string(),
+                    reason: format!("missing argument for parameter '{}'", parameter.name),
+                    location: location.clone(),
+                }),
+            })
+            .collect()
+    }
+
+    fn parameter_index(parameters: &[ParameterSpec], name: &str) -> Option<usize> {
+        parameters
+            .iter()
+            .position(|parameter| parameter.name == name)
+    }
+}
@@ -0,0 +1,329 @@
+//! Aliasing checks for `MutRef` borrows
+//!
+//! While a `MutRef` to a variable is in use, no other `Ref` or `MutRef` to
+//! that variable may be used. `.ref()` and `.mutRef()` borrow the variable
+//! (or one of its fields) they are called on, and `MutRef(x)` briefly borrows
+//! `x` to clone it. A borrow bound to a variable lasts until that variable's
+//! last use, so a conflicting borrow is reported once the earlier one is used
+//! again; borrows that are not bound to a variable end with their statement.
+//! Values built around references, such as data class instances, tuples and
+//! collection literals, hold their borrows like the references themselves. A
+//! variable used anywhere in a loop is used again by the next iteration, so
+//! borrows made later in the body conflict with it too.
+
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{Argument, Expr, LocatedExpr, MethodCallExpr};
+use crate::builtins::OperatorMethod;
+use crate::error::Span;
+
+/// A variable, keyed by name and the depth of the scope declaring it
+type Binding = (String, usize);
+
+/// A borrow of a variable or one of its fields
+#[derive(Debug, Clone)]
+struct Borrow {
+    serial: usize, // Identifies the borrow while its holders change
+    root: Binding,
+    path: Vec<String>, // The variable followed by the borrowed fields
+    mutable: bool,
+    span: Span,
+    id: Option<usize>,     // Method call creating the borrow
+    holders: Vec<Binding>, // Variables holding the reference; empty for temporaries
+}
+
+impl Borrow {
+    /// Whether two borrows overlap and at least one of them is mutable
+    fn conflicts_with(&self, other: &Borrow) -> bool {
+        (self.mutable || other.mutable)
+            && self.root == other.root
+            && self
+                .path
+                .iter()
+                .zip(&other.path)
+                .all(|(this, that)| this == that)
+    }
+}
+
+/// Live borrows of the current function, and later borrows conflicting with
+/// them, which are errors if the earlier borrow is used again
+#[derive(Debug, Clone, Default)]
+pub(super) struct BorrowState {
+    live: Vec<Borrow>,
+    pending: Vec<(usize, Borrow)>, // Serial of the earlier borrow, and the later one
+    next_serial: usize,
+    uses: Vec<(Binding, Span)>, // Uses of variables holding borrows, for loops
+}
+
+/// Where the borrows stood when a loop started
+#[derive(Debug, Clone, Copy)]
+pub(super) struct LoopBorrows {
+    serial: usize,
+    uses: usize,
+}
+
+impl VeltranoTypeChecker {
+    fn borrow_state(&mut self) -> Option<&mut BorrowState> {
+        self.function_contexts
+            .last_mut()
+            .map(|context| &mut context.borrows)
+    }
+
+    /// The variable and fields of a place expression such as `p.name`
+    fn borrowed_place(&self, expr: &LocatedExpr) -> Option<(Binding, Vec<String>)> {
+        match &expr.node {
+            Expr::Identifier(name) => {
+                let depth = self.env.variable_scope_depth(name)?;
+                Some(((name.clone(), depth), vec![name.clone()]))
+            }
+            Expr::FieldAccess(field_access) => {
+                let (root, mut path) = self.borrowed_place(&field_access.object)?;
+                path.push(field_access.field.clone());
+                Some((root, path))
+            }
+            Expr::Parenthesized(paren_expr) => self.borrowed_place(&paren_expr.expr),
+            _ => None,
+        }
+    }
+
+    /// Record a borrow of `place`, checking it against the live borrows
+    fn record_borrow(
+        &mut self,
+        place: &LocatedExpr,
+        mutable: bool,
+        span: &Span,
+        id: Option<usize>,
+    ) -> Result<(), TypeCheckError> {
+        let Some((root, path)) = self.borrowed_place(place) else {
+            return Ok(());
+        };
+        let Some(state) = self.borrow_state() else {
+            return Ok(());
+        };
+        let borrow = Borrow {
+            serial: state.next_serial,
+            root,
+            path,
+            mutable,
+            span: span.clone(),
+            id,
+            holders: Vec::new(),
+        };
+        state.next_serial += 1;
+        for earlier in state
+            .live
+            .iter()
+            .filter(|live| live.conflicts_with(&borrow))
+        {
+            // Borrows of the same statement are all in use at once
+            if earlier.holders.is_empty() {
+                return Err(Self::conflicting_borrow(earlier, &borrow, None));
+            }
+            state.pending.push((earlier.serial, borrow.clone()));
+        }
+        state.live.push(borrow);
+        Ok(())
+    }
+
+    /// Record the borrow made by `.ref()` or `.mutRef()` at `span`
+    pub(super) fn record_operator_borrow(
+        &mut self,
+        method_call: &MethodCallExpr,
+        span: &Span,
+    ) -> Result<(), TypeCheckError> {
+        // Member functions and imports may shadow the builtin operators
+        if !method_call.args.is_empty()
+            || self.method_resolutions.contains_key(&method_call.id)
+            || self.node_types.contains_key(&method_call.id)
+        {
+            return Ok(());
+        }
+        let mutable = if method_call.method == OperatorMethod::MutRef.method_name() {
+            true
+        } else if method_call.method == OperatorMethod::Ref.method_name() {
+            false
+        } else {
+            return Ok(());
+        };
+        self.record_borrow(&method_call.object, mutable, span, Some(method_call.id))
+    }
+
+    /// `MutRef(x)` borrows `x` just long enough to clone it
+    pub(super) fn record_clone_borrow(
+        &mut self,
+        place: &LocatedExpr,
+        span: &Span,
+    ) -> Result<(), TypeCheckError> {
+        self.record_borrow(place, false, span, None)
+    }
+
+    /// Check a use of a variable that may hold a borrow overlapping a later,
+    /// conflicting one
+    pub(super) fn check_borrow_use(
+        &mut self,
+        name: &str,
+        span: &Span,
+    ) -> Result<(), TypeCheckError> {
+        let Some(depth) = self.env.variable_scope_depth(name) else {
+            return Ok(());
+        };
+        let binding = (name.to_string(), depth);
+        let Some(state) = self.borrow_state() else {
+            return Ok(());
+        };
+        if state
+            .live
+            .iter()
+            .any(|borrow| borrow.holders.contains(&binding))
+        {
+            state.uses.push((binding.clone(), span.clone()));
+        }
+        for (serial, later) in &state.pending {
+            let earlier = state
+                .live
+                .iter()
+                .find(|borrow| borrow.serial == *serial && borrow.holders.contains(&binding));
+            if let Some(earlier) = earlier {
+                return Err(Self::conflicting_borrow(earlier, later, Some(span)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Start checking a loop, whose body runs again after its last statement
+    pub(super) fn start_loop_borrows(&mut self) -> Option<LoopBorrows> {
+        self.borrow_state().map(|state| LoopBorrows {
+            serial: state.next_serial,
+            uses: state.uses.len(),
+        })
+    }
+
+    /// Check the borrows made in a loop against the variables used anywhere in
+    /// it: a use before a conflicting borrow comes again in the next iteration
+    pub(super) fn check_loop_borrows(
+        &mut self,
+        start: Option<LoopBorrows>,
+    ) -> Result<(), TypeCheckError> {
+        let (Some(start), Some(state)) = (start, self.borrow_state()) else {
+            return Ok(());
+        };
+        for (serial, later) in &state.pending {
+            if later.serial < start.serial {
+                continue;
+            }
+            let Some(earlier) = state.live.iter().find(|borrow| borrow.serial == *serial) else {
+                continue;
+            };
+            let used_at = state.uses[start.uses..]
+                .iter()
+                .find(|(binding, _)| earlier.holders.contains(binding));
+            if let Some((_, used_at)) = used_at {
+                return Err(Self::conflicting_borrow(earlier, later, Some(used_at)));
+            }
+        }
+        Ok(())
+    }
+
+    /// The borrows held by the value of `expr`: the method calls that may create
+    /// them and the variables it copies, through the values constructed around them
+    fn held_borrows(&self, expr: &LocatedExpr, ids: &mut Vec<usize>, sources: &mut Vec<Binding>) {
+        match &expr.node {
+            Expr::Identifier(name) => {
+                if let Some(depth) = self.env.variable_scope_depth(name) {
+                    sources.push((name.clone(), depth));
+                }
+            }
+            Expr::MethodCall(method_call) => ids.push(method_call.id),
+            Expr::Parenthesized(paren_expr) => self.held_borrows(&paren_expr.expr, ids, sources),
+            Expr::Tuple(tuple) => {
+                for element in &tuple.elements {
+                    self.held_borrows(element, ids, sources);
+                }
+            }
+            Expr::Call(call)
+                if self.is_collection_literal_call(call)
+                    || matches!(&call.callee.node, Expr::Identifier(name)
+                        if self.env.lookup_data_class(name).is_some()) =>
+            {
+                for arg in &call.args {
+                    match arg {
+                        Argument::Bare(arg_expr, _) | Argument::Named(_, arg_expr, _) => {
+                            self.held_borrows(arg_expr, ids, sources)
+                        }
+                        Argument::Shorthand(name, _) => {
+                            if let Some(depth) = self.env.variable_scope_depth(name) {
+                                sources.push((name.clone(), depth));
+                            }
+                        }
+                        Argument::StandaloneComment(_, _) => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Bind the references held by `initializer` to a newly declared variable,
+    /// which keeps their borrows alive until its last use
+    pub(super) fn bind_borrow(&mut self, name: &str, initializer: &LocatedExpr) {
+        let binding = (name.to_string(), self.env.scope_depth());
+        let mut ids = Vec::new();
+        let mut sources = Vec::new();
+        self.held_borrows(initializer, &mut ids, &mut sources);
+        let Some(state) = self.borrow_state() else {
+            return;
+        };
+
+        // A new binding replaces an earlier variable of the same name in its scope
+        for borrow in &mut state.live {
+            borrow.holders.retain(|holder| *holder != binding);
+        }
+
+        for borrow in &mut state.live {
+            let created_here = borrow.id.is_some_and(|id| ids.contains(&id));
+            let copied_here = sources.iter().any(|source| borrow.holders.contains(source));
+            if created_here || copied_here {
+                borrow.holders.push(binding.clone());
+            }
+        }
+    }
+
+    /// End the borrows that are not bound to a variable when their statement ends
+    pub(super) fn release_temporary_borrows(&mut self) {
+        self.release_borrows(|_| true);
+    }
+
+    /// End the borrows held only by variables of scopes deeper than `depth`
+    pub(super) fn release_scope_borrows(&mut self, depth: usize) {
+        self.release_borrows(|holder| holder.1 <= depth);
+    }
+
+    /// Keep the holders of each borrow that satisfy `keep`, ending the borrows
+    /// left without a holder
+    fn release_borrows(&mut self, keep: impl Fn(&Binding) -> bool) {
+        if let Some(state) = self.borrow_state() {
+            for borrow in &mut state.live {
+                borrow.holders.retain(&keep);
+            }
+            state.live.retain(|borrow| !borrow.holders.is_empty());
+            let live = &state.live;
+            state
+                .pending
+                .retain(|(serial, _)| live.iter().any(|borrow| borrow.serial == *serial));
+        }
+    }
+
+    fn conflicting_borrow(
+        earlier: &Borrow,
+        later: &Borrow,
+        used_at: Option<&Span>,
+    ) -> TypeCheckError {
+        TypeCheckError::ConflictingBorrow {
+            place: later.path.join("."),
+            first_mutable: earlier.mutable,
+            first_at: earlier.span.clone(),
+            second_mutable: later.mutable,
+            second_at: later.span.clone(),
+            first_used_at: used_at.cloned(),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Conditional compilation checking
+//!
+//! `@Cfg(...)` conditions are passed on to Rust as `#[cfg(...)]`. The type
+//! checker does not know which configuration will be built, so it checks every
+//! item, but lets items under mutually exclusive conditions share a name as
+//! long as they have the same signature, since callers are checked against one.
+
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{Annotation, AnnotationArg, FunDeclStmt, Parameter, Program, Stmt};
+use crate::error::SourceLocation;
+use crate::types::VeltranoType;
+
+/// `key = "value"` options that hold a single value in any configuration
+const SINGLE_VALUED_KEYS: &[&str] = &[
+    "target_arch",
+    "target_os",
+    "target_env",
+    "target_endian",
+    "target_pointer_width",
+    "target_vendor",
+    "panic",
+];
+
+/// A parsed `cfg` predicate
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum CfgPredicate {
+    Flag(String),             // `test`, `unix`
+    KeyValue(String, String), // `feature = "json"`
+    Not(Box<CfgPredicate>),
+    All(Vec<CfgPredicate>),
+    Any(Vec<CfgPredicate>),
+}
+
+impl CfgPredicate {
+    /// Parse the text inside `#[cfg(...)]`
+    pub(super) fn parse(text: &str) -> Option<CfgPredicate> {
+        let mut parser = CfgParser { rest: text };
+        let predicate = parser.predicate()?;
+        parser.skip_whitespace();
+        parser.rest.is_empty().then_some(predicate)
+    }
+
+    /// Condition of an item: all of its `@Cfg` annotations, if it has any
+    pub(super) fn of_annotations(annotations: &[Annotation]) -> Option<CfgPredicate> {
+        let mut predicates: Vec<CfgPredicate> = annotations
+            .iter()
+            .filter(|a| a.name == "Cfg")
+            .filter_map(|a| match a.args.as_slice() {
+                [AnnotationArg::Identifier(text) | AnnotationArg::String(text)] => {
+                    CfgPredicate::parse(text)
+                }
+                _ => None,
+            })
+            .collect();
+        match predicates.len() {
+            0 => None,
+            1 => predicates.pop(),
+            _ => Some(CfgPredicate::All(predicates)),
+        }
+    }
+
+    /// Whether this predicate and `other` can never hold at the same time
+    pub(super) fn excludes(&self, other: &CfgPredicate) -> bool {
+        let theirs = other.conjuncts();
+        self.conjuncts()
+            .iter()
+            .any(|ours| theirs.iter().any(|theirs| contradict(ours, theirs)))
+    }
+
+    /// Predicates that must all hold for this one to hold
+    fn conjuncts(&self) -> Vec<&CfgPredicate> {
+        match self {
+            CfgPredicate::All(predicates) => {
+                predicates.iter().flat_map(|p| p.conjuncts()).collect()
+            }
+            _ => vec![self],
+        }
+    }
+}
+
+fn contradict(a: &CfgPredicate, b: &CfgPredicate) -> bool {
+    match (a, b) {
+        (CfgPredicate::Not(negated), other) | (other, CfgPredicate::Not(negated)) => {
+            **negated == *other
+        }
+        (CfgPredicate::KeyValue(key_a, value_a), CfgPredicate::KeyValue(key_b, value_b)) => {
+            key_a == key_b && value_a != value_b && SINGLE_VALUED_KEYS.contains(&key_a.as_str())
+        }
+        _ => false,
+    }
+}
+
+/// A parameter or field: name, type and whether it has a default value
+type Slot = (String, VeltranoType, bool);
+
+/// The part of a definition that its uses are checked against
+#[derive(Debug, PartialEq)]
+enum Signature {
+    Function {
+        lifetime_params: Vec<String>,
+        params: Vec<Slot>,
+        return_type: Option<VeltranoType>,
+        is_async: bool,
+    },
+    DataClass {
+        lifetime_params: Vec<String>,
+        fields: Vec<Slot>,
+        methods: Vec<(String, Signature)>,
+    },
+}
+
+impl Signature {
+    fn function(
+        lifetime_params: &[String],
+        params: &[Parameter],
+        return_type: Option<&VeltranoType>,
+        is_async: bool,
+    ) -> Signature {
+        Signature::Function {
+            lifetime_params: lifetime_params.to_vec(),
+            params: params
+                .iter()
+                .map(|p| {
+                    (
+                        p.name.clone(),
+                        p.param_type.node.clone(),
+                        p.default_value.is_some(),
+                    )
+                })
+                .collect(),
+            return_type: return_type.cloned(),
+            is_async,
+        }
+    }
+
+    fn of_function(fun_decl: &FunDeclStmt) -> Signature {
+        Signature::function(
+            &fun_decl.lifetime_params,
+            &fun_decl.params,
+            fun_decl.return_type.as_ref().map(|t| &t.node),
+            fun_decl.is_async,
+        )
+    }
+}
+
+/// Recursive descent over `name`, `name = "value"` and `all/any/not(...)`
+struct CfgParser<'a> {
+    rest: &'a str,
+}
+
+impl CfgParser<'_> {
+    fn predicate(&mut self) -> Option<CfgPredicate> {
+        let name = self.identifier()?;
+        if self.eat('=') {
+            return Some(CfgPredicate::KeyValue(name, self.string()?));
+        }
+        if !self.eat('(') {
+            return Some(CfgPredicate::Flag(name));
+        }
+
+        let mut predicates = Vec::new();
+        while !self.eat(')') {
+            predicates.push(self.predicate()?);
+            if !self.eat(',') {
+                self.eat(')').then_some(())?;
+                break;
+            }
+        }
+        match name.as_str() {
+            "all" => Some(CfgPredicate::All(predicates)),
+            "any" => Some(CfgPredicate::Any(predicates)),
+            "not" if predicates.len() == 1 => Some(CfgPredicate::Not(Box::new(predicates.pop()?))),
+            _ => None,
+        }
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let end = self
+            .rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(self.rest.len());
+        let name = &self.rest[..end];
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        self.rest = &self.rest[end..];
+        Some(name.to_string())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let body = self.rest.strip_prefix('"')?;
+        let end = body.find('"')?;
+        self.rest = &body[end + 1..];
+        Some(body[..end].to_string())
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+}
+
+impl VeltranoTypeChecker {
+    /// Report top-level functions and data classes defined more than once,
+    /// unless the definitions are under mutually exclusive `@Cfg` conditions
+    /// and have the same signature
+    pub(super) fn check_duplicate_definitions(&self, program: &Program) -> Vec<TypeCheckError> {
+        let mut errors = Vec::new();
+        let mut definitions: Vec<(&str, &str, Option<CfgPredicate>, Signature)> = Vec::new();
+
+        for stmt in &program.statements {
+            let (annotations, stmt) = match stmt {
+                Stmt::Annotated(annotated) => (annotated.annotations.as_slice(), &*annotated.stmt),
+                _ => (&[][..], stmt),
+            };
+            let (kind, name, annotations, location, signature): (_, _, _, &SourceLocation, _) =
+                match stmt {
+                    Stmt::FunDecl(fun_decl) => (
+                        "function",
+                        &fun_decl.name,
+                        fun_decl.annotations.as_slice(),
+                        &fun_decl.location,
+                        Signature::of_function(fun_decl),
+                    ),
+                    Stmt::ExternFun(extern_fun) => (
+                        "function",
+                        &extern_fun.name,
+                        annotations,
+                        &extern_fun.location,
+                        Signature::function(
+                            &[],
+                            &extern_fun.params,
+                            extern_fun.return_type.as_ref().map(|t| &t.node),
+                            false,
+                        ),
+                    ),
+                    Stmt::DataClass(data_class) => (
+                        "data class",
+                        &data_class.name,
+                        data_class.annotations.as_slice(),
+                        &data_class.location,
+                        Signature::DataClass {
+                            lifetime_params: data_class.lifetime_params.clone(),
+                            fields: data_class
+                                .fields
+                                .iter()
+                                .map(|f| {
+                                    (
+                                        f.name.clone(),
+                                        f.field_type.node.clone(),
+                                        f.default_value.is_some(),
+                                    )
+                                })
+                                .collect(),
+                            methods: data_class
+                                .methods
+                                .iter()
+                                .map(|m| {
+                                    (m.fun_decl.name.clone(), Signature::of_function(&m.fun_decl))
+                                })
+                                .collect(),
+                        },
+                    ),
+                    _ => continue,
+                };
+
+            let cfg = CfgPredicate::of_annotations(annotations);
+            let same_name = definitions.iter().filter(|(other_kind, other_name, _, _)| {
+                *other_kind == kind && *other_name == name
+            });
+            let mut error = None;
+            for (_, _, other_cfg, other_signature) in same_name {
+                if !matches!((&cfg, other_cfg), (Some(a), Some(b)) if a.excludes(b)) {
+                    error = Some(TypeCheckError::DuplicateDefinition {
+                        kind: kind.to_string(),
+                        name: name.clone(),
+                        location: location.clone(),
+                    });
+                    break;
+                }
+                if *other_signature != signature {
+                    error = Some(TypeCheckError::CfgSignatureMismatch {
+                        kind: kind.to_string(),
+                        name: name.clone(),
+                        location: location.clone(),
+                    });
+                }
+            }
+            errors.extend(error);
+            definitions.push((kind, name, cfg, signature));
+        }
+
+        errors
+    }
+}
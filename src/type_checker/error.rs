@@ -4,7 +4,7 @@
 //! and methods for error analysis and suggestions.
 
 use crate::ast::BinaryOp;
-use crate::error::SourceLocation;
+use crate::error::{SourceLocation, Span};
 use crate::types::{TypeConstructor, VeltranoType};
 
 /// Type checking errors with detailed information
@@ -49,12 +49,17 @@ pub enum TypeCheckError {
         actual: usize,
         location: SourceLocation,
     },
-    _IndexingNotSupported {
+    InvalidArguments {
+        function: String,
+        reason: String,
+        location: SourceLocation,
+    },
+    IndexingNotSupported {
         object_type: VeltranoType,
         index_type: VeltranoType,
         location: SourceLocation,
     },
-    _BinaryOperatorNotSupported {
+    BinaryOperatorNotSupported {
         operator: BinaryOp,
         left_type: VeltranoType,
         right_type: VeltranoType,
@@ -93,6 +98,101 @@ pub enum TypeCheckError {
         reason: String,
         location: SourceLocation,
     },
+    InvalidDestructuring {
+        target_type: VeltranoType,
+        reason: String,
+        location: SourceLocation,
+    },
+    CannotInferElementType {
+        function: String,
+        location: SourceLocation,
+    },
+    InvalidAnnotation {
+        annotation: String,
+        reason: String,
+        location: SourceLocation,
+    },
+    DuplicateDefinition {
+        kind: String,
+        name: String,
+        location: SourceLocation,
+    },
+    CfgSignatureMismatch {
+        kind: String,
+        name: String,
+        location: SourceLocation,
+    },
+    NotFfiSafe {
+        function: String,
+        item: String,
+        rust_type: String,
+        location: SourceLocation,
+    },
+    InvalidOperatorFunction {
+        class_name: String,
+        function: String,
+        reason: String,
+        location: SourceLocation,
+    },
+    InvalidOverride {
+        class_name: String,
+        function: String,
+        reason: String,
+        location: SourceLocation,
+    },
+    InvalidLifetimeLabel {
+        label: String,
+        reason: String,
+        location: SourceLocation,
+    },
+    LifetimeMismatch {
+        expected: String,
+        actual: String,
+        location: SourceLocation,
+    },
+    UnsafeCallOutsideUnsafeBlock {
+        function: String,
+        span: Span,
+    },
+    InvalidAwait {
+        reason: String,
+        location: SourceLocation,
+    },
+    InvalidDeref {
+        reason: String,
+        suggestion: Option<String>,
+        location: SourceLocation,
+    },
+    UseAfterMove {
+        name: String,
+        moved_at: Span,
+        used_at: Span,
+        in_loop: bool, // Moved in an earlier iteration of the enclosing loop
+        suggestion: String,
+    },
+    DanglingReference {
+        target: String, // What the reference borrows, such as "local variable 's'"
+        borrowed_at: Span,
+        returned_at: Span,
+        suggestion: String,
+    },
+    ConflictingBorrow {
+        place: String, // The borrowed variable or field, such as "p.name"
+        first_mutable: bool,
+        first_at: Span,
+        second_mutable: bool,
+        second_at: Span,
+        first_used_at: Option<Span>, // Where the first borrow is used after the second
+    },
+    InvalidAssignment {
+        reason: String,
+        location: SourceLocation,
+    },
+    InvalidFunctionReference {
+        name: String,
+        reason: String,
+        location: SourceLocation,
+    },
 }
 
 /// Information about a resolved method call
@@ -100,6 +200,14 @@ pub enum TypeCheckError {
 pub struct MethodResolution {
     pub rust_type: crate::rust_interop::RustType,
     pub method_name: String,
+    pub borrowed_args: Vec<bool>, // Arguments passed by value where the Rust method takes a reference
+}
+
+/// Borrows needed to apply an operator resolved through an `operator fun`
+#[derive(Debug, Clone)]
+pub struct OperatorResolution {
+    pub borrow_left: bool, // Left operand (or the unary operand) is owned and must be borrowed
+    pub borrow_right: bool, // Right operand is owned and must be borrowed
 }
 
 /// Error analysis and suggestion generation
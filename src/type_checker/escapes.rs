@@ -0,0 +1,211 @@
+//! Dangling reference checking for returned references
+//!
+//! A reference returned from a function must outlive it, so it can borrow
+//! from the caller through a parameter or be allocated in the caller's arena
+//! with `.bumpRef()`, but it can't borrow a variable or temporary value of the
+//! function itself. References bound to variables remember where they came
+//! from, so returning them later is checked too, and so do data classes,
+//! tuples and collections built from references, along with their fields,
+//! elements and `.bumpRef()` copies.
+
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{Argument, Expr, LocatedExpr};
+use crate::builtins::OperatorMethod;
+use crate::error::Span;
+use crate::rust_interop::RustType;
+use std::collections::HashMap;
+
+/// Where the value behind a reference lives
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum ReferenceOrigin {
+    /// Allocated in the caller's arena with `.bumpRef()`
+    Bump,
+    /// A variable of the function itself, borrowed at `span`
+    Local { name: String, span: Span },
+    /// A temporary value that is dropped at the end of its statement
+    Temporary { span: Span },
+}
+
+/// Origins of the references bound to variables, keyed by name and the depth
+/// of the scope declaring them; parameters borrow from the caller and have none
+#[derive(Debug, Clone, Default)]
+pub(super) struct ReferenceOrigins {
+    origins: HashMap<(String, usize), ReferenceOrigin>,
+}
+
+impl VeltranoTypeChecker {
+    /// Where the value behind the reference `expr` evaluates to lives, if it
+    /// belongs to the current function or its caller's arena
+    fn reference_origin(&mut self, expr: &LocatedExpr) -> Option<ReferenceOrigin> {
+        match &expr.node {
+            Expr::Parenthesized(paren_expr) => self.reference_origin(&paren_expr.expr),
+            Expr::Identifier(name) => self.variable_origin(name),
+            // Fields hold the references of the value they belong to
+            Expr::FieldAccess(field_access) => self.held_origin(&field_access.object),
+            Expr::Index(index) => {
+                // Elements that can't be copied are borrowed in place
+                let element_borrowed = self
+                    .node_types
+                    .get(&index.id)
+                    .filter(|t| t.data_class_name().is_none() && t.map_types().is_none())
+                    .and_then(|t| t.element_type())
+                    .cloned()
+                    .is_some_and(|element_type| {
+                        element_type
+                            .borrowed_component_type(&mut self.trait_checker)
+                            .is_some()
+                    });
+                if element_borrowed {
+                    self.place_origin(expr, &expr.span)
+                } else {
+                    self.held_origin(&index.object)
+                }
+            }
+            Expr::Tuple(tuple) => tuple
+                .elements
+                .iter()
+                .find_map(|element| self.reference_origin(element)),
+            Expr::Call(call)
+                if self.is_collection_literal_call(call)
+                    || matches!(&call.callee.node, Expr::Identifier(name)
+                        if self.env.lookup_data_class(name).is_some()) =>
+            {
+                call.args.iter().find_map(|arg| match arg {
+                    Argument::Bare(arg_expr, _) | Argument::Named(_, arg_expr, _) => {
+                        self.reference_origin(arg_expr)
+                    }
+                    Argument::Shorthand(name, _) => self.variable_origin(name),
+                    Argument::StandaloneComment(_, _) => None,
+                })
+            }
+            Expr::MethodCall(method_call) if method_call.args.is_empty() => {
+                // Member functions and imports may shadow the builtin operators
+                if self.method_resolutions.contains_key(&method_call.id)
+                    || self.node_types.contains_key(&method_call.id)
+                {
+                    return None;
+                }
+                let method = method_call.method.as_str();
+                if method == OperatorMethod::BumpRef.method_name() {
+                    // The arena copy still holds the references of the value
+                    Some(
+                        self.reference_origin(&method_call.object)
+                            .unwrap_or(ReferenceOrigin::Bump),
+                    )
+                } else if method == OperatorMethod::Ref.method_name()
+                    || method == OperatorMethod::MutRef.method_name()
+                {
+                    self.place_origin(&method_call.object, &expr.span)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Where the references held by the value of `expr` point; an element
+    /// used in place, like the object of a field access, isn't borrowed
+    fn held_origin(&mut self, expr: &LocatedExpr) -> Option<ReferenceOrigin> {
+        match &expr.node {
+            Expr::Parenthesized(paren_expr) => self.held_origin(&paren_expr.expr),
+            Expr::Index(index) => self.held_origin(&index.object),
+            _ => self.reference_origin(expr),
+        }
+    }
+
+    /// Where the references held by variable `name` point
+    fn variable_origin(&self, name: &str) -> Option<ReferenceOrigin> {
+        let depth = self.env.variable_scope_depth(name)?;
+        self.function_contexts
+            .last()?
+            .reference_origins
+            .origins
+            .get(&(name.to_string(), depth))
+            .cloned()
+    }
+
+    /// Where the place `expr` borrowed by `.ref()` at `span` lives
+    fn place_origin(&mut self, expr: &LocatedExpr, span: &Span) -> Option<ReferenceOrigin> {
+        match &expr.node {
+            Expr::Parenthesized(paren_expr) => self.place_origin(&paren_expr.expr, span),
+            // The receiver of a member function is borrowed from the caller
+            Expr::Identifier(name) if name == "this" => None,
+            Expr::Identifier(name) => Some(ReferenceOrigin::Local {
+                name: name.clone(),
+                span: span.clone(),
+            }),
+            Expr::FieldAccess(field_access) => {
+                self.element_origin(&field_access.object, field_access.id, span)
+            }
+            Expr::Index(index) => self.element_origin(&index.object, index.id, span),
+            // Constants are promoted to static values
+            Expr::Literal(_) | Expr::FunctionRef(_) => None,
+            _ => Some(ReferenceOrigin::Temporary { span: span.clone() }),
+        }
+    }
+
+    /// Where a field or element of `object` lives: behind the reference if
+    /// `object` is one, and in `object` itself otherwise
+    fn element_origin(
+        &mut self,
+        object: &LocatedExpr,
+        id: usize,
+        span: &Span,
+    ) -> Option<ReferenceOrigin> {
+        let object_type = self.node_types.get(&id)?.clone();
+        // `.ref()` of an owned collection has the collection's type, but is a reference
+        let is_borrow = matches!(&object.node, Expr::MethodCall(method_call)
+            if method_call.args.is_empty()
+                && (method_call.method == OperatorMethod::Ref.method_name()
+                    || method_call.method == OperatorMethod::MutRef.method_name()));
+        match object_type.to_rust_type(&mut self.trait_checker) {
+            RustType::Ref { .. } | RustType::MutRef { .. } => self.reference_origin(object),
+            _ if is_borrow => self.reference_origin(object),
+            _ => self.place_origin(object, span),
+        }
+    }
+
+    /// Remember where the reference bound to a newly declared variable comes from
+    pub(super) fn record_reference_origin(&mut self, name: &str, initializer: &LocatedExpr) {
+        let origin = self.reference_origin(initializer);
+        let depth = self.env.scope_depth();
+        if let Some(context) = self.function_contexts.last_mut() {
+            let key = (name.to_string(), depth);
+            match origin {
+                Some(origin) => context.reference_origins.origins.insert(key, origin),
+                None => context.reference_origins.origins.remove(&key),
+            };
+        }
+    }
+
+    /// Check that a returned value does not borrow from the returning function
+    pub(super) fn check_returned_reference(
+        &mut self,
+        expr: &LocatedExpr,
+    ) -> Result<(), TypeCheckError> {
+        let (target, borrowed_at, suggestion) = match self.reference_origin(expr) {
+            Some(ReferenceOrigin::Local { name, span }) => (
+                format!("local variable '{}'", name),
+                span,
+                format!(
+                    "Allocate the value in the caller's arena with {}.bumpRef() instead of borrowing it",
+                    name
+                ),
+            ),
+            Some(ReferenceOrigin::Temporary { span }) => (
+                "a temporary value".to_string(),
+                span,
+                "Allocate the value in the caller's arena with .bumpRef() instead of .ref()"
+                    .to_string(),
+            ),
+            Some(ReferenceOrigin::Bump) | None => return Ok(()),
+        };
+        Err(TypeCheckError::DanglingReference {
+            target,
+            borrowed_at,
+            returned_at: expr.span.clone(),
+            suggestion,
+        })
+    }
+}
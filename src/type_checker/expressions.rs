@@ -6,15 +6,18 @@
 
 use crate::ast::*;
 use crate::error::{SourceLocation, Span};
-use crate::types::{DataClassDefinition, FunctionSignature, TypeConstructor, VeltranoType};
+use crate::types::{
+    tuple_component_index, DataClassDefinition, FunctionSignature, TypeConstructor, VeltranoType,
+};
 
+use super::arguments::{BoundArgument, ParameterSpec};
 use super::error::TypeCheckError;
-use super::types::{substitute_generic_type, TypeValidator};
+use super::types::{map_lookup_type, substitute_generic_type, TypeValidator};
 use super::VeltranoTypeChecker;
 
 impl VeltranoTypeChecker {
     /// Helper to filter out standalone comment arguments
-    fn filter_non_comment_args(args: &[Argument]) -> Vec<&Argument> {
+    pub(super) fn filter_non_comment_args(args: &[Argument]) -> Vec<&Argument> {
         args.iter()
             .filter(|arg| !matches!(arg, Argument::StandaloneComment(_, _)))
             .collect()
@@ -28,7 +31,20 @@ impl VeltranoTypeChecker {
     ) -> Result<VeltranoType, TypeCheckError> {
         match &expr.node {
             Expr::MethodCall(method_call) => {
-                self.check_method_call_with_expected_type(method_call, expected_type)
+                let method_type =
+                    self.check_method_call_with_expected_type(method_call, expected_type)?;
+                self.check_unsafe_call(method_call.id, &method_call.method, &expr.span)?;
+                self.record_operator_borrow(method_call, &expr.span)?;
+                // `.bumpRef()` allocates into the arena of the expected lifetime
+                match expected_type.and_then(|expected| expected.reference_lifetime()) {
+                    Some(label) if method_call.method == "bumpRef" => {
+                        Ok(method_type.with_lifetime(Some(label.clone())))
+                    }
+                    _ => Ok(method_type),
+                }
+            }
+            Expr::Call(call) if self.is_collection_literal_call(call) => {
+                self.check_collection_literal(call, &expr.span, expected_type)
             }
             _ => self.check_expression(expr),
         }
@@ -41,16 +57,51 @@ impl VeltranoTypeChecker {
     ) -> Result<VeltranoType, TypeCheckError> {
         match &expr.node {
             Expr::Literal(literal) => self.check_literal(literal),
-            Expr::Identifier(name) => self.check_identifier(name, &expr.span),
+            Expr::Identifier(name) => self.check_moving_identifier(name, &expr.span),
             Expr::Binary(binary) => self.check_binary_expression(binary, &expr.span),
             Expr::Unary(unary) => self.check_unary_expression(unary, &expr.span),
-            Expr::Call(call) => self.check_call_expression(call, &expr.span),
-            Expr::MethodCall(method_call) => self.check_method_call(method_call),
+            Expr::Call(call) => {
+                let call_type = self.check_call_expression(call, &expr.span)?;
+                if let Expr::Identifier(func_name) = &call.callee.node {
+                    self.check_unsafe_call(call.id, func_name, &expr.span)?;
+                }
+                Ok(call_type)
+            }
+            Expr::MethodCall(method_call) => {
+                let method_type = self.check_method_call(method_call)?;
+                self.check_unsafe_call(method_call.id, &method_call.method, &expr.span)?;
+                self.record_operator_borrow(method_call, &expr.span)?;
+                Ok(method_type)
+            }
             Expr::FieldAccess(field_access) => self.check_field_access(field_access, &expr.span),
             Expr::Parenthesized(paren_expr) => self.check_expression(&paren_expr.expr),
+            Expr::Tuple(tuple) => self.check_tuple_expression(tuple),
+            Expr::Index(index) => self.check_index_expression(index),
+            Expr::FunctionRef(function_ref) => {
+                self.check_function_reference(function_ref, &expr.span)
+            }
+            Expr::RustCode(rust_code) => {
+                // The embedded code is trusted to have its declared type
+                let result_type = &rust_code.result_type;
+                self.validate_type(&result_type.node, result_type.span.start.clone())?;
+                Ok(result_type.node.clone())
+            }
         }
     }
 
+    /// Check tuple expression; each element keeps its own type
+    fn check_tuple_expression(
+        &mut self,
+        tuple: &TupleExpr,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let element_types = tuple
+            .elements
+            .iter()
+            .map(|element| self.check_expression(element))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(VeltranoType::tuple(element_types))
+    }
+
     /// Check literal expression
     fn check_literal(&self, literal: &LiteralExpr) -> Result<VeltranoType, TypeCheckError> {
         let veltrano_type = match literal {
@@ -65,7 +116,11 @@ impl VeltranoTypeChecker {
     }
 
     /// Check identifier (variable lookup)
-    fn check_identifier(&self, name: &str, span: &Span) -> Result<VeltranoType, TypeCheckError> {
+    pub(super) fn check_identifier(
+        &self,
+        name: &str,
+        span: &Span,
+    ) -> Result<VeltranoType, TypeCheckError> {
         self.env
             .lookup_variable(name)
             .cloned()
@@ -81,8 +136,15 @@ impl VeltranoTypeChecker {
         binary: &BinaryExpr,
         span: &Span,
     ) -> Result<VeltranoType, TypeCheckError> {
-        let left_type = self.check_expression(&binary.left)?;
-        let right_type = self.check_expression(&binary.right)?;
+        // Operands are borrowed unless the resolved operator takes them by value
+        let left_type = self.check_place_expression(&binary.left)?;
+        let right_type = self.check_place_expression(&binary.right)?;
+
+        // Data class operands go through their `operator fun` members
+        if let Some(result_type) = self.resolve_binary_operator(binary, &left_type, &right_type)? {
+            self.consume_binary_operands(binary, &left_type, &right_type);
+            return Ok(result_type);
+        }
 
         // For now, implement basic arithmetic and comparison operators
         match binary.operator {
@@ -96,6 +158,15 @@ impl VeltranoTypeChecker {
                     matches!(t.constructor, TypeConstructor::I64 | TypeConstructor::USize)
                 };
 
+                // Other operands use the std::ops impls of their Rust types
+                if !is_numeric(&left_type) || !is_numeric(&right_type) {
+                    if let Some(output) = self.resolve_std_operator(binary, &left_type, &right_type)
+                    {
+                        self.consume_binary_operands(binary, &left_type, &right_type);
+                        return Ok(output);
+                    }
+                }
+
                 if !is_numeric(&left_type) {
                     return Err(TypeCheckError::TypeMismatch {
                         expected: VeltranoType::i64(),
@@ -145,6 +216,11 @@ impl VeltranoTypeChecker {
                 } else if TypeValidator::types_equal(&left_type, &right_type) {
                     // Otherwise types must match exactly
                     Ok(VeltranoType::bool())
+                } else if let Some(output) =
+                    self.resolve_std_operator(binary, &left_type, &right_type)
+                {
+                    // Or be comparable through the std::cmp impls of their Rust types
+                    Ok(output)
                 } else {
                     return Err(TypeCheckError::TypeMismatch {
                         expected: left_type,
@@ -190,7 +266,11 @@ impl VeltranoTypeChecker {
         unary: &UnaryExpr,
         span: &Span,
     ) -> Result<VeltranoType, TypeCheckError> {
-        let operand_type = self.check_expression(&unary.operand)?;
+        let operand_type = self.check_place_expression(&unary.operand)?;
+
+        if let Some(result_type) = self.resolve_unary_operator(unary, &operand_type) {
+            return Ok(result_type);
+        }
 
         match unary.operator {
             UnaryOp::Minus => {
@@ -210,6 +290,16 @@ impl VeltranoTypeChecker {
         }
     }
 
+    /// Type of a call to a function returning `return_type`; calling a
+    /// `suspend fun` yields a future of it
+    fn call_result_type(signature: &FunctionSignature, return_type: VeltranoType) -> VeltranoType {
+        if signature.is_async {
+            VeltranoType::future(return_type)
+        } else {
+            return_type
+        }
+    }
+
     /// Check function call expression
     pub(super) fn check_call_expression(
         &mut self,
@@ -217,6 +307,16 @@ impl VeltranoTypeChecker {
         span: &Span,
     ) -> Result<VeltranoType, TypeCheckError> {
         if let Expr::Identifier(func_name) = &call.callee.node {
+            // Variables of function type shadow functions of the same name
+            if let Some(function_type) = self
+                .env
+                .lookup_variable(func_name)
+                .filter(|t| t.constructor == TypeConstructor::Function)
+                .cloned()
+            {
+                return self.check_function_value_call(func_name, &function_type, call, span);
+            }
+
             // Check if this is a built-in function first
             if self.builtin_registry.is_rust_macro(func_name) {
                 return self.check_rust_macro_call(func_name, call, span);
@@ -229,19 +329,15 @@ impl VeltranoTypeChecker {
 
             // Check user-defined functions first (highest priority)
             if let Some(func_sig) = self.env.lookup_function(func_name).cloned() {
-                // Check argument count (excluding standalone comments)
-                let non_comment_args = Self::filter_non_comment_args(&call.args);
-                let actual_arg_count = non_comment_args.len();
-
-                if actual_arg_count != func_sig.parameters.len() {
-                    return Err(TypeCheckError::ArgumentCountMismatch {
+                if func_sig.is_unsafe && !self.in_unsafe_block {
+                    return Err(TypeCheckError::UnsafeCallOutsideUnsafeBlock {
                         function: func_name.clone(),
-                        expected: func_sig.parameters.len(),
-                        actual: actual_arg_count,
-                        location: SourceLocation::new(span.start_line(), span.start_column()),
+                        span: span.clone(),
                     });
                 }
 
+                let non_comment_args = Self::filter_non_comment_args(&call.args);
+
                 // Check if this is a generic function
                 let has_generic_params = func_sig
                     .parameters
@@ -249,52 +345,102 @@ impl VeltranoTypeChecker {
                     .any(|p| matches!(&p.constructor, TypeConstructor::Generic(_, _)));
 
                 if has_generic_params {
+                    // Check argument count (excluding standalone comments)
+                    if non_comment_args.len() != func_sig.parameters.len() {
+                        return Err(TypeCheckError::ArgumentCountMismatch {
+                            function: func_name.clone(),
+                            expected: func_sig.parameters.len(),
+                            actual: non_comment_args.len(),
+                            location: SourceLocation::new(span.start_line(), span.start_column()),
+                        });
+                    }
                     // Handle generic function instantiation
-                    return self.check_generic_function_call(func_name, &func_sig, call, span);
+                    return self
+                        .check_generic_function_call(func_name, &func_sig, call, span)
+                        .map(|return_type| Self::call_result_type(&func_sig, return_type));
                 }
 
+                // Match arguments to parameters by position or by name
+                let parameters: Vec<ParameterSpec> = func_sig
+                    .parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, _)| ParameterSpec {
+                        name: func_sig
+                            .parameter_names
+                            .get(i)
+                            .map(String::as_str)
+                            .unwrap_or_default(),
+                        has_default: func_sig.parameter_defaults.get(i).copied().unwrap_or(false),
+                    })
+                    .collect();
+                let bound_args = Self::bind_arguments(
+                    func_name,
+                    &parameters,
+                    &non_comment_args,
+                    span,
+                    |name, arg_span| TypeCheckError::InvalidArguments {
+                        function: func_name.clone(),
+                        reason: format!("no parameter named '{}'", name),
+                        location: arg_span.start.clone(),
+                    },
+                )?;
+
                 // Type check arguments
-                for (i, arg) in non_comment_args.iter().enumerate() {
-                    let arg_expr = match arg {
-                        Argument::Bare(expr, _) => expr,
-                        Argument::Named(name, _, _) => {
-                            return Err(TypeCheckError::UnsupportedFeature {
-                                feature: format!("Named argument '{}'", name),
-                                location: SourceLocation::new(
-                                    span.start_line(),
-                                    span.start_column(),
-                                ),
-                            });
-                        }
-                        Argument::Shorthand(field, _) => {
-                            return Err(TypeCheckError::UnsupportedFeature {
-                                feature: format!("Shorthand argument '.{}'", field),
-                                location: SourceLocation::new(
-                                    span.start_line(),
-                                    span.start_column(),
-                                ),
-                            });
+                let mut arg_types = Vec::new();
+                for (expected_type, bound_arg) in func_sig.parameters.iter().zip(bound_args) {
+                    let (actual_type, location) = match bound_arg {
+                        BoundArgument::Expr(arg_expr) => (
+                            self.check_expression_with_expected_type(
+                                arg_expr,
+                                Some(expected_type),
+                            )?,
+                            arg_expr.span.start.clone(),
+                        ),
+                        BoundArgument::Variable(name) => (
+                            self.check_moving_identifier(name, span)?,
+                            span.start.clone(),
+                        ),
+                        // Defaults are checked against the parameter type at the declaration
+                        BoundArgument::Default => {
+                            arg_types.push(expected_type.clone());
+                            continue;
                         }
-                        Argument::StandaloneComment(_, _) => unreachable!(), // filtered out
                     };
 
-                    let expected_type = &func_sig.parameters[i];
-                    let actual_type =
-                        self.check_expression_with_expected_type(arg_expr, Some(expected_type))?;
-
-                    if &actual_type != expected_type {
+                    if !self.is_assignable(expected_type, &actual_type) {
                         return Err(TypeCheckError::TypeMismatch {
                             expected: expected_type.clone(),
                             actual: actual_type,
-                            location: SourceLocation::new(
-                                arg_expr.span.start_line(),
-                                arg_expr.span.start_column(),
-                            ),
+                            location,
                         });
                     }
+                    arg_types.push(actual_type);
                 }
 
-                return Ok(func_sig.return_type.clone());
+                let return_type = if func_sig.lifetime_params.is_empty() {
+                    func_sig.return_type.clone()
+                } else {
+                    self.bind_call_lifetimes(
+                        call.id,
+                        &func_sig.lifetime_params,
+                        &func_sig.parameters,
+                        &arg_types,
+                        &func_sig.return_type,
+                    )
+                };
+                return Ok(Self::call_result_type(&func_sig, return_type));
+            }
+
+            // vecOf(..), arrayOf(..), Array(size, value), mapOf(..), sortedMapOf(..)
+            // and setOf(..) construct collections
+            if is_collection_literal_name(func_name) {
+                return self.check_collection_literal(call, span, None);
+            }
+
+            // Pair(a, b) and Triple(a, b, c) construct tuples
+            if let Some(arity) = tuple_constructor_arity(func_name) {
+                return self.check_tuple_constructor_call(func_name, arity, call, span);
             }
 
             // Check if this is an imported method being called as a function
@@ -316,7 +462,11 @@ impl VeltranoTypeChecker {
                 location: SourceLocation::new(span.start_line(), span.start_column()),
             })
         } else {
-            // For now, only support direct function calls
+            // Calls through other expressions need a function value
+            let callee_type = self.check_expression(&call.callee)?;
+            if callee_type.constructor == TypeConstructor::Function {
+                return self.check_function_value_call("function value", &callee_type, call, span);
+            }
             Err(TypeCheckError::FunctionNotFound {
                 name: "unknown".to_string(),
                 location: SourceLocation::new(span.start_line(), span.start_column()),
@@ -349,6 +499,12 @@ impl VeltranoTypeChecker {
         let first_non_comment_arg = non_comment_args[0]; // Safe because we already checked count
 
         let arg_type = match first_non_comment_arg {
+            // MutRef(x) borrows and clones its argument
+            Argument::Bare(expr, _) if func_name == "MutRef" => {
+                let arg_type = self.check_place_expression(expr)?;
+                self.record_clone_borrow(expr, span)?;
+                arg_type
+            }
             Argument::Bare(expr, _) => self.check_expression(expr)?,
             Argument::Named(_, _, _) | Argument::Shorthand(_, _) => {
                 return Err(TypeCheckError::ArgumentCountMismatch {
@@ -380,6 +536,287 @@ impl VeltranoTypeChecker {
         }
     }
 
+    /// Whether a call is a builtin collection literal not shadowed by user code
+    pub(super) fn is_collection_literal_call(&self, call: &CallExpr) -> bool {
+        match &call.callee.node {
+            Expr::Identifier(name) => {
+                is_collection_literal_name(name)
+                    && self.env.lookup_function(name).is_none()
+                    && self.env.lookup_data_class(name).is_none()
+            }
+            _ => false,
+        }
+    }
+
+    /// Check `vecOf(..)`, `arrayOf(..)`, `Array(size, value)` and the map and set
+    /// literals, inferring the element type from the elements or from the expected type
+    fn check_collection_literal(
+        &mut self,
+        call: &CallExpr,
+        span: &Span,
+        expected_type: Option<&VeltranoType>,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let func_name = match &call.callee.node {
+            Expr::Identifier(name) => name.clone(),
+            _ => unreachable!("collection literals are called by name"),
+        };
+        let location = SourceLocation::new(span.start_line(), span.start_column());
+
+        let mut elements = Vec::new();
+        for arg in Self::filter_non_comment_args(&call.args) {
+            match arg {
+                Argument::Bare(expr, _) => elements.push(expr),
+                Argument::Named(name, _, _) => {
+                    return Err(TypeCheckError::UnsupportedFeature {
+                        feature: format!("Named argument '{}' in {}", name, func_name),
+                        location,
+                    });
+                }
+                Argument::Shorthand(field, _) => {
+                    return Err(TypeCheckError::UnsupportedFeature {
+                        feature: format!("Shorthand argument '.{}' in {}", field, func_name),
+                        location,
+                    });
+                }
+                Argument::StandaloneComment(_, _) => unreachable!(), // filtered out
+            }
+        }
+
+        // Map entries are `key to value` pairs
+        let expected_element = match func_name.as_str() {
+            "mapOf" | "sortedMapOf" => expected_type
+                .and_then(|t| t.map_types())
+                .map(|(key, value)| VeltranoType::tuple(vec![key.clone(), value.clone()])),
+            "setOf" => expected_type.and_then(|t| t.set_element_type()).cloned(),
+            _ => expected_type.and_then(|t| t.element_type()).cloned(),
+        };
+
+        if func_name == "Array" {
+            return self.check_array_fill(&elements, expected_element.as_ref(), location);
+        }
+
+        let mut element_type = expected_element;
+        for element in &elements {
+            let actual =
+                self.check_expression_with_expected_type(element, element_type.as_ref())?;
+            match &element_type {
+                Some(expected)
+                    if !TypeValidator::is_assignable(
+                        expected,
+                        &actual,
+                        &mut self.trait_checker,
+                    ) =>
+                {
+                    return Err(TypeCheckError::TypeMismatch {
+                        expected: expected.clone(),
+                        actual,
+                        location: element.span.start.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => element_type = Some(actual),
+            }
+        }
+
+        let element_type = element_type.ok_or_else(|| TypeCheckError::CannotInferElementType {
+            function: func_name.clone(),
+            location: location.clone(),
+        })?;
+
+        match func_name.as_str() {
+            "vecOf" => Ok(VeltranoType::own(VeltranoType::vec(element_type))),
+            "setOf" => Ok(VeltranoType::own(VeltranoType::hash_set(element_type))),
+            "mapOf" | "sortedMapOf" => {
+                let (TypeConstructor::Tuple, [key, value]) =
+                    (&element_type.constructor, element_type.args.as_slice())
+                else {
+                    return Err(TypeCheckError::InvalidArguments {
+                        function: func_name,
+                        reason: "each entry must be written `key to value`".to_string(),
+                        location,
+                    });
+                };
+                Ok(VeltranoType::own(if func_name == "mapOf" {
+                    VeltranoType::hash_map(key.clone(), value.clone())
+                } else {
+                    VeltranoType::btree_map(key.clone(), value.clone())
+                }))
+            }
+            _ => {
+                // Arrays of Copy elements are naturally owned
+                let array = VeltranoType::array(element_type, elements.len());
+                if array.implements_copy(&mut self.trait_checker) {
+                    Ok(array)
+                } else {
+                    Ok(VeltranoType::own(array))
+                }
+            }
+        }
+    }
+
+    /// Check `Array(size, value)`, which repeats a Copy value `size` times
+    fn check_array_fill(
+        &mut self,
+        args: &[&LocatedExpr],
+        expected_element: Option<&VeltranoType>,
+        location: SourceLocation,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        if args.len() != 2 {
+            return Err(TypeCheckError::ArgumentCountMismatch {
+                function: "Array".to_string(),
+                expected: 2,
+                actual: args.len(),
+                location,
+            });
+        }
+
+        let size = match &args[0].node {
+            Expr::Literal(LiteralExpr::Int(size)) if *size >= 0 => *size as usize,
+            _ => {
+                return Err(TypeCheckError::InvalidTypeConstructor {
+                    message: "Array(size, value) requires a non-negative integer literal size"
+                        .to_string(),
+                    location: args[0].span.start.clone(),
+                });
+            }
+        };
+
+        let element_type = self.check_expression_with_expected_type(args[1], expected_element)?;
+        if !element_type.implements_copy(&mut self.trait_checker) {
+            return Err(TypeCheckError::InvalidTypeConstructor {
+                message: format!(
+                    "Array(size, value) repeats its value and requires a Copy element type, found {:?}. Use arrayOf(...) to list the elements instead.",
+                    element_type
+                ),
+                location: args[1].span.start.clone(),
+            });
+        }
+
+        Ok(VeltranoType::array(element_type, size))
+    }
+
+    /// Check index expression `xs[i]`
+    fn check_index_expression(
+        &mut self,
+        index: &IndexExpr,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let object_type = self.check_place_expression(&index.object)?;
+        self.node_types.insert(index.id, object_type.clone());
+
+        // Data classes are indexed through their `operator fun get`
+        if let Some(signature) = object_type
+            .data_class_name()
+            .and_then(|name| self.env.lookup_data_class(name))
+            .and_then(|data_class| data_class.operator("get"))
+            .cloned()
+        {
+            let parameter_type = &signature.parameters[0];
+            let index_type = match &index.index.node {
+                // Integer literals are accepted for USize parameters, as in built-in indexing
+                Expr::Literal(LiteralExpr::Int(value))
+                    if *value >= 0 && parameter_type.constructor == TypeConstructor::USize =>
+                {
+                    VeltranoType::usize()
+                }
+                _ => {
+                    self.check_expression_with_expected_type(&index.index, Some(parameter_type))?
+                }
+            };
+            if !self.types_equal(parameter_type, &index_type) {
+                return Err(TypeCheckError::TypeMismatch {
+                    expected: parameter_type.clone(),
+                    actual: index_type,
+                    location: index.index.span.start.clone(),
+                });
+            }
+            return Ok(signature.return_type);
+        }
+
+        // Maps are indexed by key and yield an optional borrowed value, like `m.get(key)`
+        if let Some((key_type, value_type)) = object_type.map_types() {
+            let (key_type, value_type) = (key_type.clone(), value_type.clone());
+            // Owned keys are looked up by their borrowed form
+            let lookup_type = match &key_type.constructor {
+                TypeConstructor::Own => key_type.inner().cloned().unwrap_or(key_type),
+                _ => key_type,
+            };
+            let index_type =
+                self.check_expression_with_expected_type(&index.index, Some(&lookup_type))?;
+            // String keys can also be looked up by Str
+            let is_str_lookup = lookup_type.constructor == TypeConstructor::String
+                && index_type.constructor == TypeConstructor::Str;
+            if !self.types_equal(&lookup_type, &index_type) && !is_str_lookup {
+                return Err(TypeCheckError::TypeMismatch {
+                    expected: lookup_type,
+                    actual: index_type,
+                    location: index.index.span.start.clone(),
+                });
+            }
+            return Ok(map_lookup_type(&value_type, &mut self.trait_checker));
+        }
+
+        // Integer literals are accepted as USize indices directly
+        let index_type = match &index.index.node {
+            Expr::Literal(LiteralExpr::Int(value)) if *value >= 0 => VeltranoType::usize(),
+            _ => self.check_expression(&index.index)?,
+        };
+
+        match object_type.element_type().cloned() {
+            Some(element_type) if index_type.constructor == TypeConstructor::USize => {
+                // Non-Copy elements are borrowed (reference-by-default)
+                Ok(element_type
+                    .borrowed_component_type(&mut self.trait_checker)
+                    .unwrap_or(element_type))
+            }
+            _ => Err(TypeCheckError::IndexingNotSupported {
+                object_type,
+                index_type,
+                location: index.index.span.start.clone(),
+            }),
+        }
+    }
+
+    /// Check `Pair(..)` / `Triple(..)` construction
+    fn check_tuple_constructor_call(
+        &mut self,
+        func_name: &str,
+        arity: usize,
+        call: &CallExpr,
+        span: &Span,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let non_comment_args = Self::filter_non_comment_args(&call.args);
+        if non_comment_args.len() != arity {
+            return Err(TypeCheckError::ArgumentCountMismatch {
+                function: func_name.to_string(),
+                expected: arity,
+                actual: non_comment_args.len(),
+                location: SourceLocation::new(span.start_line(), span.start_column()),
+            });
+        }
+
+        let mut element_types = Vec::with_capacity(arity);
+        for arg in non_comment_args {
+            match arg {
+                Argument::Bare(expr, _) => element_types.push(self.check_expression(expr)?),
+                Argument::Named(name, _, _) => {
+                    return Err(TypeCheckError::UnsupportedFeature {
+                        feature: format!("Named argument '{}' in {}", name, func_name),
+                        location: SourceLocation::new(span.start_line(), span.start_column()),
+                    });
+                }
+                Argument::Shorthand(field, _) => {
+                    return Err(TypeCheckError::UnsupportedFeature {
+                        feature: format!("Shorthand argument '.{}' in {}", field, func_name),
+                        location: SourceLocation::new(span.start_line(), span.start_column()),
+                    });
+                }
+                Argument::StandaloneComment(_, _) => unreachable!(), // filtered out
+            }
+        }
+
+        Ok(VeltranoType::tuple(element_types))
+    }
+
     /// Check data class constructor call
     fn check_data_class_constructor_call(
         &mut self,
@@ -391,68 +828,159 @@ impl VeltranoTypeChecker {
         // Filter out comments to get actual arguments
         let actual_args = Self::filter_non_comment_args(&call.args);
 
-        // Check argument count
-        if actual_args.len() != data_class.fields.len() {
-            return Err(TypeCheckError::ArgumentCountMismatch {
-                function: class_name.to_string(),
-                expected: data_class.fields.len(),
-                actual: actual_args.len(),
-                location: SourceLocation::new(span.start_line(), span.start_column()),
-            });
-        }
+        // Match arguments to fields by position or by name; fields with defaults may be omitted
+        let parameters: Vec<ParameterSpec> = data_class
+            .fields
+            .iter()
+            .map(|field| ParameterSpec {
+                name: &field.name,
+                has_default: field.has_default,
+            })
+            .collect();
+        let bound_args = Self::bind_arguments(
+            class_name,
+            &parameters,
+            &actual_args,
+            span,
+            |name, arg_span| TypeCheckError::FieldNotFound {
+                object_type: VeltranoType::custom(class_name.to_string()),
+                field: name.to_string(),
+                location: SourceLocation::new(arg_span.start_line(), arg_span.start_column()),
+            },
+        )?;
 
-        // Check argument types, handling named arguments and field order
-        for (i, arg) in actual_args.iter().enumerate() {
-            let (expected_field, actual_type, arg_span) = match arg {
-                Argument::Bare(expr, _) => {
-                    // Positional argument - match by index
-                    let field = &data_class.fields[i];
-                    let actual_type = self.check_expression(expr)?;
-                    (field, actual_type, expr.span.clone())
-                }
-                Argument::Named(field_name, expr, _) => {
-                    // Named argument - find matching field
-                    let field = data_class
-                        .fields
-                        .iter()
-                        .find(|f| f.name == *field_name)
-                        .ok_or_else(|| TypeCheckError::FieldNotFound {
-                            object_type: VeltranoType {
-                                constructor: TypeConstructor::Custom(class_name.to_string()),
-                                args: vec![],
-                            },
-                            field: field_name.clone(),
-                            location: SourceLocation::new(
-                                expr.span.start_line(),
-                                expr.span.start_column(),
-                            ),
-                        })?;
-                    let actual_type = self.check_expression(expr)?;
-                    (field, actual_type, expr.span.clone())
+        // Check argument types
+        for (expected_field, bound_arg) in data_class.fields.iter().zip(bound_args) {
+            let (actual_type, arg_span) = match bound_arg {
+                BoundArgument::Expr(expr) => {
+                    self.check_stored_function_value(expr, "stored in a data class field")?;
+                    (self.check_expression(expr)?, expr.span.clone())
                 }
-                Argument::Shorthand(var_name, _) => {
-                    // Shorthand argument - field name is the variable name
-                    let field = data_class
-                        .fields
-                        .iter()
-                        .find(|f| f.name == *var_name)
-                        .ok_or_else(|| TypeCheckError::FieldNotFound {
-                            object_type: VeltranoType {
-                                constructor: TypeConstructor::Custom(class_name.to_string()),
-                                args: vec![],
-                            },
-                            field: var_name.clone(),
-                            location: SourceLocation::new(span.start_line(), span.start_column()),
-                        })?;
+                BoundArgument::Variable(var_name) => {
+                    self.check_stored_function_variable(
+                        var_name,
+                        "stored in a data class field",
+                        span,
+                    )?;
                     // For shorthand, we need to create a dummy span
-                    let actual_type = self.check_identifier(var_name, span)?;
-                    (field, actual_type, span.clone())
+                    (self.check_moving_identifier(var_name, span)?, span.clone())
                 }
-                Argument::StandaloneComment(_, _) => unreachable!(), // Filtered out above
+                BoundArgument::Default => continue,
             };
 
             // Check type compatibility
-            if !TypeValidator::types_equal(&expected_field.field_type, &actual_type) {
+            if !TypeValidator::is_assignable(
+                &expected_field.field_type,
+                &actual_type,
+                &mut self.trait_checker,
+            ) {
+                return Err(TypeCheckError::TypeMismatch {
+                    expected: expected_field.field_type.clone(),
+                    actual: actual_type,
+                    location: SourceLocation::new(arg_span.start_line(), arg_span.start_column()),
+                });
+            }
+        }
+
+        // Return the data class type as owned; Copy data classes are values already
+        let class_type = VeltranoType::custom(class_name.to_string());
+        if class_type.implements_copy(&mut self.trait_checker) {
+            Ok(class_type)
+        } else {
+            Ok(VeltranoType::own(class_type))
+        }
+    }
+
+    /// Check `.copy()` of a data class; every field may be overridden by name
+    /// or shorthand
+    pub(super) fn check_data_class_copy(
+        &mut self,
+        object_type: &VeltranoType,
+        class_name: &str,
+        copy: &MethodCallExpr,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        self.node_types.insert(copy.id, object_type.clone());
+        let span = &copy.object.span;
+
+        let class_name = class_name.to_string();
+        let data_class = self
+            .env
+            .lookup_data_class(&class_name)
+            .cloned()
+            .ok_or_else(|| TypeCheckError::MethodNotFound {
+                receiver_type: object_type.clone(),
+                method: "copy".to_string(),
+                location: copy.object.span.start.clone(),
+            })?;
+
+        // The fields that aren't overridden are cloned
+        let is_clone = self
+            .data_class_derives
+            .get(&class_name)
+            .is_some_and(|derives| derives.iter().any(|d| d == "Clone"));
+        if !is_clone {
+            return Err(TypeCheckError::InvalidArguments {
+                function: format!("{}.copy", class_name),
+                reason: format!(
+                    "copies are cloned, but not every field of '{}' implements Clone",
+                    class_name
+                ),
+                location: copy.object.span.start.clone(),
+            });
+        }
+
+        if let Some(expr) = copy.args.first() {
+            return Err(TypeCheckError::InvalidArguments {
+                function: format!("{}.copy", class_name),
+                reason: "fields must be overridden by name or with .field shorthand".to_string(),
+                location: expr.span.start.clone(),
+            });
+        }
+        let actual_args = Self::filter_non_comment_args(&copy.named_args);
+
+        // Fields not overridden keep the value of the copied object
+        let parameters: Vec<ParameterSpec> = data_class
+            .fields
+            .iter()
+            .map(|field| ParameterSpec {
+                name: &field.name,
+                has_default: true,
+            })
+            .collect();
+        let bound_args = Self::bind_arguments(
+            &format!("{}.copy", class_name),
+            &parameters,
+            &actual_args,
+            span,
+            |name, arg_span| TypeCheckError::FieldNotFound {
+                object_type: VeltranoType::custom(class_name.clone()),
+                field: name.to_string(),
+                location: SourceLocation::new(arg_span.start_line(), arg_span.start_column()),
+            },
+        )?;
+
+        for (expected_field, bound_arg) in data_class.fields.iter().zip(bound_args) {
+            let (actual_type, arg_span) = match bound_arg {
+                BoundArgument::Expr(expr) => {
+                    self.check_stored_function_value(expr, "stored in a data class field")?;
+                    (self.check_expression(expr)?, expr.span.clone())
+                }
+                BoundArgument::Variable(var_name) => {
+                    self.check_stored_function_variable(
+                        var_name,
+                        "stored in a data class field",
+                        span,
+                    )?;
+                    (self.check_moving_identifier(var_name, span)?, span.clone())
+                }
+                BoundArgument::Default => continue,
+            };
+
+            if !TypeValidator::is_assignable(
+                &expected_field.field_type,
+                &actual_type,
+                &mut self.trait_checker,
+            ) {
                 return Err(TypeCheckError::TypeMismatch {
                     expected: expected_field.field_type.clone(),
                     actual: actual_type,
@@ -461,31 +989,41 @@ impl VeltranoTypeChecker {
             }
         }
 
-        // Return the data class type as owned
-        Ok(VeltranoType::own(VeltranoType {
-            constructor: TypeConstructor::Custom(class_name.to_string()),
-            args: vec![],
-        }))
+        // The copy is a new value, owned like a constructed one
+        let class_type = VeltranoType::custom(class_name);
+        if class_type.implements_copy(&mut self.trait_checker) {
+            Ok(class_type)
+        } else {
+            Ok(VeltranoType::own(class_type))
+        }
     }
 
     /// Check Rust macro call (skip type checking)
     fn check_rust_macro_call(
         &mut self,
-        _func_name: &str,
+        func_name: &str,
         call: &CallExpr,
-        _span: &Span,
+        span: &Span,
     ) -> Result<VeltranoType, TypeCheckError> {
+        if func_name == "assertEquals" {
+            return self.check_assert_equals_call(call, span);
+        }
+
         // Rust macros skip type checking - they accept any arguments
-        // Just validate that arguments are syntactically correct expressions
+        // Just validate that arguments are syntactically correct expressions;
+        // macros borrow the variables they are given
         for arg in &call.args {
             match arg {
                 Argument::Bare(expr, _) => {
-                    self.check_expression(expr)?; // Ensure expression is valid
+                    self.check_place_expression(expr)?; // Ensure expression is valid
                 }
                 Argument::Named(_, expr, _) => {
-                    self.check_expression(expr)?; // Ensure expression is valid
+                    self.check_place_expression(expr)?; // Ensure expression is valid
+                }
+                Argument::Shorthand(name, _) => {
+                    self.check_not_moved(name, span, false)?;
+                    self.check_borrow_use(name, span)?;
                 }
-                Argument::Shorthand(_, _) => continue, // No expression to validate
                 Argument::StandaloneComment(_, _) => continue, // Skip comments
             }
         }
@@ -494,13 +1032,72 @@ impl VeltranoTypeChecker {
         Ok(VeltranoType::unit())
     }
 
+    /// `assertEquals(expected, actual, ...)` compares two values of the same
+    /// type; any further arguments form the failure message
+    fn check_assert_equals_call(
+        &mut self,
+        call: &CallExpr,
+        span: &Span,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let args = Self::filter_non_comment_args(&call.args);
+        let (expected, actual) = match args.as_slice() {
+            [Argument::Bare(expected, _), Argument::Bare(actual, _), ..] => (expected, actual),
+            _ => {
+                return Err(TypeCheckError::InvalidArguments {
+                    function: "assertEquals".to_string(),
+                    reason: "expected the expected and actual values as the first two arguments"
+                        .to_string(),
+                    location: span.start.clone(),
+                })
+            }
+        };
+
+        let expected_type = self.check_place_expression(expected)?;
+        let actual_type = match &actual.node {
+            Expr::Identifier(_) => self.check_place_expression(actual)?,
+            _ => self.check_expression_with_expected_type(actual, Some(&expected_type))?,
+        };
+        if !self.types_equal(&expected_type, &actual_type) {
+            return Err(TypeCheckError::TypeMismatch {
+                expected: expected_type,
+                actual: actual_type,
+                location: actual.span.start.clone(),
+            });
+        }
+
+        for arg in &args[2..] {
+            if let Argument::Bare(expr, _) | Argument::Named(_, expr, _) = arg {
+                self.check_place_expression(expr)?;
+            }
+        }
+        Ok(VeltranoType::unit())
+    }
+
     /// Check field access expression
     pub(super) fn check_field_access(
         &mut self,
         field_access: &FieldAccessExpr,
         span: &Span,
     ) -> Result<VeltranoType, TypeCheckError> {
-        let object_type = self.check_expression(&field_access.object)?;
+        let object_type = self.check_place_expression(&field_access.object)?;
+        self.node_types.insert(field_access.id, object_type.clone());
+
+        // Tuples (directly or through a reference) expose positional components
+        let tuple_type = match &object_type.constructor {
+            TypeConstructor::Ref => object_type.inner(),
+            _ => Some(&object_type),
+        }
+        .filter(|t| matches!(t.constructor, TypeConstructor::Tuple));
+        if let Some(tuple_type) = tuple_type {
+            return tuple_component_index(&field_access.field)
+                .and_then(|index| tuple_type.args.get(index))
+                .cloned()
+                .ok_or_else(|| TypeCheckError::FieldNotFound {
+                    object_type: object_type.clone(),
+                    field: field_access.field.clone(),
+                    location: SourceLocation::new(span.start_line(), span.start_column()),
+                });
+        }
 
         // Handle field access based on the object type
         match &object_type.constructor {
@@ -560,3 +1157,20 @@ impl VeltranoTypeChecker {
         }
     }
 }
+
+/// Arity of the builtin tuple constructors (`Pair`, `Triple`)
+pub(super) fn tuple_constructor_arity(name: &str) -> Option<usize> {
+    match name {
+        "Pair" => Some(2),
+        "Triple" => Some(3),
+        _ => None,
+    }
+}
+
+/// Builtin collection literal functions
+fn is_collection_literal_name(name: &str) -> bool {
+    matches!(
+        name,
+        "vecOf" | "arrayOf" | "Array" | "mapOf" | "sortedMapOf" | "setOf"
+    )
+}
@@ -0,0 +1,185 @@
+//! C interop checking
+//!
+//! Functions exported with `@CExport` and foreign functions declared with
+//! `extern "C" fun` cross the C ABI, so their signatures are restricted to
+//! types that have the same representation in C and Rust.
+
+use super::annotations::invalid_annotation;
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{ExternFunStmt, FunDeclStmt, Located, Parameter};
+use crate::error::SourceLocation;
+use crate::types::{FunctionSignature, TypeConstructor, VeltranoType};
+
+impl VeltranoTypeChecker {
+    /// Check an `extern "C" fun` declaration and register it as an unsafe function
+    pub(super) fn check_extern_function(
+        &mut self,
+        extern_fun: &ExternFunStmt,
+    ) -> Result<(), TypeCheckError> {
+        if extern_fun.abi != "C" {
+            return Err(TypeCheckError::UnsupportedFeature {
+                feature: format!("extern \"{}\" functions", extern_fun.abi),
+                location: extern_fun.location.clone(),
+            });
+        }
+
+        for param in &extern_fun.params {
+            if param.default_value.is_some() {
+                return Err(TypeCheckError::UnsupportedFeature {
+                    feature: "default values on extern function parameters".to_string(),
+                    location: param.param_type.span.start.clone(),
+                });
+            }
+            self.validate_type(&param.param_type.node, param.param_type.span.start.clone())?;
+        }
+        if let Some(return_type) = &extern_fun.return_type {
+            self.validate_type(&return_type.node, return_type.span.start.clone())?;
+        }
+        self.check_ffi_signature(
+            &extern_fun.name,
+            &extern_fun.params,
+            extern_fun.return_type.as_ref(),
+        )?;
+
+        self.env.declare_function(
+            extern_fun.name.clone(),
+            Self::extern_function_signature(extern_fun),
+        );
+        Ok(())
+    }
+
+    /// Signature of a foreign function; calling it is always unsafe
+    pub(super) fn extern_function_signature(extern_fun: &ExternFunStmt) -> FunctionSignature {
+        FunctionSignature {
+            name: extern_fun.name.clone(),
+            parameters: extern_fun
+                .params
+                .iter()
+                .map(|p| p.param_type.node.clone())
+                .collect(),
+            return_type: extern_fun
+                .return_type
+                .as_ref()
+                .map(|t| t.node.clone())
+                .unwrap_or_else(VeltranoType::unit),
+            lifetime_params: vec![],
+            parameter_names: extern_fun.params.iter().map(|p| p.name.clone()).collect(),
+            parameter_defaults: vec![false; extern_fun.params.len()],
+            is_async: false,
+            is_unsafe: true,
+        }
+    }
+
+    /// Check that a `@CExport` function can be called from C
+    pub(super) fn check_c_export_function(
+        &mut self,
+        fun_decl: &FunDeclStmt,
+    ) -> Result<(), TypeCheckError> {
+        let Some(annotation) = fun_decl.annotations.iter().find(|a| a.name == "CExport") else {
+            return Ok(());
+        };
+
+        self.check_ffi_signature(
+            &fun_decl.name,
+            &fun_decl.params,
+            fun_decl.return_type.as_ref(),
+        )?;
+
+        let unsupported = if !fun_decl.lifetime_params.is_empty() {
+            Some("exported functions cannot have lifetime parameters")
+        } else if self.hidden_bump_functions.contains(&fun_decl.name) {
+            Some("exported functions cannot allocate into a caller's bump arena")
+        } else if fun_decl.is_async {
+            Some("exported functions cannot be suspend functions")
+        } else {
+            None
+        };
+        match unsupported {
+            Some(reason) => Err(invalid_annotation(annotation, reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that every parameter and the return type of a C function is FFI-safe
+    fn check_ffi_signature(
+        &mut self,
+        function: &str,
+        params: &[Parameter],
+        return_type: Option<&Located<VeltranoType>>,
+    ) -> Result<(), TypeCheckError> {
+        for param in params {
+            if !is_ffi_safe(&param.param_type.node) {
+                return Err(self.not_ffi_safe(
+                    function,
+                    format!("Parameter '{}'", param.name),
+                    &param.param_type.node,
+                    &param.param_type.span.start,
+                ));
+            }
+        }
+
+        if let Some(return_type) = return_type {
+            let returns_nothing = matches!(
+                return_type.node.constructor,
+                TypeConstructor::Unit | TypeConstructor::Nothing
+            );
+            if !returns_nothing && !is_ffi_safe(&return_type.node) {
+                return Err(self.not_ffi_safe(
+                    function,
+                    "Return type".to_string(),
+                    &return_type.node,
+                    &return_type.span.start,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn not_ffi_safe(
+        &mut self,
+        function: &str,
+        item: String,
+        veltrano_type: &VeltranoType,
+        location: &SourceLocation,
+    ) -> TypeCheckError {
+        TypeCheckError::NotFfiSafe {
+            function: function.to_string(),
+            item,
+            rust_type: veltrano_type
+                .to_rust_type(&mut self.trait_checker)
+                .to_rust_syntax(),
+            location: location.clone(),
+        }
+    }
+}
+
+/// Integers, Bool and raw pointers to sized values are passed to and from C unchanged
+fn is_ffi_safe(veltrano_type: &VeltranoType) -> bool {
+    match veltrano_type.constructor {
+        TypeConstructor::I32
+        | TypeConstructor::I64
+        | TypeConstructor::ISize
+        | TypeConstructor::U32
+        | TypeConstructor::U64
+        | TypeConstructor::USize
+        | TypeConstructor::Bool => true,
+        TypeConstructor::Ptr | TypeConstructor::MutPtr => {
+            veltrano_type.inner().is_some_and(is_thin_pointee)
+        }
+        _ => false,
+    }
+}
+
+/// Whether a pointer to this type is a plain address: strings, slices and trait
+/// objects are unsized, and pointers or references to them carry a length or vtable
+fn is_thin_pointee(pointee: &VeltranoType) -> bool {
+    match pointee.constructor {
+        TypeConstructor::Str | TypeConstructor::Slice | TypeConstructor::Dyn(_) => false,
+        TypeConstructor::Own
+        | TypeConstructor::Ref
+        | TypeConstructor::MutRef
+        | TypeConstructor::Ptr
+        | TypeConstructor::MutPtr => pointee.inner().is_some_and(is_thin_pointee),
+        _ => true,
+    }
+}
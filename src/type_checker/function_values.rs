@@ -0,0 +1,217 @@
+//! Function references and calls through function values
+//!
+//! `::add` refers to a top-level function and `Person::greet` to a member
+//! function, which takes its receiver as the first parameter. Both have a
+//! function type such as `(I64, I64) -> I64` and can be called like functions.
+//! A reference to a function that takes a bump parameter captures the caller's
+//! bump, so it can be passed to a parameter but not returned or stored in a
+//! data class field, which hold plain function pointers.
+
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{Argument, CallExpr, Expr, FunctionRefExpr, LocatedExpr};
+use crate::error::{SourceLocation, Span};
+use crate::types::{FunctionSignature, TypeConstructor, VeltranoType};
+
+impl VeltranoTypeChecker {
+    /// Type of a function reference
+    pub(super) fn check_function_reference(
+        &mut self,
+        function_ref: &FunctionRefExpr,
+        span: &Span,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let location = span.start.clone();
+        let invalid = |reason: &str| TypeCheckError::InvalidFunctionReference {
+            name: function_ref.name.clone(),
+            reason: reason.to_string(),
+            location: location.clone(),
+        };
+
+        let (receiver, signature) = match &function_ref.receiver {
+            Some(class_name) => {
+                let data_class = self.env.lookup_data_class(class_name).ok_or_else(|| {
+                    TypeCheckError::InvalidFunctionReference {
+                        name: format!("{}::{}", class_name, function_ref.name),
+                        reason: format!("'{}' is not a data class", class_name),
+                        location: location.clone(),
+                    }
+                })?;
+                let method = data_class.method(&function_ref.name).ok_or_else(|| {
+                    TypeCheckError::MethodNotFound {
+                        receiver_type: VeltranoType::custom(class_name.clone()),
+                        method: function_ref.name.clone(),
+                        location: location.clone(),
+                    }
+                })?;
+                (
+                    Some(VeltranoType::custom(class_name.clone())),
+                    method.signature.clone(),
+                )
+            }
+            None => {
+                if self
+                    .builtin_registry
+                    .is_builtin_function(&function_ref.name)
+                {
+                    return Err(invalid("built-in functions cannot be referenced"));
+                }
+                let signature = self
+                    .env
+                    .lookup_function(&function_ref.name)
+                    .ok_or_else(|| TypeCheckError::FunctionNotFound {
+                        name: function_ref.name.clone(),
+                        location: location.clone(),
+                    })?;
+                (None, signature.clone())
+            }
+        };
+
+        Self::check_referenceable(&signature).map_err(invalid)?;
+        let parameters = receiver.into_iter().chain(signature.parameters).collect();
+        Ok(VeltranoType::function(parameters, signature.return_type))
+    }
+
+    /// The function whose bump the function value `expr` captures, if any
+    fn captured_bump_function(&self, expr: &LocatedExpr) -> Option<String> {
+        match &expr.node {
+            Expr::Parenthesized(paren_expr) => self.captured_bump_function(&paren_expr.expr),
+            Expr::FunctionRef(function_ref) if function_ref.receiver.is_none() => {
+                let takes_bump = self.hidden_bump_functions.contains(&function_ref.name)
+                    || self
+                        .env
+                        .lookup_function(&function_ref.name)
+                        .is_some_and(|signature| !signature.lifetime_params.is_empty());
+                takes_bump.then(|| function_ref.name.clone())
+            }
+            Expr::Identifier(name) => self.captured_bump_of_variable(name),
+            _ => None,
+        }
+    }
+
+    /// The function whose bump the function value bound to variable `name` captures, if any
+    fn captured_bump_of_variable(&self, name: &str) -> Option<String> {
+        let depth = self.env.variable_scope_depth(name)?;
+        self.function_contexts
+            .last()?
+            .bump_closures
+            .get(&(name.to_string(), depth))
+            .cloned()
+    }
+
+    /// Remember whether the function value bound to a newly declared variable captures a bump
+    pub(super) fn record_bump_closure(&mut self, name: &str, initializer: &LocatedExpr) {
+        let function = self.captured_bump_function(initializer);
+        let depth = self.env.scope_depth();
+        if let Some(context) = self.function_contexts.last_mut() {
+            let key = (name.to_string(), depth);
+            match function {
+                Some(function) => context.bump_closures.insert(key, function),
+                None => context.bump_closures.remove(&key),
+            };
+        }
+    }
+
+    /// Check that a function value `placement` (returned, stored in a field)
+    /// does not capture a bump, which a function pointer can't hold
+    pub(super) fn check_stored_function_value(
+        &self,
+        expr: &LocatedExpr,
+        placement: &str,
+    ) -> Result<(), TypeCheckError> {
+        match self.captured_bump_function(expr) {
+            Some(name) => Err(Self::captured_bump_error(name, placement, &expr.span)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a shorthand field argument like [`Self::check_stored_function_value`]
+    pub(super) fn check_stored_function_variable(
+        &self,
+        name: &str,
+        placement: &str,
+        span: &Span,
+    ) -> Result<(), TypeCheckError> {
+        match self.captured_bump_of_variable(name) {
+            Some(name) => Err(Self::captured_bump_error(name, placement, span)),
+            None => Ok(()),
+        }
+    }
+
+    /// Error for a function value capturing the bump of function `name`
+    fn captured_bump_error(name: String, placement: &str, span: &Span) -> TypeCheckError {
+        TypeCheckError::InvalidFunctionReference {
+            name,
+            reason: format!(
+                "it takes a bump parameter, so its reference captures the caller's bump and can't be {}",
+                placement
+            ),
+            location: span.start.clone(),
+        }
+    }
+
+    /// Why a function cannot be turned into a Rust function value, if it can't
+    fn check_referenceable(signature: &FunctionSignature) -> Result<(), &'static str> {
+        if signature.is_unsafe {
+            return Err("unsafe functions can only be called inside an unsafe block");
+        }
+        if signature.is_async {
+            return Err("suspend functions cannot be referenced");
+        }
+        let is_generic = |t: &VeltranoType| {
+            matches!(
+                t.constructor,
+                TypeConstructor::Generic(_, _) | TypeConstructor::Function
+            )
+        };
+        if signature.parameters.iter().any(is_generic) {
+            return Err("functions with generic or function-typed parameters cannot be referenced");
+        }
+        Ok(())
+    }
+
+    /// Check a call through a value of function type, such as a parameter `f: (I64) -> I64`
+    pub(super) fn check_function_value_call(
+        &mut self,
+        name: &str,
+        function_type: &VeltranoType,
+        call: &CallExpr,
+        span: &Span,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let Some((parameters, return_type)) = function_type.function_parts() else {
+            return Err(TypeCheckError::FunctionNotFound {
+                name: name.to_string(),
+                location: span.start.clone(),
+            });
+        };
+
+        let args = Self::filter_non_comment_args(&call.args);
+        if args.len() != parameters.len() {
+            return Err(TypeCheckError::ArgumentCountMismatch {
+                function: name.to_string(),
+                expected: parameters.len(),
+                actual: args.len(),
+                location: SourceLocation::new(span.start_line(), span.start_column()),
+            });
+        }
+
+        for (expected_type, arg) in parameters.iter().zip(args) {
+            let Argument::Bare(arg_expr, _) = arg else {
+                return Err(TypeCheckError::InvalidArguments {
+                    function: name.to_string(),
+                    reason: "function values take positional arguments only".to_string(),
+                    location: span.start.clone(),
+                });
+            };
+            let actual_type =
+                self.check_expression_with_expected_type(arg_expr, Some(expected_type))?;
+            if !self.is_assignable(expected_type, &actual_type) {
+                return Err(TypeCheckError::TypeMismatch {
+                    expected: expected_type.clone(),
+                    actual: actual_type,
+                    location: arg_expr.span.start.clone(),
+                });
+            }
+        }
+
+        Ok(return_type.clone())
+    }
+}
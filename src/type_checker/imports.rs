@@ -284,6 +284,7 @@ impl ImportHandler {
                     MethodResolution {
                         rust_type: rust_type.clone(),
                         method_name: method_name.clone(),
+                        borrowed_args: Vec::new(),
                     },
                 );
 
@@ -323,6 +324,35 @@ pub fn register_builtin_imports(
     // Slice conversion
     register_type_method(handler, "Vec", "asSlice", "toSlice", trait_checker);
 
+    // Map and set methods
+    for map in ["std::collections::HashMap", "std::collections::BTreeMap"] {
+        for (rust_method, veltrano_name) in [
+            ("get", "get"),
+            ("containsKey", "containsKey"),
+            ("insert", "insert"),
+            ("remove", "remove"),
+            ("len", "length"),
+            ("isEmpty", "isEmpty"),
+        ] {
+            register_type_method(handler, map, rust_method, veltrano_name, trait_checker);
+        }
+    }
+    for (rust_method, veltrano_name) in [
+        ("contains", "contains"),
+        ("insert", "insert"),
+        ("remove", "remove"),
+        ("len", "length"),
+        ("isEmpty", "isEmpty"),
+    ] {
+        register_type_method(
+            handler,
+            "std::collections::HashSet",
+            rust_method,
+            veltrano_name,
+            trait_checker,
+        );
+    }
+
     crate::debug_println!(
         "DEBUG: Built-in imports registered. Has clone: {}, Has toString: {}, Has length: {}",
         handler.has_imports("clone"),
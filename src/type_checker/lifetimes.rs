@@ -0,0 +1,219 @@
+//! Lifetime label checking
+//!
+//! Validates `@label` annotations on types, keeps references with a function's
+//! own lifetime from escaping it, and checks that labeled references are only
+//! returned or assigned where the labels agree.
+
+use super::borrows::BorrowState;
+use super::escapes::ReferenceOrigins;
+use super::moves::MoveState;
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::error::SourceLocation;
+use crate::types::{TypeConstructor, VeltranoType};
+use std::collections::HashMap;
+
+/// The function whose body is being checked
+#[derive(Debug, Clone)]
+pub(super) struct FunctionContext {
+    pub name: String,
+    pub lifetime_params: Vec<String>,
+    pub return_type: VeltranoType,
+    pub is_async: bool,
+    pub moves: MoveState, // Owned variables moved so far in the body
+    pub reference_origins: ReferenceOrigins, // Where references bound to variables point
+    pub borrows: BorrowState, // Live `.ref()` and `.mutRef()` borrows
+    pub bump_closures: HashMap<(String, usize), String>, // Variables bound to references of functions taking a bump
+}
+
+/// Whether a lifetime label can be attached to this type level
+fn accepts_lifetime(veltrano_type: &VeltranoType) -> bool {
+    matches!(
+        veltrano_type.constructor,
+        TypeConstructor::Ref
+            | TypeConstructor::MutRef
+            | TypeConstructor::Str
+            | TypeConstructor::String
+            | TypeConstructor::Custom(_)
+    )
+}
+
+/// Label the outermost reference level of a type that carries no label yet
+pub(super) fn with_default_lifetime(
+    veltrano_type: VeltranoType,
+    default: Option<&String>,
+) -> VeltranoType {
+    match default {
+        Some(label) if veltrano_type.reference_lifetime().is_none() => {
+            if accepts_lifetime(&veltrano_type) {
+                veltrano_type.with_lifetime(Some(label.clone()))
+            } else {
+                let mut defaulted = veltrano_type;
+                defaulted.args = defaulted
+                    .args
+                    .into_iter()
+                    .map(|arg| with_default_lifetime(arg, Some(label)))
+                    .collect();
+                defaulted
+            }
+        }
+        _ => veltrano_type,
+    }
+}
+
+/// Bind the labels of a callee's parameter type to those of the argument passed for it
+fn bind_lifetimes(
+    param_type: &VeltranoType,
+    arg_type: &VeltranoType,
+    bindings: &mut HashMap<String, String>,
+) {
+    if let (Some(param_label), Some(arg_label)) =
+        (&param_type.lifetime, arg_type.reference_lifetime())
+    {
+        bindings
+            .entry(param_label.clone())
+            .or_insert_with(|| arg_label.clone());
+    }
+    for (param_arg, arg_arg) in param_type.args.iter().zip(&arg_type.args) {
+        bind_lifetimes(param_arg, arg_arg, bindings);
+    }
+}
+
+impl VeltranoTypeChecker {
+    /// Check that every label in a type is one of `allowed` and sits on a reference type
+    pub(super) fn check_lifetime_labels(
+        &self,
+        veltrano_type: &VeltranoType,
+        allowed: &[String],
+        location: &SourceLocation,
+    ) -> Result<(), TypeCheckError> {
+        if let Some(label) = &veltrano_type.lifetime {
+            if !accepts_lifetime(veltrano_type) {
+                return Err(TypeCheckError::InvalidLifetimeLabel {
+                    label: label.clone(),
+                    reason: "only reference types can carry a lifetime label".to_string(),
+                    location: location.clone(),
+                });
+            }
+            if !allowed.contains(label) {
+                return Err(TypeCheckError::InvalidLifetimeLabel {
+                    label: label.clone(),
+                    reason: "lifetime is not declared".to_string(),
+                    location: location.clone(),
+                });
+            }
+        }
+        for arg in &veltrano_type.args {
+            self.check_lifetime_labels(arg, allowed, location)?;
+        }
+        Ok(())
+    }
+
+    /// Check a signature type of `function`, whose own lifetime cannot appear in it
+    pub(super) fn check_signature_lifetimes(
+        &self,
+        veltrano_type: &VeltranoType,
+        function: &str,
+        lifetime_params: &[String],
+        location: &SourceLocation,
+    ) -> Result<(), TypeCheckError> {
+        if veltrano_type
+            .lifetime_labels()
+            .iter()
+            .any(|label| label.as_str() == function)
+        {
+            return Err(Self::escaping_lifetime(function, location));
+        }
+        self.check_lifetime_labels(veltrano_type, lifetime_params, location)
+    }
+
+    /// Labels usable in the body of the current function: its parameters and its own name
+    pub(super) fn labels_in_scope(&self) -> Vec<String> {
+        match self.function_contexts.last() {
+            Some(context) => {
+                let mut labels = context.lifetime_params.clone();
+                labels.push(context.name.clone());
+                labels
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Check that a value of type `actual` can be stored where `expected` is declared
+    pub(super) fn check_lifetime_compatibility(
+        &self,
+        expected: &VeltranoType,
+        actual: &VeltranoType,
+        location: &SourceLocation,
+    ) -> Result<(), TypeCheckError> {
+        if let (Some(expected_label), Some(actual_label)) =
+            (expected.reference_lifetime(), actual.reference_lifetime())
+        {
+            if expected_label != actual_label {
+                return Err(TypeCheckError::LifetimeMismatch {
+                    expected: expected_label.clone(),
+                    actual: actual_label.clone(),
+                    location: location.clone(),
+                });
+            }
+        }
+        for (expected_arg, actual_arg) in expected.args.iter().zip(&actual.args) {
+            self.check_lifetime_compatibility(expected_arg, actual_arg, location)?;
+        }
+        Ok(())
+    }
+
+    /// Check the lifetimes of a value returned from the current function
+    pub(super) fn check_return_lifetimes(
+        &self,
+        actual: &VeltranoType,
+        location: &SourceLocation,
+    ) -> Result<(), TypeCheckError> {
+        let Some(context) = self.function_contexts.last() else {
+            return Ok(());
+        };
+        if actual
+            .lifetime_labels()
+            .iter()
+            .any(|label| **label == context.name)
+        {
+            return Err(Self::escaping_lifetime(&context.name, location));
+        }
+        self.check_lifetime_compatibility(&context.return_type, actual, location)
+    }
+
+    /// Record which caller lifetimes a call to a labeled function binds, and
+    /// return the call's result type in terms of the caller's labels
+    pub(super) fn bind_call_lifetimes(
+        &mut self,
+        call_id: usize,
+        lifetime_params: &[String],
+        parameters: &[VeltranoType],
+        arg_types: &[VeltranoType],
+        return_type: &VeltranoType,
+    ) -> VeltranoType {
+        let mut bindings = HashMap::new();
+        for (param_type, arg_type) in parameters.iter().zip(arg_types) {
+            bind_lifetimes(param_type, arg_type, &mut bindings);
+        }
+
+        self.lifetime_bindings.insert(
+            call_id,
+            lifetime_params
+                .iter()
+                .map(|label| bindings.get(label).cloned())
+                .collect(),
+        );
+        return_type.map_lifetimes(&|label| bindings.get(label).cloned())
+    }
+
+    fn escaping_lifetime(function: &str, location: &SourceLocation) -> TypeCheckError {
+        TypeCheckError::InvalidLifetimeLabel {
+            label: function.to_string(),
+            reason: format!(
+                "references with the own lifetime of '{}' cannot escape it",
+                function
+            ),
+            location: location.clone(),
+        }
+    }
+}
@@ -3,13 +3,16 @@
 //! This module contains logic for checking method calls including
 //! imported methods, built-in methods, and trait methods.
 
-use crate::ast::MethodCallExpr;
-use crate::error::SourceLocation;
-use crate::rust_interop::{RustType, SelfKind};
+use crate::ast::{Argument, MethodCallExpr};
+use crate::builtins::OperatorMethod;
+use crate::error::{SourceLocation, Span};
+use crate::rust_interop::{associated_type_name, trait_path, RustType, SelfKind};
 use crate::types::{TypeConstructor, VeltranoType};
+use std::collections::HashMap;
 
 use super::error::{MethodResolution, TypeCheckError};
 use super::imports::ImportedMethod;
+use super::types::{bind_placeholders, substitute_placeholders};
 use super::VeltranoTypeChecker;
 
 impl VeltranoTypeChecker {
@@ -27,7 +30,67 @@ impl VeltranoTypeChecker {
         method_call: &MethodCallExpr,
         expected_type: Option<&VeltranoType>,
     ) -> Result<VeltranoType, TypeCheckError> {
-        let receiver_type = self.check_expression(&method_call.object)?;
+        let receiver_type = self.check_place_expression(&method_call.object)?;
+        let return_type =
+            self.check_method_call_on_receiver(&receiver_type, method_call, expected_type)?;
+
+        // Member functions and the borrowing operators leave the receiver in place;
+        // every other method takes an owned receiver by value
+        let borrows_receiver = self.node_types.contains_key(&method_call.id)
+            || [
+                OperatorMethod::Ref,
+                OperatorMethod::MutRef,
+                OperatorMethod::Deref,
+            ]
+            .iter()
+            .any(|op| op.method_name() == method_call.method);
+        if !borrows_receiver {
+            self.consume_place(&method_call.object, &receiver_type);
+        }
+        Ok(return_type)
+    }
+
+    /// Check a method call whose receiver has been checked
+    fn check_method_call_on_receiver(
+        &mut self,
+        receiver_type: &VeltranoType,
+        method_call: &MethodCallExpr,
+        expected_type: Option<&VeltranoType>,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let receiver_type = receiver_type.clone();
+
+        // Member functions of data classes take precedence over imports and built-ins
+        if let Some(return_type) = self.check_member_function_call(&receiver_type, method_call)? {
+            return Ok(return_type);
+        }
+
+        // Otherwise `.copy()` of a data class copies it with field overrides
+        if method_call.method == "copy" {
+            if let Some(class_name) = receiver_type.data_class_name() {
+                let class_name = class_name.to_string();
+                return self.check_data_class_copy(&receiver_type, &class_name, method_call);
+            }
+        }
+
+        // Only data class copies take named arguments
+        let has_named_args = method_call
+            .named_args
+            .iter()
+            .any(|arg| !matches!(arg, Argument::StandaloneComment(_, _)));
+        if has_named_args {
+            return Err(TypeCheckError::InvalidArguments {
+                function: method_call.method.clone(),
+                reason: "named arguments are only supported by copy() of a data class".to_string(),
+                location: method_call.object.span.start.clone(),
+            });
+        }
+
+        // Trait objects and impl Trait values call the methods of their trait
+        if let Some(return_type) =
+            self.check_trait_bound_method_call(&receiver_type, method_call)?
+        {
+            return Ok(return_type);
+        }
 
         // Check if this method is explicitly imported - imports shadow built-ins completely
         if let Some(imports) = self.import_handler.get_imports(&method_call.method) {
@@ -236,19 +299,22 @@ impl VeltranoTypeChecker {
                 }
                 1 => {
                     // Exactly one import matched - store the resolution
-                    let (rust_type, method_name, return_type) = &matching_imports[0];
                     crate::debug_println!(
                         "DEBUG: Storing method resolution for ID {}: {:?}.{}",
                         method_call.id,
-                        rust_type,
-                        method_name
+                        matching_imports[0].0,
+                        matching_imports[0].1
                     );
+                    let (rust_type, method_name, return_type) = matching_imports.remove(0);
+                    let borrowed_args =
+                        self.check_imported_method_args(&rust_type, &method_name, method_call)?;
                     let resolution = MethodResolution {
-                        rust_type: rust_type.clone(),
-                        method_name: method_name.clone(),
+                        rust_type,
+                        method_name,
+                        borrowed_args,
                     };
                     self.method_resolutions.insert(method_call.id, resolution);
-                    return Ok(return_type.clone());
+                    return Ok(return_type);
                 }
                 _ => {
                     // Multiple imports matched - ambiguous
@@ -269,12 +335,145 @@ impl VeltranoTypeChecker {
         self.check_builtin_method_call(&receiver_type, method_call)
     }
 
+    /// Check a call to a member function of a data class; `None` if the
+    /// receiver has no member with that name
+    fn check_member_function_call(
+        &mut self,
+        receiver_type: &VeltranoType,
+        method_call: &MethodCallExpr,
+    ) -> Result<Option<VeltranoType>, TypeCheckError> {
+        let Some(signature) = receiver_type
+            .data_class_name()
+            .and_then(|name| self.env.lookup_data_class(name))
+            .and_then(|data_class| data_class.method(&method_call.method))
+            .map(|method| method.signature.clone())
+        else {
+            return Ok(None);
+        };
+
+        if method_call.args.len() != signature.parameters.len() {
+            return Err(TypeCheckError::ArgumentCountMismatch {
+                function: method_call.method.clone(),
+                expected: signature.parameters.len(),
+                actual: method_call.args.len(),
+                location: method_call.object.span.start.clone(),
+            });
+        }
+
+        for (arg, param_type) in method_call.args.iter().zip(&signature.parameters) {
+            let arg_type = self.check_expression_with_expected_type(arg, Some(param_type))?;
+            if !self.is_assignable(param_type, &arg_type) {
+                return Err(TypeCheckError::TypeMismatch {
+                    expected: param_type.clone(),
+                    actual: arg_type,
+                    location: arg.span.start.clone(),
+                });
+            }
+        }
+
+        // The receiver type marks the call as a member call for codegen
+        self.node_types
+            .insert(method_call.id, receiver_type.clone());
+        Ok(Some(signature.return_type))
+    }
+
+    /// Check a call to a method of the trait of a `Dyn<Trait>` or `Impl<Trait>`
+    /// receiver, as declared by the trait; `None` if the trait has no method
+    /// with that name
+    fn check_trait_bound_method_call(
+        &mut self,
+        receiver_type: &VeltranoType,
+        method_call: &MethodCallExpr,
+    ) -> Result<Option<VeltranoType>, TypeCheckError> {
+        let Some((trait_name, trait_args)) = receiver_type.trait_bound() else {
+            return Ok(None);
+        };
+        let (trait_name, trait_args) = (trait_name.to_string(), trait_args.to_vec());
+        let Ok(Some(method_info)) = self
+            .trait_checker
+            .query_trait_method_directly(&trait_name, &method_call.method)
+        else {
+            return Ok(None);
+        };
+
+        // A method taking self by value can't be called through a reference
+        let receiver_is_borrowed = matches!(
+            receiver_type.to_rust_type(&mut self.trait_checker),
+            RustType::Ref { .. } | RustType::MutRef { .. }
+        );
+        let method_not_found = || TypeCheckError::MethodNotFound {
+            receiver_type: receiver_type.clone(),
+            method: method_call.method.clone(),
+            location: SourceLocation::new(
+                method_call.object.span.start_line(),
+                method_call.object.span.start_column(),
+            ),
+        };
+        if (method_info.self_kind == SelfKind::Value && receiver_is_borrowed)
+            || !self
+                .builtin_registry
+                .receiver_can_provide_rust_access_for_imported(
+                    receiver_type,
+                    &method_info.self_kind,
+                    &mut self.trait_checker,
+                )
+        {
+            return Err(method_not_found());
+        }
+
+        if method_call.args.len() != method_info._parameters.len() {
+            return Err(TypeCheckError::ArgumentCountMismatch {
+                function: method_call.method.clone(),
+                expected: method_info._parameters.len(),
+                actual: method_call.args.len(),
+                location: method_call.object.span.start.clone(),
+            });
+        }
+        for arg in &method_call.args {
+            self.check_expression(arg)?;
+        }
+
+        // The trait's type argument is its associated type, as in `Iterator<Item = T>`
+        let mut bindings = HashMap::new();
+        if let (Some(associated), Some(trait_arg)) =
+            (associated_type_name(&trait_name), trait_args.first())
+        {
+            bindings.insert(format!("${}", associated), trait_arg.clone());
+        }
+        let return_type = method_info
+            .return_type
+            .to_veltrano_type()
+            .map_err(|_| method_not_found())?;
+        let return_type = substitute_placeholders(&return_type, &bindings, &mut self.trait_checker);
+
+        // Trait methods are called through the trait, as for imported trait methods
+        self.method_resolutions.insert(
+            method_call.id,
+            MethodResolution {
+                rust_type: RustType::Custom {
+                    name: trait_path(&trait_name),
+                    generics: vec![],
+                },
+                method_name: method_call.method.clone(),
+                borrowed_args: Vec::new(),
+            },
+        );
+        Ok(Some(return_type))
+    }
+
     /// Check built-in method call (when no imports exist)
     fn check_builtin_method_call(
         &mut self,
         receiver_type: &VeltranoType,
         method_call: &MethodCallExpr,
     ) -> Result<VeltranoType, TypeCheckError> {
+        if method_call.method == OperatorMethod::Await.method_name() {
+            self.check_await_call(receiver_type, method_call)?;
+        }
+        if method_call.method == OperatorMethod::Deref.method_name() {
+            self.check_deref_call(receiver_type, method_call)?;
+        }
+
         // Only check built-ins if no imports exist for this method name
         if let Some(return_type) = self.builtin_registry.get_method_return_type(
             &method_call.method,
@@ -321,13 +520,18 @@ impl VeltranoTypeChecker {
         };
 
         // Check if the receiver type matches what the method expects
-        // based on the method's self_kind
+        // based on the method's self_kind, binding the generic parameters of
+        // the imported type (`Vec<$T>`) to the receiver's type arguments
+        let mut bindings = HashMap::new();
         let receiver_matches = if let Ok(import_veltrano_type) = rust_type.to_veltrano_type() {
             crate::debug_println!(
                 "DEBUG: Checking receiver match - rust_type: {:?} -> veltrano_type: {:?}",
                 rust_type,
                 import_veltrano_type
             );
+            let mut matches = |pattern: &VeltranoType, actual: &VeltranoType| {
+                bind_placeholders(pattern, actual, &mut bindings)
+            };
             match method_info.self_kind {
                 SelfKind::Value => {
                     // Method expects self - for Copy types, allow bare type; otherwise Own<T>
@@ -337,15 +541,17 @@ impl VeltranoTypeChecker {
                         .unwrap_or(false)
                     {
                         // Copy types can be used directly
-                        receiver_type == &import_veltrano_type
+                        matches(&import_veltrano_type, receiver_type)
                     } else {
                         // Non-Copy types must be wrapped in Own
                         // The import type might already be Own<T> from to_veltrano_type
                         if matches!(&import_veltrano_type.constructor, TypeConstructor::Own) {
-                            receiver_type == &import_veltrano_type
+                            matches(&import_veltrano_type, receiver_type)
                         } else {
                             matches!(&receiver_type.constructor, TypeConstructor::Own)
-                                && receiver_type.inner() == Some(&import_veltrano_type)
+                                && receiver_type
+                                    .inner()
+                                    .is_some_and(|inner| matches(&import_veltrano_type, inner))
                         }
                     }
                 }
@@ -358,15 +564,19 @@ impl VeltranoTypeChecker {
                     {
                         // Copy types need Ref<Self>
                         matches!(&receiver_type.constructor, TypeConstructor::Ref)
-                            && receiver_type.inner() == Some(&import_veltrano_type)
+                            && receiver_type
+                                .inner()
+                                .is_some_and(|inner| matches(&import_veltrano_type, inner))
                     } else {
                         // Non-Copy types use bare Self
                         // But to_veltrano_type may have wrapped it in Own, so check both
                         if matches!(&import_veltrano_type.constructor, TypeConstructor::Own) {
                             // If import type is Own<T>, extract T for comparison
-                            import_veltrano_type.inner() == Some(receiver_type)
+                            import_veltrano_type
+                                .inner()
+                                .is_some_and(|inner| matches(inner, receiver_type))
                         } else {
-                            receiver_type == &import_veltrano_type
+                            matches(&import_veltrano_type, receiver_type)
                         }
                     }
                 }
@@ -379,13 +589,15 @@ impl VeltranoTypeChecker {
                     {
                         // Copy types need MutRef<Self>
                         matches!(&receiver_type.constructor, TypeConstructor::MutRef)
-                            && receiver_type.inner() == Some(&import_veltrano_type)
+                            && receiver_type
+                                .inner()
+                                .is_some_and(|inner| matches(&import_veltrano_type, inner))
                     } else {
                         // Non-Copy types use MutRef<Own<Self>>
                         if matches!(&receiver_type.constructor, TypeConstructor::MutRef) {
                             if let Some(inner) = receiver_type.inner() {
                                 // inner should be Own<T> and import_veltrano_type should be Own<T>
-                                inner == &import_veltrano_type
+                                matches(&import_veltrano_type, inner)
                             } else {
                                 false
                             }
@@ -440,10 +652,174 @@ impl VeltranoTypeChecker {
 
         // Convert the Rust return type to Veltrano type
         if let Ok(veltrano_return_type) = method_info.return_type.to_veltrano_type() {
-            Ok(veltrano_return_type)
+            if bindings.is_empty() {
+                Ok(veltrano_return_type)
+            } else {
+                Ok(substitute_placeholders(
+                    &veltrano_return_type,
+                    &bindings,
+                    &mut self.trait_checker,
+                ))
+            }
         } else {
             // Return a reasonable default type if conversion fails
             Ok(receiver_type.clone())
         }
     }
+
+    /// Check the arguments of a resolved imported method; values passed where
+    /// the method takes a reference, like the key of `HashMap::get`, are borrowed
+    fn check_imported_method_args(
+        &mut self,
+        rust_type: &RustType,
+        rust_method_name: &str,
+        method_call: &MethodCallExpr,
+    ) -> Result<Vec<bool>, TypeCheckError> {
+        let parameters = match self
+            .trait_checker
+            .query_method_signature(rust_type, rust_method_name)
+        {
+            Ok(Some(method_info)) => method_info._parameters,
+            _ => Vec::new(),
+        };
+        let mut borrowed_args = Vec::new();
+        for (i, arg) in method_call.args.iter().enumerate() {
+            let arg_type = self.check_expression(arg)?;
+            let takes_reference = matches!(parameters.get(i), Some(RustType::Ref { .. }));
+            let is_reference = matches!(
+                arg_type.to_rust_type(&mut self.trait_checker),
+                RustType::Ref { .. } | RustType::MutRef { .. }
+            );
+            borrowed_args.push(takes_reference && !is_reference);
+        }
+        Ok(borrowed_args)
+    }
+
+    /// Reject a call resolved to an unsafe imported function or method unless
+    /// it is inside an `unsafe` block
+    pub(super) fn check_unsafe_call(
+        &mut self,
+        call_id: usize,
+        name: &str,
+        span: &Span,
+    ) -> Result<(), TypeCheckError> {
+        if self.in_unsafe_block {
+            return Ok(());
+        }
+        let Some(resolution) = self.method_resolutions.get(&call_id).cloned() else {
+            return Ok(());
+        };
+        let is_unsafe = matches!(
+            self.trait_checker
+                .query_method_signature(&resolution.rust_type, &resolution.method_name),
+            Ok(Some(method_info)) if method_info.is_unsafe
+        );
+        if is_unsafe {
+            return Err(TypeCheckError::UnsafeCallOutsideUnsafeBlock {
+                function: name.to_string(),
+                span: span.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that `.await()` is used inside a `suspend fun` on a value whose
+    /// Rust type implements Future with a known output
+    fn check_await_call(
+        &mut self,
+        receiver_type: &VeltranoType,
+        method_call: &MethodCallExpr,
+    ) -> Result<(), TypeCheckError> {
+        let invalid_await = |reason: &str| TypeCheckError::InvalidAwait {
+            reason: reason.to_string(),
+            location: method_call.object.span.start.clone(),
+        };
+
+        if !method_call.args.is_empty() {
+            return Err(invalid_await("await() takes no arguments"));
+        }
+        if !self
+            .function_contexts
+            .last()
+            .is_some_and(|context| context.is_async)
+        {
+            return Err(invalid_await("only allowed inside a suspend fun"));
+        }
+
+        let rust_type = receiver_type.to_rust_type(&mut self.trait_checker);
+        if !self
+            .trait_checker
+            .type_implements_trait(&rust_type, "Future")
+            .unwrap_or(false)
+        {
+            return Err(invalid_await(&format!(
+                "{} does not implement Future",
+                rust_type.to_rust_syntax()
+            )));
+        }
+        if receiver_type.constructor != TypeConstructor::Future {
+            return Err(invalid_await(&format!(
+                "the output type of {} is unknown",
+                rust_type.to_rust_syntax()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that `.deref()` reads through a Ref or MutRef whose pointee can be copied out
+    fn check_deref_call(
+        &mut self,
+        receiver_type: &VeltranoType,
+        method_call: &MethodCallExpr,
+    ) -> Result<(), TypeCheckError> {
+        let invalid_deref =
+            |reason: String, suggestion: Option<&str>| TypeCheckError::InvalidDeref {
+                reason,
+                suggestion: suggestion.map(str::to_string),
+                location: method_call.object.span.start.clone(),
+            };
+        let clone_suggestion = Some("Use .clone() to get an owned copy of the value instead");
+
+        if !method_call.args.is_empty() {
+            return Err(invalid_deref(
+                "deref() takes no arguments".to_string(),
+                None,
+            ));
+        }
+
+        let rust_type = receiver_type.to_rust_type(&mut self.trait_checker);
+        let movable = match &receiver_type.constructor {
+            TypeConstructor::Ref | TypeConstructor::MutRef => {
+                let Some(pointee) = receiver_type.inner() else {
+                    return Ok(());
+                };
+                // Shared references are always Copy, even when their pointee is not
+                matches!(
+                    pointee.to_rust_type(&mut self.trait_checker),
+                    RustType::Ref { .. }
+                ) || pointee.implements_copy(&mut self.trait_checker)
+            }
+            // Naturally referenced types would be moved out of their borrow
+            _ if matches!(rust_type, RustType::Ref { .. }) => false,
+            _ => {
+                return Err(invalid_deref(
+                    format!("{} is not a reference", rust_type.to_rust_syntax()),
+                    clone_suggestion.filter(|_| receiver_type.constructor == TypeConstructor::Own),
+                ))
+            }
+        };
+        match &rust_type {
+            RustType::Ref { inner, .. } | RustType::MutRef { inner, .. } if !movable => {
+                Err(invalid_deref(
+                    format!(
+                        "cannot move {} out of {} because it does not implement Copy",
+                        inner.to_rust_syntax(),
+                        rust_type.to_rust_syntax()
+                    ),
+                    clone_suggestion,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
 }
@@ -1,7 +1,17 @@
+mod annotations;
+mod arguments;
+mod borrows;
+mod cfg;
 pub mod error;
+mod escapes;
 mod expressions;
+mod ffi;
+mod function_values;
 mod imports;
+mod lifetimes;
 mod method_calls;
+mod moves;
+mod operators;
 mod statements;
 mod types;
 
@@ -11,8 +21,9 @@ use crate::error::VeltranoError;
 use crate::rust_interop::RustInteropRegistry;
 use crate::types::*;
 
-pub use error::{MethodResolution, TypeCheckError};
+pub use error::{MethodResolution, OperatorResolution, TypeCheckError};
 use imports::{register_builtin_imports, ImportHandler};
+use lifetimes::FunctionContext;
 
 /// Main type checker with strict type checking (no implicit conversions)
 pub struct VeltranoTypeChecker {
@@ -21,6 +32,13 @@ pub struct VeltranoTypeChecker {
     builtin_registry: BuiltinRegistry,
     import_handler: ImportHandler,
     method_resolutions: std::collections::HashMap<usize, MethodResolution>, // Maps method call IDs to their resolutions
+    node_types: std::collections::HashMap<usize, VeltranoType>, // Maps field access/destructuring/index IDs to their operand types
+    lifetime_bindings: std::collections::HashMap<usize, Vec<Option<String>>>, // Maps call IDs to the caller labels bound to each callee label
+    operator_resolutions: std::collections::HashMap<usize, OperatorResolution>, // Maps operator expression IDs to their `operator fun` resolutions
+    data_class_derives: std::collections::HashMap<String, Vec<String>>, // Maps data class names to their `#[derive]` lists
+    hidden_bump_functions: std::collections::HashSet<String>, // Top-level functions that take a hidden bump parameter
+    function_contexts: Vec<FunctionContext>,                  // Enclosing functions, innermost last
+    in_unsafe_block: bool, // Whether calls to unsafe Rust items are allowed
 }
 
 impl VeltranoTypeChecker {
@@ -37,6 +55,13 @@ impl VeltranoTypeChecker {
             builtin_registry: BuiltinRegistry::new(),
             import_handler,
             method_resolutions: std::collections::HashMap::new(),
+            node_types: std::collections::HashMap::new(),
+            lifetime_bindings: std::collections::HashMap::new(),
+            operator_resolutions: std::collections::HashMap::new(),
+            data_class_derives: std::collections::HashMap::new(),
+            hidden_bump_functions: std::collections::HashSet::new(),
+            function_contexts: Vec::new(),
+            in_unsafe_block: false,
         };
 
         // Initialize built-in functions and methods
@@ -49,6 +74,33 @@ impl VeltranoTypeChecker {
         &self.method_resolutions
     }
 
+    /// Get the operand types recorded for field accesses, destructurings and index expressions
+    pub fn get_node_types(&self) -> &std::collections::HashMap<usize, VeltranoType> {
+        &self.node_types
+    }
+
+    /// Get the caller lifetime bound to each lifetime parameter of labeled function calls
+    pub fn get_lifetime_bindings(&self) -> &std::collections::HashMap<usize, Vec<Option<String>>> {
+        &self.lifetime_bindings
+    }
+
+    /// Get the operator expressions resolved through `operator fun` members of data classes
+    pub fn get_operator_resolutions(
+        &self,
+    ) -> &std::collections::HashMap<usize, OperatorResolution> {
+        &self.operator_resolutions
+    }
+
+    /// Get the `#[derive]` list inferred for each data class
+    pub fn get_data_class_derives(&self) -> &std::collections::HashMap<String, Vec<String>> {
+        &self.data_class_derives
+    }
+
+    /// Get the top-level functions that receive a hidden bump parameter
+    pub fn get_hidden_bump_functions(&self) -> &std::collections::HashSet<String> {
+        &self.hidden_bump_functions
+    }
+
     fn init_builtin_functions(&mut self) {
         // Register built-in function signatures from the builtin registry
         let function_signatures = self.builtin_registry.get_function_signatures();
@@ -62,6 +114,23 @@ impl VeltranoTypeChecker {
     pub fn check_program(&mut self, program: &Program) -> Result<(), Vec<TypeCheckError>> {
         let mut errors = Vec::new();
 
+        // Derived traits decide whether data classes are Copy value types, which
+        // every signature mentioning them depends on
+        let data_classes: Vec<&DataClassStmt> = program
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Stmt::DataClass(data_class) => Some(data_class),
+                _ => None,
+            })
+            .collect();
+        self.data_class_derives = self
+            .trait_checker
+            .register_data_class_derives(&data_classes);
+        self.hidden_bump_functions = Self::hidden_bump_functions(program, &self.data_class_derives);
+
+        errors.extend(self.check_duplicate_definitions(program));
+
         // First pass: collect all function signatures (including nested ones)
         for statement in &program.statements {
             if let Err(error) = self.collect_function_signatures_from_statement(statement) {
@@ -95,4 +164,52 @@ impl VeltranoTypeChecker {
                 .collect()
         })
     }
+
+    /// Find the unlabeled top-level functions that allocate or hold references, and so
+    /// take a hidden bump parameter, given the `#[derive]` list of each data class
+    pub fn hidden_bump_functions(
+        program: &Program,
+        data_class_derives: &std::collections::HashMap<String, Vec<String>>,
+    ) -> std::collections::HashSet<String> {
+        // Copy data classes are passed by value, so they don't call for a bump
+        let value_classes: std::collections::HashSet<String> = data_class_derives
+            .iter()
+            .filter(|(_, derives)| derives.iter().any(|d| d == "Copy"))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let functions: Vec<&FunDeclStmt> = program
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Stmt::FunDecl(fun_decl) => Some(fun_decl),
+                _ => None,
+            })
+            .collect();
+
+        // Functions with lifetime labels get one bump parameter per label instead,
+        // but callers pass bumps to both kinds
+        let mut functions_with_bump: std::collections::HashSet<String> = functions
+            .iter()
+            .filter(|fun_decl| !fun_decl.lifetime_params.is_empty())
+            .map(|fun_decl| fun_decl.name.clone())
+            .collect();
+        let mut hidden_bump_functions = std::collections::HashSet::new();
+
+        // Keep iterating until no changes are made (to handle transitive dependencies)
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for fun_decl in &functions {
+                if fun_decl.lifetime_params.is_empty()
+                    && !hidden_bump_functions.contains(&fun_decl.name)
+                    && fun_decl.needs_lifetime_params(&functions_with_bump, &value_classes)
+                {
+                    hidden_bump_functions.insert(fun_decl.name.clone());
+                    functions_with_bump.insert(fun_decl.name.clone());
+                    changed = true;
+                }
+            }
+        }
+        hidden_bump_functions
+    }
 }
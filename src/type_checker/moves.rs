@@ -0,0 +1,216 @@
+//! Use-after-move checking for `Own<T>` values
+//!
+//! Passing an owned variable by value moves it; using it again afterwards is
+//! an error. Moves are tracked in statement order through the body of each
+//! function. A variable moved in either branch of an `if` counts as moved
+//! after it, unless that branch returns, and a variable declared outside a
+//! loop can't be moved inside it on a path that goes on to the next iteration.
+
+use super::{TypeCheckError, VeltranoTypeChecker};
+use crate::ast::{Expr, LocatedExpr};
+use crate::error::Span;
+use crate::types::{TypeConstructor, VeltranoType};
+use std::collections::HashMap;
+
+/// Variables moved so far, keyed by name and the depth of the scope declaring
+/// them, with the span of the move
+#[derive(Debug, Clone, Default)]
+pub(super) struct MoveState {
+    moved: HashMap<(String, usize), Span>,
+    returned: bool, // The path has returned, so its moves don't reach later statements
+}
+
+impl MoveState {
+    /// Moves made on either path count after the paths join, unless that path returned
+    fn merge(&mut self, other: MoveState) {
+        if other.returned {
+            return;
+        }
+        if self.returned {
+            *self = other;
+            return;
+        }
+        for (key, span) in other.moved {
+            self.moved.entry(key).or_insert(span);
+        }
+    }
+}
+
+impl VeltranoTypeChecker {
+    fn move_state(&mut self) -> Option<&mut MoveState> {
+        self.function_contexts
+            .last_mut()
+            .map(|context| &mut context.moves)
+    }
+
+    /// Check a use of a variable that may already have been moved; `consuming`
+    /// tells whether this use moves the value too
+    pub(super) fn check_not_moved(
+        &mut self,
+        name: &str,
+        span: &Span,
+        consuming: bool,
+    ) -> Result<(), TypeCheckError> {
+        let Some(depth) = self.env.variable_scope_depth(name) else {
+            return Ok(());
+        };
+        let Some(moved_at) = self
+            .move_state()
+            .and_then(|state| state.moved.get(&(name.to_string(), depth)))
+            .cloned()
+        else {
+            return Ok(());
+        };
+        // The same expression may be checked more than once
+        if moved_at == *span {
+            return Ok(());
+        }
+        let suggestion = if consuming {
+            format!(
+                "Call {}.clone() where it is first moved so each use gets its own value",
+                name
+            )
+        } else {
+            format!(
+                "Pass {}.ref() where it is moved if that use only needs to borrow it, or call {}.clone() there",
+                name, name
+            )
+        };
+        Err(TypeCheckError::UseAfterMove {
+            name: name.to_string(),
+            moved_at,
+            used_at: span.clone(),
+            in_loop: false,
+            suggestion,
+        })
+    }
+
+    /// Record that an `Own<T>` variable is moved by this use
+    pub(super) fn record_move(&mut self, name: &str, variable_type: &VeltranoType, span: &Span) {
+        if variable_type.constructor != TypeConstructor::Own {
+            return;
+        }
+        let Some(depth) = self.env.variable_scope_depth(name) else {
+            return;
+        };
+        if let Some(state) = self.move_state() {
+            state
+                .moved
+                .entry((name.to_string(), depth))
+                .or_insert_with(|| span.clone());
+        }
+    }
+
+    /// A new binding replaces any moved variable of the same name in its scope
+    pub(super) fn forget_move(&mut self, name: &str) {
+        let depth = self.env.scope_depth();
+        if let Some(state) = self.move_state() {
+            state.moved.remove(&(name.to_string(), depth));
+        }
+    }
+
+    /// Check a variable used by value, which moves it if it is owned
+    pub(super) fn check_moving_identifier(
+        &mut self,
+        name: &str,
+        span: &Span,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        let variable_type = self.check_identifier(name, span)?;
+        self.check_not_moved(name, span, true)?;
+        self.check_borrow_use(name, span)?;
+        self.record_move(name, &variable_type, span);
+        Ok(variable_type)
+    }
+
+    /// Check an expression used in place, such as the operand of `.ref()`;
+    /// a variable used this way is borrowed rather than moved
+    pub(super) fn check_place_expression(
+        &mut self,
+        expr: &LocatedExpr,
+    ) -> Result<VeltranoType, TypeCheckError> {
+        match &expr.node {
+            Expr::Identifier(name) => {
+                let variable_type = self.check_identifier(name, &expr.span)?;
+                self.check_not_moved(name, &expr.span, false)?;
+                self.check_borrow_use(name, &expr.span)?;
+                Ok(variable_type)
+            }
+            Expr::Parenthesized(paren_expr) => self.check_place_expression(&paren_expr.expr),
+            _ => self.check_expression(expr),
+        }
+    }
+
+    /// Record a move of a place expression whose value turned out to be consumed
+    pub(super) fn consume_place(&mut self, expr: &LocatedExpr, value_type: &VeltranoType) {
+        match &expr.node {
+            Expr::Identifier(name) => self.record_move(name, value_type, &expr.span),
+            Expr::Parenthesized(paren_expr) => self.consume_place(&paren_expr.expr, value_type),
+            _ => {}
+        }
+    }
+
+    /// Record that the current path returns
+    pub(super) fn record_return(&mut self) {
+        if let Some(state) = self.move_state() {
+            state.returned = true;
+        }
+    }
+
+    /// Current moves, to be restored for the other branch of an `if`
+    pub(super) fn save_moves(&mut self) -> MoveState {
+        self.move_state().cloned().unwrap_or_default()
+    }
+
+    /// Join the moves of one branch of an `if` with those of the other branch,
+    /// which are current
+    pub(super) fn join_moves(&mut self, branch: MoveState) {
+        if let Some(state) = self.move_state() {
+            state.merge(branch);
+        }
+    }
+
+    /// Replace the current moves, returning the ones they replace
+    pub(super) fn replace_moves(&mut self, moves: MoveState) -> MoveState {
+        match self.move_state() {
+            Some(state) => std::mem::replace(state, moves),
+            None => MoveState::default(),
+        }
+    }
+
+    /// A variable declared outside a loop and moved in its body would be used
+    /// again by the next iteration; a body that returns has none, and the loop
+    /// is left without running it
+    pub(super) fn check_loop_moves(
+        &mut self,
+        before: &MoveState,
+        loop_depth: usize,
+    ) -> Result<(), TypeCheckError> {
+        let Some(state) = self.move_state() else {
+            return Ok(());
+        };
+        if state.returned {
+            *state = before.clone();
+            return Ok(());
+        }
+        let mut moved_in_loop: Vec<_> = state
+            .moved
+            .iter()
+            .filter(|(key, _)| key.1 <= loop_depth && !before.moved.contains_key(*key))
+            .map(|((name, _), span)| (name.clone(), span.clone()))
+            .collect();
+        moved_in_loop.sort_by_key(|(_, span)| (span.start.line, span.start.column));
+        match moved_in_loop.into_iter().next() {
+            Some((name, span)) => Err(TypeCheckError::UseAfterMove {
+                suggestion: format!(
+                    "Call {}.clone() so each iteration moves its own value",
+                    name
+                ),
+                name,
+                moved_at: span.clone(),
+                used_at: span,
+                in_loop: true,
+            }),
+            None => Ok(()),
+        }
+    }
+}
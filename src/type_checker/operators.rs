@@ -0,0 +1,242 @@
+//! Operator overloading through `operator fun` members of data classes
+//!
+//! Validates operator function declarations and resolves binary, unary and
+//! index expressions whose operand is a data class to them. Binary operators
+//! on other non-primitive operands resolve to the std trait impls of their
+//! Rust types.
+
+use crate::ast::{BinaryExpr, BinaryOp, MemberFunction, UnaryExpr};
+use crate::types::{TypeConstructor, VeltranoType};
+
+use super::error::{OperatorResolution, TypeCheckError};
+use super::VeltranoTypeChecker;
+
+/// Functions that can be declared with `operator fun`, with their parameter counts
+const OPERATOR_FUNCTIONS: &[(&str, usize)] = &[
+    ("plus", 1),
+    ("minus", 1),
+    ("times", 1),
+    ("div", 1),
+    ("rem", 1),
+    ("unaryMinus", 0),
+    ("compareTo", 1),
+    ("equals", 1),
+    ("get", 1),
+];
+
+impl VeltranoTypeChecker {
+    /// Check that an `operator fun` overloads a known operator with a usable signature
+    pub(super) fn check_operator_function(
+        &self,
+        class_name: &str,
+        member: &MemberFunction,
+    ) -> Result<(), TypeCheckError> {
+        let fun_decl = &member.fun_decl;
+        let invalid = |reason: String| TypeCheckError::InvalidOperatorFunction {
+            class_name: class_name.to_string(),
+            function: fun_decl.name.clone(),
+            reason,
+            location: member.location.clone(),
+        };
+
+        let Some((_, arity)) = OPERATOR_FUNCTIONS
+            .iter()
+            .find(|(name, _)| *name == fun_decl.name)
+        else {
+            let names: Vec<&str> = OPERATOR_FUNCTIONS.iter().map(|(name, _)| *name).collect();
+            return Err(invalid(format!(
+                "not an overloadable operator; expected one of {}",
+                names.join(", ")
+            )));
+        };
+
+        if fun_decl.params.len() != *arity {
+            return Err(invalid(match arity {
+                0 => "must not take parameters".to_string(),
+                _ => format!("must take exactly {} parameter", arity),
+            }));
+        }
+
+        let Some(return_type) = &fun_decl.return_type else {
+            return Err(invalid("must declare a return type".to_string()));
+        };
+
+        // Comparisons implement PartialEq/PartialOrd, which compare values of the same class
+        let comparison_return = match fun_decl.name.as_str() {
+            "equals" => Some(VeltranoType::bool()),
+            "compareTo" => Some(VeltranoType::i64()),
+            _ => None,
+        };
+        if let Some(expected_return) = comparison_return {
+            let class_type = VeltranoType::custom(class_name.to_string());
+            if !self.types_equal(&fun_decl.params[0].param_type.node, &class_type)
+                || !self.types_equal(&return_type.node, &expected_return)
+            {
+                return Err(invalid(format!(
+                    "must take a {} and return {:?}",
+                    class_name, expected_return
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a binary expression whose left operand is a data class through
+    /// its operator function; `None` leaves the expression to the built-in rules
+    pub(super) fn resolve_binary_operator(
+        &mut self,
+        binary: &BinaryExpr,
+        left_type: &VeltranoType,
+        right_type: &VeltranoType,
+    ) -> Result<Option<VeltranoType>, TypeCheckError> {
+        let (Some(class_name), Some(function)) = (
+            left_type.data_class_name(),
+            binary.operator.operator_function_name(),
+        ) else {
+            return Ok(None);
+        };
+
+        let Some(signature) = self
+            .env
+            .lookup_data_class(class_name)
+            .and_then(|data_class| data_class.operator(function))
+            .cloned()
+        else {
+            return match binary.operator {
+                // Equality keeps comparing values of the same type
+                BinaryOp::Equal | BinaryOp::NotEqual => Ok(None),
+                _ => Err(TypeCheckError::BinaryOperatorNotSupported {
+                    operator: binary.operator.clone(),
+                    left_type: left_type.clone(),
+                    right_type: right_type.clone(),
+                    location: binary.left.span.start.clone(),
+                }),
+            };
+        };
+
+        let parameter_type = &signature.parameters[0];
+        if !self.accepts_operand(parameter_type, right_type) {
+            return Err(TypeCheckError::TypeMismatch {
+                expected: parameter_type.clone(),
+                actual: right_type.clone(),
+                location: binary.right.span.start.clone(),
+            });
+        }
+
+        let left_owned = left_type.constructor == TypeConstructor::Own;
+        let right_owned = right_type.constructor == TypeConstructor::Own;
+        let (resolution, result_type) = match function {
+            // PartialEq/PartialOrd compare both operands at the same reference level
+            "equals" | "compareTo" => (
+                OperatorResolution {
+                    borrow_left: left_owned && !right_owned,
+                    borrow_right: right_owned && !left_owned,
+                },
+                VeltranoType::bool(),
+            ),
+            // Arithmetic is implemented for borrowed operands
+            _ => (
+                OperatorResolution {
+                    borrow_left: left_owned,
+                    borrow_right: right_owned
+                        && matches!(parameter_type.constructor, TypeConstructor::Custom(_)),
+                },
+                signature.return_type,
+            ),
+        };
+        self.operator_resolutions.insert(binary.id, resolution);
+
+        Ok(Some(result_type))
+    }
+
+    /// Resolve a binary expression through the `std::ops`/`std::cmp` impls of
+    /// its operands' Rust types, recording the borrows the impl needs
+    pub(super) fn resolve_std_operator(
+        &mut self,
+        binary: &BinaryExpr,
+        left_type: &VeltranoType,
+        right_type: &VeltranoType,
+    ) -> Option<VeltranoType> {
+        let trait_name = binary.operator.std_trait_name()?;
+        let left = left_type.to_rust_type(&mut self.trait_checker);
+        let right = right_type.to_rust_type(&mut self.trait_checker);
+        let found = self
+            .trait_checker
+            .resolve_operator_impl(trait_name, &left, &right)?;
+        let output = found.output.to_veltrano_type().ok()?;
+
+        self.operator_resolutions.insert(
+            binary.id,
+            OperatorResolution {
+                borrow_left: found.borrow_left,
+                borrow_right: found.borrow_right,
+            },
+        );
+        Some(output)
+    }
+
+    /// Record moves of owned operands that an arithmetic operator takes by value
+    pub(super) fn consume_binary_operands(
+        &mut self,
+        binary: &BinaryExpr,
+        left_type: &VeltranoType,
+        right_type: &VeltranoType,
+    ) {
+        // Comparisons always borrow their operands
+        if matches!(
+            binary.operator.operator_function_name(),
+            Some("equals" | "compareTo") | None
+        ) {
+            return;
+        }
+        let Some(resolution) = self.operator_resolutions.get(&binary.id).cloned() else {
+            return;
+        };
+        if !resolution.borrow_left && !left_type.implements_copy(&mut self.trait_checker) {
+            self.consume_place(&binary.left, left_type);
+        }
+        if !resolution.borrow_right && !right_type.implements_copy(&mut self.trait_checker) {
+            self.consume_place(&binary.right, right_type);
+        }
+    }
+
+    /// Resolve a unary expression on a data class through its operator function
+    pub(super) fn resolve_unary_operator(
+        &mut self,
+        unary: &UnaryExpr,
+        operand_type: &VeltranoType,
+    ) -> Option<VeltranoType> {
+        let signature = self
+            .env
+            .lookup_data_class(operand_type.data_class_name()?)?
+            .operator(unary.operator.operator_function_name())?
+            .clone();
+
+        self.operator_resolutions.insert(
+            unary.id,
+            OperatorResolution {
+                borrow_left: operand_type.constructor == TypeConstructor::Own,
+                borrow_right: false,
+            },
+        );
+        Some(signature.return_type)
+    }
+
+    /// Whether an operand can be passed for an operator parameter; owned data
+    /// class values are borrowed for parameters taking the class by reference
+    pub(super) fn accepts_operand(
+        &self,
+        parameter_type: &VeltranoType,
+        operand_type: &VeltranoType,
+    ) -> bool {
+        if self.types_equal(parameter_type, operand_type) {
+            return true;
+        }
+        matches!(parameter_type.constructor, TypeConstructor::Custom(_))
+            && operand_type.constructor == TypeConstructor::Own
+            && operand_type
+                .inner()
+                .is_some_and(|inner| self.types_equal(parameter_type, inner))
+    }
+}
@@ -6,18 +6,31 @@
 
 use crate::ast::query::AstQuery;
 use crate::ast::*;
+use crate::builtins::OperatorMethod;
 use crate::error::SourceLocation;
-use crate::types::{DataClassDefinition, DataClassFieldSignature, FunctionSignature, VeltranoType};
+use crate::types::{
+    DataClassDefinition, DataClassFieldSignature, DataClassMethodSignature, FunctionSignature,
+    TypeConstructor, VeltranoType,
+};
 
+use super::annotations::{invalid_annotation, AnnotationTarget};
+use super::borrows::BorrowState;
 use super::error::TypeCheckError;
-use super::types::TypeValidator;
+use super::escapes::ReferenceOrigins;
+use super::lifetimes::{with_default_lifetime, FunctionContext};
+use super::moves::MoveState;
+use super::types::{validate_binding_type, TypeValidator};
 use super::VeltranoTypeChecker;
 
 impl VeltranoTypeChecker {
     /// Check a statement for type correctness
     pub(super) fn check_statement(&mut self, stmt: &Stmt) -> Result<(), TypeCheckError> {
+        // Borrows not bound to a variable end with the statement making them
+        self.release_temporary_borrows();
         match stmt {
             Stmt::VarDecl(var_decl) => self.check_var_declaration(var_decl),
+            Stmt::Destructure(destructure) => self.check_destructuring_declaration(destructure),
+            Stmt::Assign(assign) => self.check_assignment(assign),
             Stmt::FunDecl(fun_decl) => self.check_function_declaration(fun_decl),
             Stmt::Expression(expr) => {
                 self.check_expression(expr)?;
@@ -25,22 +38,33 @@ impl VeltranoTypeChecker {
             }
             Stmt::Return(expr_opt) => {
                 if let Some(expr) = expr_opt {
-                    self.check_expression(expr)?;
+                    let return_type = self.check_expression(expr)?;
+                    self.check_return_lifetimes(&return_type, &expr.span.start)?;
+                    self.check_returned_reference(expr)?;
+                    self.check_stored_function_value(expr, "returned")?;
                 }
+                self.record_return();
                 Ok(())
             }
             Stmt::If(if_stmt) => {
                 self.check_expression(&if_stmt.condition)?;
+                let before_branches = self.save_moves();
                 self.check_statement(&if_stmt.then_branch)?;
+                let then_moves = self.replace_moves(before_branches);
                 if let Some(else_branch) = &if_stmt.else_branch {
                     self.check_statement(else_branch)?;
                 }
+                self.join_moves(then_moves);
                 Ok(())
             }
             Stmt::While(while_stmt) => {
+                let before_loop = self.save_moves();
+                let loop_depth = self.env.scope_depth();
+                let loop_borrows = self.start_loop_borrows();
                 self.check_expression(&while_stmt.condition)?;
                 self.check_statement(&while_stmt.body)?;
-                Ok(())
+                self.check_loop_borrows(loop_borrows)?;
+                self.check_loop_moves(&before_loop, loop_depth)
             }
             Stmt::Block(statements) => {
                 self.env.enter_scope();
@@ -48,14 +72,35 @@ impl VeltranoTypeChecker {
                     self.check_statement(stmt)?;
                 }
                 self.env.exit_scope();
+                self.release_scope_borrows(self.env.scope_depth());
                 Ok(())
             }
+            Stmt::Unsafe(statements) => {
+                let was_unsafe = std::mem::replace(&mut self.in_unsafe_block, true);
+                self.env.enter_scope();
+                let result = statements
+                    .iter()
+                    .try_for_each(|stmt| self.check_statement(stmt));
+                self.env.exit_scope();
+                self.release_scope_borrows(self.env.scope_depth());
+                self.in_unsafe_block = was_unsafe;
+                result
+            }
             Stmt::DataClass(data_class) => self.check_data_class_declaration(data_class),
             Stmt::Import(import) => self.check_import_statement(import),
+            Stmt::ExternFun(extern_fun) => self.check_extern_function(extern_fun),
+            Stmt::Annotated(annotated) => {
+                self.check_annotations(&annotated.annotations, AnnotationTarget::Statement)?;
+                self.check_statement(&annotated.stmt)
+            }
             Stmt::Comment(_) => {
                 // Comments don't need type checking
                 Ok(())
             }
+            Stmt::RustBlock(_) => {
+                // Embedded Rust code is opaque to the type checker
+                Ok(())
+            }
         }
     }
 
@@ -73,30 +118,254 @@ impl VeltranoTypeChecker {
         &mut self,
         data_class: &DataClassStmt,
     ) -> Result<(), TypeCheckError> {
+        self.check_annotations(&data_class.annotations, AnnotationTarget::DataClass)?;
+
         // Validate all field types
         for field in &data_class.fields {
+            self.check_annotations(&field.annotations, AnnotationTarget::Field)?;
             self.validate_type(&field.field_type.node, field.field_type.span.start.clone())?;
+            validate_binding_type(&field.field_type.node, &field.field_type.span.start)?;
+            self.check_lifetime_labels(
+                &field.field_type.node,
+                &data_class.lifetime_params,
+                &field.field_type.span.start,
+            )?;
+            if let Some(default_value) = &field.default_value {
+                self.check_default_value(default_value, &field.field_type.node)?;
+            }
         }
 
-        // Create data class definition
+        // Create data class definition; field labels only have meaning inside the class
         let fields: Vec<DataClassFieldSignature> = data_class
             .fields
             .iter()
             .map(|f| DataClassFieldSignature {
                 name: f.name.clone(),
-                field_type: f.field_type.node.clone(),
+                field_type: f.field_type.node.map_lifetimes(&|_| None),
+                has_default: f.default_value.is_some(),
             })
             .collect();
 
+        // Member signatures are registered before any body is checked, so members can call each other
+        let mut methods = Vec::new();
+        for member in &data_class.methods {
+            let fun_decl = &member.fun_decl;
+            self.check_annotations(&fun_decl.annotations, AnnotationTarget::MemberFunction)?;
+            if !fun_decl.lifetime_params.is_empty() {
+                return Err(TypeCheckError::UnsupportedFeature {
+                    feature: "lifetime parameters on member functions".to_string(),
+                    location: member.location.clone(),
+                });
+            }
+            self.check_function_signature_types(fun_decl, &data_class.lifetime_params)?;
+            if member.is_operator {
+                self.check_operator_function(&data_class.name, member)?;
+            }
+            if member.is_override {
+                self.check_override_function(&data_class.name, member)?;
+            }
+            methods.push(DataClassMethodSignature {
+                signature: Self::function_signature(fun_decl),
+                is_operator: member.is_operator,
+            });
+        }
+
         let definition = DataClassDefinition {
             _name: data_class.name.clone(),
             fields,
+            methods,
         };
 
         // Register the data class in the environment
         self.env
             .declare_data_class(data_class.name.clone(), definition);
 
+        // Member bodies see the borrowed receiver as `this`
+        let receiver_type = VeltranoType::custom(data_class.name.clone());
+        for member in &data_class.methods {
+            self.check_function_body(
+                &member.fun_decl,
+                Some(&receiver_type),
+                &data_class.lifetime_params,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Check that an `override fun` replaces a generated implementation; only
+    /// `toString(): Own<String>`, which implements `Display`, can be overridden
+    fn check_override_function(
+        &self,
+        class_name: &str,
+        member: &MemberFunction,
+    ) -> Result<(), TypeCheckError> {
+        let fun_decl = &member.fun_decl;
+        let invalid = |reason: &str| TypeCheckError::InvalidOverride {
+            class_name: class_name.to_string(),
+            function: fun_decl.name.clone(),
+            reason: reason.to_string(),
+            location: member.location.clone(),
+        };
+
+        if fun_decl.name != "toString" {
+            return Err(invalid("only toString can be overridden"));
+        }
+        let returns_string = fun_decl.return_type.as_ref().is_some_and(|return_type| {
+            self.types_equal(
+                &return_type.node,
+                &VeltranoType::own(VeltranoType::string()),
+            )
+        });
+        if !fun_decl.params.is_empty() || !returns_string {
+            return Err(invalid("must take no parameters and return Own<String>"));
+        }
+        Ok(())
+    }
+
+    /// Check destructuring declaration: `val (a, b) = expr`
+    pub(super) fn check_destructuring_declaration(
+        &mut self,
+        destructure: &DestructureStmt,
+    ) -> Result<(), TypeCheckError> {
+        let initializer = &destructure.initializer;
+        let init_type = self.check_place_expression(initializer)?;
+        self.node_types.insert(destructure.id, init_type.clone());
+
+        // Component types, and whether they are reached through a reference
+        let (components, borrowed) = match &init_type.constructor {
+            TypeConstructor::Tuple => (init_type.args.clone(), false),
+            TypeConstructor::Ref => match init_type.inner() {
+                Some(inner) if matches!(inner.constructor, TypeConstructor::Tuple) => {
+                    (inner.args.clone(), true)
+                }
+                _ => return Err(self.not_destructurable(&init_type, initializer)),
+            },
+            TypeConstructor::Custom(name) => (
+                self.data_class_field_types(name, &init_type, initializer)?,
+                true,
+            ),
+            TypeConstructor::Own => match init_type.inner().map(|inner| &inner.constructor) {
+                Some(TypeConstructor::Custom(name)) => (
+                    self.data_class_field_types(name, &init_type, initializer)?,
+                    false,
+                ),
+                _ => return Err(self.not_destructurable(&init_type, initializer)),
+            },
+            _ => return Err(self.not_destructurable(&init_type, initializer)),
+        };
+
+        if destructure.names.len() > components.len() {
+            return Err(TypeCheckError::InvalidDestructuring {
+                target_type: init_type,
+                reason: format!(
+                    "declares {} variables but the value has only {} components",
+                    destructure.names.len(),
+                    components.len()
+                ),
+                location: initializer.span.start.clone(),
+            });
+        }
+
+        // Binding a field that can't be copied moves the value
+        let moves_value = !borrowed
+            && destructure
+                .names
+                .iter()
+                .zip(&components)
+                .any(|(name, component)| {
+                    name != "_" && !component.implements_copy(&mut self.trait_checker)
+                });
+        if moves_value {
+            self.consume_place(initializer, &init_type);
+        }
+
+        for (name, component) in destructure.names.iter().zip(components) {
+            if name == "_" {
+                continue;
+            }
+            // Components reached through a reference are borrowed unless they can be copied
+            let variable_type = if borrowed {
+                component
+                    .borrowed_component_type(&mut self.trait_checker)
+                    .unwrap_or(component)
+            } else {
+                component
+            };
+            self.forget_move(name);
+            self.env.declare_variable(name.clone(), variable_type);
+        }
+
+        Ok(())
+    }
+
+    /// Field types of a data class in declaration order, for destructuring
+    fn data_class_field_types(
+        &self,
+        name: &str,
+        init_type: &VeltranoType,
+        initializer: &LocatedExpr,
+    ) -> Result<Vec<VeltranoType>, TypeCheckError> {
+        self.env
+            .lookup_data_class(name)
+            .map(|data_class| {
+                data_class
+                    .fields
+                    .iter()
+                    .map(|field| field.field_type.clone())
+                    .collect()
+            })
+            .ok_or_else(|| self.not_destructurable(init_type, initializer))
+    }
+
+    fn not_destructurable(
+        &self,
+        init_type: &VeltranoType,
+        initializer: &LocatedExpr,
+    ) -> TypeCheckError {
+        TypeCheckError::InvalidDestructuring {
+            target_type: init_type.clone(),
+            reason: "only tuples and data classes have components".to_string(),
+            location: initializer.span.start.clone(),
+        }
+    }
+
+    /// Check assignment statement: only `target.deref() = value` through a MutRef is allowed
+    pub(super) fn check_assignment(&mut self, assign: &AssignStmt) -> Result<(), TypeCheckError> {
+        let invalid = |reason: &str| TypeCheckError::InvalidAssignment {
+            reason: reason.to_string(),
+            location: assign.target.span.start.clone(),
+        };
+        let Expr::MethodCall(method_call) = &assign.target.node else {
+            return Err(invalid("only `.deref()` of a MutRef can be assigned to"));
+        };
+        if method_call.method != OperatorMethod::Deref.method_name() || !method_call.args.is_empty()
+        {
+            return Err(invalid("only `.deref()` of a MutRef can be assigned to"));
+        }
+
+        // Writing does not move out of the reference, so the pointee need not be Copy
+        let reference_type = self.check_expression(&method_call.object)?;
+        let target_type = match (&reference_type.constructor, reference_type.inner()) {
+            (TypeConstructor::MutRef, Some(inner)) => inner.clone(),
+            _ => {
+                let rust_type = reference_type.to_rust_type(&mut self.trait_checker);
+                return Err(invalid(&format!(
+                    "cannot write through {}, which is not a mutable reference",
+                    rust_type.to_rust_syntax()
+                )));
+            }
+        };
+
+        let value_type =
+            self.check_expression_with_expected_type(&assign.value, Some(&target_type))?;
+        if !self.is_assignable(&target_type, &value_type) {
+            return Err(TypeCheckError::TypeMismatch {
+                expected: target_type,
+                actual: value_type,
+                location: assign.value.span.start.clone(),
+            });
+        }
         Ok(())
     }
 
@@ -108,6 +377,12 @@ impl VeltranoTypeChecker {
         // Validate type annotation if present
         if let Some(declared_type) = &var_decl.type_annotation {
             self.validate_type(&declared_type.node, declared_type.span.start.clone())?;
+            validate_binding_type(&declared_type.node, &declared_type.span.start)?;
+            self.check_lifetime_labels(
+                &declared_type.node,
+                &self.labels_in_scope(),
+                &declared_type.span.start,
+            )?;
         }
 
         if let Some(initializer) = &var_decl.initializer {
@@ -122,16 +397,34 @@ impl VeltranoTypeChecker {
                 let expected_type = declared_type.node.clone();
 
                 // Strict type checking: types must match exactly
-                if !self.types_equal(&expected_type, &init_type) {
+                if !self.is_assignable(&expected_type, &init_type) {
                     return Err(TypeCheckError::TypeMismatch {
                         expected: expected_type,
                         actual: init_type,
                         location: initializer.span.start.clone(),
                     });
                 }
+                self.check_lifetime_compatibility(
+                    &expected_type,
+                    &init_type,
+                    &initializer.span.start,
+                )?;
+
+                // The declared type keeps its lifetime labels
+                self.forget_move(&var_decl.name);
+                self.record_reference_origin(&var_decl.name, initializer);
+                self.record_bump_closure(&var_decl.name, initializer);
+                self.bind_borrow(&var_decl.name, initializer);
+                self.env
+                    .declare_variable(var_decl.name.clone(), expected_type);
+                return Ok(());
             }
 
             // Declare the variable in the environment
+            self.forget_move(&var_decl.name);
+            self.record_reference_origin(&var_decl.name, initializer);
+            self.record_bump_closure(&var_decl.name, initializer);
+            self.bind_borrow(&var_decl.name, initializer);
             self.env.declare_variable(var_decl.name.clone(), init_type);
         }
 
@@ -143,6 +436,18 @@ impl VeltranoTypeChecker {
         &mut self,
         stmt: &Stmt,
     ) -> Result<(), TypeCheckError> {
+        // Foreign functions can be called before their declaration
+        let declared = match stmt {
+            Stmt::Annotated(annotated) => &*annotated.stmt,
+            _ => stmt,
+        };
+        if let Stmt::ExternFun(extern_fun) = declared {
+            self.env.declare_function(
+                extern_fun.name.clone(),
+                Self::extern_function_signature(extern_fun),
+            );
+        }
+
         // Use the query infrastructure to find all function declarations
         let function_decls = AstQuery::find_function_decls(stmt);
 
@@ -159,38 +464,84 @@ impl VeltranoTypeChecker {
         &mut self,
         fun_decl: &FunDeclStmt,
     ) -> Result<(), TypeCheckError> {
+        self.check_function_signature_types(fun_decl, &[])?;
+
+        // Create function signature and add to environment
+        let signature = Self::function_signature(fun_decl);
+
+        self.env.declare_function(fun_decl.name.clone(), signature);
+
+        Ok(())
+    }
+
+    /// Validate parameter and return types of a function; `outer_labels` are
+    /// lifetime labels declared by the enclosing data class
+    fn check_function_signature_types(
+        &mut self,
+        fun_decl: &FunDeclStmt,
+        outer_labels: &[String],
+    ) -> Result<(), TypeCheckError> {
+        let labels: Vec<String> = outer_labels
+            .iter()
+            .chain(&fun_decl.lifetime_params)
+            .cloned()
+            .collect();
+
         // Validate parameter types
         for param in &fun_decl.params {
             self.validate_type(&param.param_type.node, param.param_type.span.start.clone())?;
+            self.check_signature_lifetimes(
+                &param.param_type.node,
+                &fun_decl.name,
+                &labels,
+                &param.param_type.span.start,
+            )?;
         }
 
         // Validate return type if present
         if let Some(return_type) = &fun_decl.return_type {
             self.validate_type(&return_type.node, return_type.span.start.clone())?;
+            self.check_signature_lifetimes(
+                &return_type.node,
+                &fun_decl.name,
+                &labels,
+                &return_type.span.start,
+            )?;
         }
 
-        // Create function signature and add to environment
+        Ok(())
+    }
+
+    /// Signature of a declared function; unlabeled references in the
+    /// signature of a labeled function take its first lifetime label
+    fn function_signature(fun_decl: &FunDeclStmt) -> FunctionSignature {
+        let default_lifetime = fun_decl.lifetime_params.first();
         let param_types: Vec<VeltranoType> = fun_decl
             .params
             .iter()
-            .map(|p| p.param_type.node.clone())
+            .map(|p| with_default_lifetime(p.param_type.node.clone(), default_lifetime))
             .collect();
 
         let return_type = fun_decl
             .return_type
             .as_ref()
-            .map(|t| t.node.clone())
+            .map(|t| with_default_lifetime(t.node.clone(), default_lifetime))
             .unwrap_or_else(|| VeltranoType::unit());
 
-        let signature = FunctionSignature {
+        FunctionSignature {
             name: fun_decl.name.clone(),
             parameters: param_types,
             return_type,
-        };
-
-        self.env.declare_function(fun_decl.name.clone(), signature);
-
-        Ok(())
+            lifetime_params: fun_decl.lifetime_params.clone(),
+            parameter_names: fun_decl.params.iter().map(|p| p.name.clone()).collect(),
+            parameter_defaults: fun_decl
+                .params
+                .iter()
+                .map(|p| p.default_value.is_some())
+                .collect(),
+            is_async: fun_decl.is_async,
+            is_unsafe: false,
+        }
     }
 
     /// Check function declaration
@@ -199,24 +550,112 @@ impl VeltranoTypeChecker {
         fun_decl: &FunDeclStmt,
     ) -> Result<(), TypeCheckError> {
         // Function signature already collected in first pass, just check the body
+        self.check_annotations(&fun_decl.annotations, AnnotationTarget::Function)?;
+        self.check_c_export_function(fun_decl)?;
+        self.check_test_function(fun_decl)?;
+        self.check_function_body(fun_decl, None, &[])
+    }
 
-        // Check function body
-        self.env.enter_scope();
+    /// Check that a `@Test` function can be run by the Rust test harness
+    fn check_test_function(&self, fun_decl: &FunDeclStmt) -> Result<(), TypeCheckError> {
+        let Some(annotation) = fun_decl.annotations.iter().find(|a| a.name == "Test") else {
+            return Ok(());
+        };
+
+        let returns_value = fun_decl
+            .return_type
+            .as_ref()
+            .is_some_and(|t| t.node.constructor != TypeConstructor::Unit);
+        let reason = if !self.function_contexts.is_empty() {
+            "test functions must be declared at the top level"
+        } else if !fun_decl.params.is_empty() {
+            "test functions cannot take parameters"
+        } else if returns_value {
+            "test functions cannot return a value"
+        } else if !fun_decl.lifetime_params.is_empty() {
+            "test functions cannot have lifetime parameters"
+        } else if fun_decl.is_async {
+            "test functions cannot be suspend functions"
+        } else {
+            return Ok(());
+        };
+        Err(invalid_annotation(annotation, reason))
+    }
 
-        // Add parameters to scope
+    /// Check the default values and body of a function; member functions
+    /// have a `receiver_type` bound to `this`
+    fn check_function_body(
+        &mut self,
+        fun_decl: &FunDeclStmt,
+        receiver_type: Option<&VeltranoType>,
+        outer_labels: &[String],
+    ) -> Result<(), TypeCheckError> {
+        // Default values are evaluated at call sites, outside the function's scope
         for param in &fun_decl.params {
-            self.env
-                .declare_variable(param.name.clone(), param.param_type.node.clone());
+            if let Some(default_value) = &param.default_value {
+                self.check_default_value(default_value, &param.param_type.node)?;
+            }
         }
 
-        // First collect any nested function signatures within the body
-        self.collect_function_signatures_from_statement(&fun_decl.body)?;
+        // Check function body
+        self.env.enter_scope();
 
-        // Then check the body
-        self.check_statement(&fun_decl.body)?;
+        // Add the receiver and parameters to scope
+        if let Some(receiver_type) = receiver_type {
+            self.env
+                .declare_variable("this".to_string(), receiver_type.clone());
+        }
+        let signature = Self::function_signature(fun_decl);
+        for (param, param_type) in fun_decl.params.iter().zip(&signature.parameters) {
+            self.env
+                .declare_variable(param.name.clone(), param_type.clone());
+        }
 
+        self.function_contexts.push(FunctionContext {
+            name: fun_decl.name.clone(),
+            lifetime_params: outer_labels
+                .iter()
+                .chain(&fun_decl.lifetime_params)
+                .cloned()
+                .collect(),
+            return_type: signature.return_type,
+            is_async: signature.is_async,
+            moves: MoveState::default(),
+            reference_origins: ReferenceOrigins::default(),
+            borrows: BorrowState::default(),
+            bump_closures: std::collections::HashMap::new(),
+        });
+
+        // Nested functions do not inherit an enclosing `unsafe` block
+        let was_unsafe = std::mem::replace(&mut self.in_unsafe_block, false);
+
+        // First collect any nested function signatures within the body, then check it
+        let result = self
+            .collect_function_signatures_from_statement(&fun_decl.body)
+            .and_then(|_| self.check_statement(&fun_decl.body));
+
+        self.in_unsafe_block = was_unsafe;
+        self.function_contexts.pop();
         self.env.exit_scope();
 
+        result
+    }
+
+    /// Check that a default value has the type of its parameter or field
+    fn check_default_value(
+        &mut self,
+        default_value: &LocatedExpr,
+        expected_type: &VeltranoType,
+    ) -> Result<(), TypeCheckError> {
+        let actual_type =
+            self.check_expression_with_expected_type(default_value, Some(expected_type))?;
+        if !self.is_assignable(expected_type, &actual_type) {
+            return Err(TypeCheckError::TypeMismatch {
+                expected: expected_type.clone(),
+                actual: actual_type,
+                location: default_value.span.start.clone(),
+            });
+        }
         Ok(())
     }
 
@@ -233,4 +672,9 @@ impl VeltranoTypeChecker {
     pub(super) fn types_equal(&self, a: &VeltranoType, b: &VeltranoType) -> bool {
         TypeValidator::types_equal(a, b)
     }
+
+    /// Type equality, plus coercion of implementing types to trait bound types
+    pub(super) fn is_assignable(&mut self, expected: &VeltranoType, actual: &VeltranoType) -> bool {
+        TypeValidator::is_assignable(expected, actual, &mut self.trait_checker)
+    }
 }
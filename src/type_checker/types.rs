@@ -4,8 +4,9 @@
 //! for working with VeltranoType in the type checking context.
 
 use crate::error::SourceLocation;
-use crate::rust_interop::RustInteropRegistry;
+use crate::rust_interop::{RustInteropRegistry, RustType};
 use crate::types::{TypeConstructor, VeltranoType};
+use std::collections::HashMap;
 
 use super::error::TypeCheckError;
 
@@ -40,6 +41,14 @@ impl TypeValidator {
                     });
                 }
             }
+            TypeConstructor::Dyn(trait_name) | TypeConstructor::Impl(trait_name)
+                if !trait_checker.trait_exists(trait_name) =>
+            {
+                return Err(TypeCheckError::InvalidTypeConstructor {
+                    message: format!("Unknown trait '{}' in a trait bound type", trait_name),
+                    location,
+                });
+            }
             _ => {
                 // For other type constructors, recursively validate type arguments
                 for arg in &veltrano_type.args {
@@ -54,6 +63,92 @@ impl TypeValidator {
     pub fn types_equal(a: &VeltranoType, b: &VeltranoType) -> bool {
         a == b // Simple structural equality
     }
+
+    /// Check if a value of type `actual` can be used where `expected` is required:
+    /// equal types, or a value whose type implements the trait of a trait bound
+    /// type in the same position (`Dyn<Display>` accepts `String` and `Ref<I64>`)
+    pub fn is_assignable(
+        expected: &VeltranoType,
+        actual: &VeltranoType,
+        trait_checker: &mut RustInteropRegistry,
+    ) -> bool {
+        if Self::types_equal(expected, actual) {
+            return true;
+        }
+        match &expected.constructor {
+            // &dyn Trait accepts any reference to a sized implementing type
+            TypeConstructor::Dyn(trait_name) => match actual.to_rust_type(trait_checker) {
+                RustType::Ref { inner, .. }
+                    if !matches!(
+                        *inner,
+                        RustType::Str | RustType::Slice { .. } | RustType::Dyn { .. }
+                    ) =>
+                {
+                    trait_checker
+                        .type_implements_trait(&inner, trait_name)
+                        .unwrap_or(false)
+                }
+                _ => false,
+            },
+            TypeConstructor::Impl(trait_name) => {
+                let rust_type = actual.to_rust_type(trait_checker);
+                trait_checker
+                    .type_implements_trait(&rust_type, trait_name)
+                    .unwrap_or(false)
+            }
+            // Box<dyn Trait>, Rc<dyn Trait> and Arc<dyn Trait> accept any implementing pointee
+            TypeConstructor::Box | TypeConstructor::Rc | TypeConstructor::Arc
+                if actual.constructor == expected.constructor =>
+            {
+                match (expected.inner(), actual.inner()) {
+                    (Some(expected_inner), Some(actual_inner)) => match &expected_inner.constructor
+                    {
+                        TypeConstructor::Dyn(trait_name) => {
+                            let pointee = actual_inner.to_rust_type(trait_checker);
+                            trait_checker
+                                .type_implements_trait(&pointee, trait_name)
+                                .unwrap_or(false)
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                }
+            }
+            // Ownership wrappers and collections coerce element-wise
+            TypeConstructor::Own | TypeConstructor::Vec | TypeConstructor::Option
+                if actual.constructor == expected.constructor =>
+            {
+                match (expected.inner(), actual.inner()) {
+                    (Some(expected_inner), Some(actual_inner)) => {
+                        Self::is_assignable(expected_inner, actual_inner, trait_checker)
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Check that a type can be written in a `val` or a data class field; `impl
+/// Trait` is only allowed in function parameter and return types
+pub fn validate_binding_type(
+    veltrano_type: &VeltranoType,
+    location: &SourceLocation,
+) -> Result<(), TypeCheckError> {
+    if let TypeConstructor::Impl(trait_name) = &veltrano_type.constructor {
+        return Err(TypeCheckError::InvalidTypeConstructor {
+            message: format!(
+                "Impl<{}> is only allowed in function parameter and return types",
+                trait_name
+            ),
+            location: location.clone(),
+        });
+    }
+    veltrano_type
+        .args
+        .iter()
+        .try_for_each(|arg| validate_binding_type(arg, location))
 }
 
 /// Validate if Own<T> type constructor is valid with the given inner type
@@ -79,6 +174,17 @@ pub fn validate_own_constructor(
         TypeConstructor::Own => {
             Err("Cannot use Own<Own<T>>. This creates double ownership.".to_string())
         }
+        TypeConstructor::Dyn(_) => Err(
+            "Cannot use Own<Dyn<..>>. Trait objects are unsized; own one through a pointer, e.g. Own<Box<Dyn<Display>>>."
+                .to_string(),
+        ),
+        TypeConstructor::Impl(_) => Err(
+            "Cannot use Own<Impl<..>>. Impl<..> values are always owned.".to_string(),
+        ),
+        TypeConstructor::Tuple => Err(
+            "Cannot use Own<(..)>. Tuples are always owned; ownership applies to each element instead, e.g. (Own<String>, I64)."
+                .to_string(),
+        ),
         _ => Ok(()),
     }
 }
@@ -105,7 +211,91 @@ pub fn substitute_generic_type(
             VeltranoType {
                 constructor: type_template.constructor.clone(),
                 args: substituted_args,
+                lifetime: type_template.lifetime.clone(),
             }
         }
     }
 }
+
+/// Match a type from an imported Rust signature, whose generic parameters are
+/// `$T` placeholders, against a concrete type, recording what each placeholder
+/// stands for
+pub fn bind_placeholders(
+    pattern: &VeltranoType,
+    actual: &VeltranoType,
+    bindings: &mut HashMap<String, VeltranoType>,
+) -> bool {
+    if let TypeConstructor::Custom(name) = &pattern.constructor {
+        if name.starts_with('$') {
+            return match bindings.get(name) {
+                Some(bound) => bound == actual,
+                None => {
+                    bindings.insert(name.clone(), actual.clone());
+                    true
+                }
+            };
+        }
+    }
+    pattern.constructor == actual.constructor
+        && pattern.args.len() == actual.args.len()
+        && pattern
+            .args
+            .iter()
+            .zip(&actual.args)
+            .all(|(pattern_arg, actual_arg)| bind_placeholders(pattern_arg, actual_arg, bindings))
+}
+
+/// Replace bound `$T` placeholders, folding the ownership wrappers this exposes
+pub fn substitute_placeholders(
+    template: &VeltranoType,
+    bindings: &HashMap<String, VeltranoType>,
+    trait_checker: &mut RustInteropRegistry,
+) -> VeltranoType {
+    if let TypeConstructor::Custom(name) = &template.constructor {
+        if let Some(bound) = bindings.get(name) {
+            return bound.clone();
+        }
+    }
+
+    let substituted = VeltranoType {
+        constructor: template.constructor.clone(),
+        args: template
+            .args
+            .iter()
+            .map(|arg| substitute_placeholders(arg, bindings, trait_checker))
+            .collect(),
+        lifetime: template.lifetime.clone(),
+    };
+    fold_ownership(substituted, trait_checker)
+}
+
+/// Type of looking up a map value, as in `m[key]` or `m.get(key)`: an optional
+/// borrow of the value type
+pub fn map_lookup_type(
+    value_type: &VeltranoType,
+    trait_checker: &mut RustInteropRegistry,
+) -> VeltranoType {
+    let borrowed = fold_ownership(VeltranoType::ref_(value_type.clone()), trait_checker);
+    fold_ownership(
+        VeltranoType::own(VeltranoType::option(borrowed)),
+        trait_checker,
+    )
+}
+
+/// Fold the outermost ownership wrappers: `Ref<Own<T>>` is `T`, and `Own<T>`
+/// of a Copy type is just `T`
+fn fold_ownership(
+    veltrano_type: VeltranoType,
+    trait_checker: &mut RustInteropRegistry,
+) -> VeltranoType {
+    let Some(inner) = veltrano_type.inner() else {
+        return veltrano_type;
+    };
+    match &veltrano_type.constructor {
+        TypeConstructor::Ref if inner.constructor == TypeConstructor::Own => {
+            inner.inner().cloned().unwrap_or(veltrano_type)
+        }
+        TypeConstructor::Own if inner.implements_copy(trait_checker) => inner.clone(),
+        _ => veltrano_type,
+    }
+}
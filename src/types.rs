@@ -6,12 +6,35 @@ use crate::rust_interop::RustType;
 use std::collections::HashMap;
 
 /// A type in the Veltrano type system supporting higher-kinded types
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
 pub struct VeltranoType {
     /// The type constructor or base type
     pub constructor: TypeConstructor,
     /// Type arguments (empty for base types)
     pub args: Vec<VeltranoType>,
+    /// Lifetime label of this reference level (`Str@a`), if written explicitly
+    pub lifetime: Option<String>,
+}
+
+/// Lifetime labels don't take part in type equality; they are checked separately
+impl PartialEq for VeltranoType {
+    fn eq(&self, other: &Self) -> bool {
+        self.constructor == other.constructor && self.args == other.args
+    }
+}
+
+/// Unlabeled types are shown without their (absent) lifetime
+impl std::fmt::Debug for VeltranoType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("VeltranoType");
+        debug
+            .field("constructor", &self.constructor)
+            .field("args", &self.args);
+        if let Some(lifetime) = &self.lifetime {
+            debug.field("lifetime", lifetime);
+        }
+        debug.finish()
+    }
 }
 
 /// Type constructors and base types with their kinds
@@ -59,22 +82,55 @@ pub enum TypeConstructor {
     Ref,
     /// MutRef<T> - mutable reference (&mut T)
     MutRef,
+    /// Ptr<T> - raw pointer (*const T), as passed to and from C
+    Ptr,
+    /// MutPtr<T> - mutable raw pointer (*mut T)
+    MutPtr,
     /// Box<T> - heap allocation
     Box,
+    /// Rc<T> - reference-counted shared ownership (std::rc::Rc)
+    Rc,
+    /// Arc<T> - atomically reference-counted shared ownership (std::sync::Arc)
+    Arc,
+    /// RefCell<T> - interior mutability with runtime borrow checks (std::cell::RefCell)
+    RefCell,
+    /// Cell<T> - interior mutability by copying values in and out (std::cell::Cell)
+    Cell,
     /// Vec<T> - dynamic array
     Vec,
     /// Option<T> - optional value
     Option,
 
+    /// HashSet<T> - hash set (std::collections::HashSet)
+    HashSet,
+
     // Higher-kinded constructors (kind * -> * -> *)
     /// Result<T, E> - result type
     Result,
+    /// HashMap<K, V> - hash map (std::collections::HashMap)
+    HashMap,
+    /// BTreeMap<K, V> - ordered map (std::collections::BTreeMap)
+    BTreeMap,
 
     // Special cases
     /// Array<T, N> - fixed-size array (size is part of type)
     Array(usize),
     /// Slice<T> - dynamically sized slice type (&[T] in Rust)
     Slice,
+    /// (T1, T2, ...) - tuple type (arity is given by the number of args)
+    Tuple,
+    /// Future<T> - value of a `suspend fun` call, `.await()`ed for its T
+    /// (impl Future<Output = T> in Rust)
+    Future,
+    /// (A, B) -> R - function type; args are the parameter types followed by
+    /// the return type (fn(A, B) -> R in Rust)
+    Function,
+    /// Dyn<Trait> - trait object; args are the trait's type arguments
+    /// (&dyn Trait in Rust, or dyn Trait behind a Box, Rc or Arc)
+    Dyn(String),
+    /// Impl<Trait> - some type implementing the trait, in parameter and
+    /// return types (impl Trait in Rust)
+    Impl(String),
 }
 
 impl VeltranoType {
@@ -84,6 +140,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::I32,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -91,6 +148,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::I64,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -98,6 +156,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::ISize,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -106,6 +165,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::U32,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -113,6 +173,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::U64,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -120,6 +181,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::USize,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -128,6 +190,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Bool,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -135,6 +198,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Char,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -142,6 +206,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Unit,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -149,6 +214,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Nothing,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -156,6 +222,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Str,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -163,6 +230,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::String,
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -170,6 +238,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Custom(name),
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -177,6 +246,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Generic(name, constraints),
             args: vec![],
+            lifetime: None,
         }
     }
 
@@ -187,6 +257,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Own,
             args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -194,6 +265,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Ref,
             args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -201,6 +273,23 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::MutRef,
             args: vec![inner],
+            lifetime: None,
+        }
+    }
+
+    pub fn ptr(inner: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::Ptr,
+            args: vec![inner],
+            lifetime: None,
+        }
+    }
+
+    pub fn mut_ptr(inner: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::MutPtr,
+            args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -208,6 +297,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Vec,
             args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -215,6 +305,39 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Box,
             args: vec![inner],
+            lifetime: None,
+        }
+    }
+
+    pub fn rc(inner: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::Rc,
+            args: vec![inner],
+            lifetime: None,
+        }
+    }
+
+    pub fn arc(inner: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::Arc,
+            args: vec![inner],
+            lifetime: None,
+        }
+    }
+
+    pub fn ref_cell(inner: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::RefCell,
+            args: vec![inner],
+            lifetime: None,
+        }
+    }
+
+    pub fn cell(inner: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::Cell,
+            args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -222,6 +345,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Array(size),
             args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -229,6 +353,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Slice,
             args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -236,6 +361,7 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Option,
             args: vec![inner],
+            lifetime: None,
         }
     }
 
@@ -243,6 +369,84 @@ impl VeltranoType {
         Self {
             constructor: TypeConstructor::Result,
             args: vec![ok_type, err_type],
+            lifetime: None,
+        }
+    }
+
+    pub fn hash_map(key_type: VeltranoType, value_type: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::HashMap,
+            args: vec![key_type, value_type],
+            lifetime: None,
+        }
+    }
+
+    pub fn btree_map(key_type: VeltranoType, value_type: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::BTreeMap,
+            args: vec![key_type, value_type],
+            lifetime: None,
+        }
+    }
+
+    pub fn hash_set(inner: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::HashSet,
+            args: vec![inner],
+            lifetime: None,
+        }
+    }
+
+    pub fn tuple(elements: Vec<VeltranoType>) -> Self {
+        Self {
+            constructor: TypeConstructor::Tuple,
+            args: elements,
+            lifetime: None,
+        }
+    }
+
+    pub fn future(output: VeltranoType) -> Self {
+        Self {
+            constructor: TypeConstructor::Future,
+            args: vec![output],
+            lifetime: None,
+        }
+    }
+
+    pub fn dyn_trait(trait_name: &str, trait_args: Vec<VeltranoType>) -> Self {
+        Self {
+            constructor: TypeConstructor::Dyn(trait_name.to_string()),
+            args: trait_args,
+            lifetime: None,
+        }
+    }
+
+    pub fn impl_trait(trait_name: &str, trait_args: Vec<VeltranoType>) -> Self {
+        Self {
+            constructor: TypeConstructor::Impl(trait_name.to_string()),
+            args: trait_args,
+            lifetime: None,
+        }
+    }
+
+    pub fn function(parameters: Vec<VeltranoType>, return_type: VeltranoType) -> Self {
+        let mut args = parameters;
+        args.push(return_type);
+        Self {
+            constructor: TypeConstructor::Function,
+            args,
+            lifetime: None,
+        }
+    }
+
+    /// Parameter and return types of a function type
+    pub fn function_parts(&self) -> Option<(&[VeltranoType], &VeltranoType)> {
+        match self.constructor {
+            TypeConstructor::Function => self
+                .args
+                .split_last()
+                .map(|(return_type, parameters)| (parameters, return_type)),
+            _ => None,
         }
     }
 
@@ -251,6 +455,53 @@ impl VeltranoType {
         self.args.first()
     }
 
+    /// Attach a lifetime label to this reference level
+    pub fn with_lifetime(mut self, lifetime: Option<String>) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Lifetime label of this reference level; an unlabeled `Ref`/`MutRef`
+    /// takes the label of the nearest labeled level inside it
+    pub fn reference_lifetime(&self) -> Option<&String> {
+        self.lifetime.as_ref().or_else(|| match self.constructor {
+            TypeConstructor::Ref | TypeConstructor::MutRef => {
+                self.inner().and_then(|inner| inner.reference_lifetime())
+            }
+            _ => None,
+        })
+    }
+
+    /// All lifetime labels written in this type, outermost first
+    pub fn lifetime_labels(&self) -> Vec<&String> {
+        let mut labels: Vec<&String> = self.lifetime.iter().collect();
+        for arg in &self.args {
+            labels.extend(arg.lifetime_labels());
+        }
+        labels
+    }
+
+    /// Rename or remove (by returning None) every lifetime label in this type
+    pub fn map_lifetimes(&self, rename: &impl Fn(&str) -> Option<String>) -> VeltranoType {
+        VeltranoType {
+            constructor: self.constructor.clone(),
+            args: self
+                .args
+                .iter()
+                .map(|arg| arg.map_lifetimes(rename))
+                .collect(),
+            lifetime: self.lifetime.as_deref().and_then(rename),
+        }
+    }
+
+    /// Rust lifetime of this reference level: its own label, or the inherited one
+    fn rust_lifetime(&self, inherited: &Option<String>) -> Option<String> {
+        match self.reference_lifetime() {
+            Some(label) => Some(format!("'{}", label)),
+            None => inherited.clone(),
+        }
+    }
+
     /// Convert this VeltranoType to its corresponding Rust type name
     /// This method requires a trait checker to determine if types implement Copy
 
@@ -270,6 +521,9 @@ impl VeltranoType {
     ) -> crate::rust_interop::RustType {
         use crate::rust_interop::RustType;
 
+        // Explicit labels override the lifetime inherited from the context
+        let lifetime = self.rust_lifetime(&lifetime);
+
         match &self.constructor {
             TypeConstructor::I32 => RustType::I32,
             TypeConstructor::I64 => RustType::I64,
@@ -349,19 +603,59 @@ impl VeltranoType {
                     RustType::Never // Error case
                 }
             }
+            TypeConstructor::Ptr => {
+                if let Some(inner) = self.inner() {
+                    RustType::ConstPtr(Box::new(
+                        inner.to_rust_type_with_lifetime(trait_checker, lifetime),
+                    ))
+                } else {
+                    RustType::Never // Error case
+                }
+            }
+            TypeConstructor::MutPtr => {
+                if let Some(inner) = self.inner() {
+                    RustType::MutPtr(Box::new(
+                        inner.to_rust_type_with_lifetime(trait_checker, lifetime),
+                    ))
+                } else {
+                    RustType::Never // Error case
+                }
+            }
             TypeConstructor::Box => {
                 if let Some(inner) = self.inner() {
                     // Box is naturally referenced (like non-Copy custom types)
                     RustType::Ref {
                         lifetime: lifetime.clone(),
                         inner: Box::new(RustType::Box(Box::new(
-                            inner.to_rust_type_with_lifetime(trait_checker, lifetime.clone()),
+                            inner.to_pointee_rust_type(trait_checker, lifetime.clone()),
                         ))),
                     }
                 } else {
                     RustType::Never // Error case
                 }
             }
+            TypeConstructor::Rc
+            | TypeConstructor::Arc
+            | TypeConstructor::RefCell
+            | TypeConstructor::Cell => {
+                if let Some(inner) = self.inner() {
+                    // Shared pointers and cells are naturally referenced, like Box
+                    let inner =
+                        Box::new(inner.to_pointee_rust_type(trait_checker, lifetime.clone()));
+                    let wrapper = match &self.constructor {
+                        TypeConstructor::Rc => RustType::Rc(inner),
+                        TypeConstructor::Arc => RustType::Arc(inner),
+                        TypeConstructor::RefCell => RustType::RefCell(inner),
+                        _ => RustType::Cell(inner),
+                    };
+                    RustType::Ref {
+                        lifetime: lifetime.clone(),
+                        inner: Box::new(wrapper),
+                    }
+                } else {
+                    RustType::Never // Error case
+                }
+            }
             TypeConstructor::Vec => {
                 if let Some(inner) = self.inner() {
                     RustType::Vec(Box::new(
@@ -395,14 +689,43 @@ impl VeltranoType {
                     RustType::Never // Error case
                 }
             }
+            TypeConstructor::HashMap | TypeConstructor::BTreeMap => {
+                if let [key, value] = self.args.as_slice() {
+                    // Maps are naturally referenced, like Box
+                    let key =
+                        Box::new(key.to_rust_type_with_lifetime(trait_checker, lifetime.clone()));
+                    let value =
+                        Box::new(value.to_rust_type_with_lifetime(trait_checker, lifetime.clone()));
+                    let map = match &self.constructor {
+                        TypeConstructor::HashMap => RustType::HashMap { key, value },
+                        _ => RustType::BTreeMap { key, value },
+                    };
+                    RustType::Ref {
+                        lifetime: lifetime.clone(),
+                        inner: Box::new(map),
+                    }
+                } else {
+                    RustType::Never // Error case
+                }
+            }
+            TypeConstructor::HashSet => {
+                if let Some(inner) = self.inner() {
+                    // Sets are naturally referenced, like maps
+                    RustType::Ref {
+                        lifetime: lifetime.clone(),
+                        inner: Box::new(RustType::HashSet(Box::new(
+                            inner.to_rust_type_with_lifetime(trait_checker, lifetime.clone()),
+                        ))),
+                    }
+                } else {
+                    RustType::Never // Error case
+                }
+            }
             TypeConstructor::Array(size) => {
-                // Arrays in Rust don't have a direct RustType representation
-                // We'll need to handle this specially in codegen
-                // For now, return a Custom type that represents the array
                 if let Some(inner) = self.inner() {
-                    RustType::Custom {
-                        name: format!("[_; {}]", size), // Placeholder
-                        generics: vec![inner.to_rust_type_with_lifetime(trait_checker, lifetime)],
+                    RustType::Array {
+                        inner: Box::new(inner.to_rust_type_with_lifetime(trait_checker, lifetime)),
+                        size: *size,
                     }
                 } else {
                     RustType::Never // Error case
@@ -423,6 +746,82 @@ impl VeltranoType {
                     RustType::Never // Error case
                 }
             }
+            TypeConstructor::Tuple => {
+                // Tuples are always owned; reference-by-default applies per element
+                RustType::Tuple(
+                    self.args
+                        .iter()
+                        .map(|arg| arg.to_rust_type_with_lifetime(trait_checker, lifetime.clone()))
+                        .collect(),
+                )
+            }
+            TypeConstructor::Future => {
+                // Futures are owned values, like tuples
+                if let Some(output) = self.inner() {
+                    RustType::Future(Box::new(
+                        output.to_rust_type_with_lifetime(trait_checker, lifetime),
+                    ))
+                } else {
+                    RustType::Never // Error case
+                }
+            }
+            TypeConstructor::Dyn(trait_name) => {
+                // Trait objects are naturally referenced, like String
+                RustType::Ref {
+                    lifetime: lifetime.clone(),
+                    inner: Box::new(RustType::Dyn {
+                        trait_name: trait_name.clone(),
+                        generics: self
+                            .args
+                            .iter()
+                            .map(|arg| {
+                                arg.to_rust_type_with_lifetime(trait_checker, lifetime.clone())
+                            })
+                            .collect(),
+                    }),
+                }
+            }
+            TypeConstructor::Impl(trait_name) => {
+                // impl Trait stands for an owned value, like a future
+                RustType::Impl {
+                    trait_name: trait_name.clone(),
+                    generics: self
+                        .args
+                        .iter()
+                        .map(|arg| arg.to_rust_type_with_lifetime(trait_checker, lifetime.clone()))
+                        .collect(),
+                }
+            }
+            TypeConstructor::Function => {
+                if let Some((parameters, return_type)) = self.function_parts() {
+                    RustType::Function {
+                        parameters: parameters
+                            .iter()
+                            .map(|p| p.to_rust_type_with_lifetime(trait_checker, lifetime.clone()))
+                            .collect(),
+                        return_type: Box::new(
+                            return_type.to_rust_type_with_lifetime(trait_checker, lifetime),
+                        ),
+                    }
+                } else {
+                    RustType::Never // Error case
+                }
+            }
+        }
+    }
+
+    /// Rust type of a value behind a pointer (Box, Rc, ...); trait objects
+    /// are unsized there rather than borrowed
+    fn to_pointee_rust_type(
+        &self,
+        trait_checker: &mut crate::rust_interop::RustInteropRegistry,
+        lifetime: Option<String>,
+    ) -> RustType {
+        match self.to_rust_type_with_lifetime(trait_checker, lifetime) {
+            RustType::Ref { inner, .. } if matches!(self.constructor, TypeConstructor::Dyn(_)) => {
+                *inner
+            }
+            other => other,
         }
     }
 
@@ -458,9 +857,23 @@ impl VeltranoType {
                 }
             }
 
+            // Raw pointers are always Copy
+            TypeConstructor::Ptr | TypeConstructor::MutPtr => true,
+
             // Own, Box, Vec, etc. never implement Copy
             TypeConstructor::Own | TypeConstructor::Box | TypeConstructor::Vec => false,
 
+            // Maps and sets own their entries
+            TypeConstructor::HashMap | TypeConstructor::BTreeMap | TypeConstructor::HashSet => {
+                false
+            }
+
+            // Shared pointers are cloned, never copied; cells are neither Copy
+            TypeConstructor::Rc
+            | TypeConstructor::Arc
+            | TypeConstructor::RefCell
+            | TypeConstructor::Cell => false,
+
             // Option and Result depend on their inner types
             TypeConstructor::Option => {
                 if let Some(inner) = self.inner() {
@@ -504,8 +917,123 @@ impl VeltranoType {
 
             // Slices don't implement Copy (they're DSTs)
             TypeConstructor::Slice => false,
+
+            // Tuples are Copy if all of their elements are Copy
+            TypeConstructor::Tuple => self
+                .args
+                .iter()
+                .all(|arg| arg.implements_copy(trait_checker)),
+
+            // Futures are consumed when awaited
+            TypeConstructor::Future => false,
+
+            // Function pointers are Copy
+            TypeConstructor::Function => true,
+
+            // Trait objects are unsized, and impl Trait promises only its trait
+            TypeConstructor::Dyn(_) | TypeConstructor::Impl(_) => false,
+        }
+    }
+
+    /// This type with one level of Ref/MutRef/Own looked through
+    fn collection(&self) -> Option<&VeltranoType> {
+        match &self.constructor {
+            TypeConstructor::Ref | TypeConstructor::MutRef | TypeConstructor::Own => self.inner(),
+            _ => Some(self),
+        }
+    }
+
+    /// Element type of an indexable collection (Vec, Array, Slice), looking
+    /// through one level of Ref/MutRef/Own
+    pub fn element_type(&self) -> Option<&VeltranoType> {
+        let collection = self.collection()?;
+        match &collection.constructor {
+            TypeConstructor::Vec | TypeConstructor::Array(_) | TypeConstructor::Slice => {
+                collection.inner()
+            }
+            _ => None,
         }
     }
+
+    /// Key and value types of a map (HashMap, BTreeMap), looking through one
+    /// level of Ref/MutRef/Own
+    pub fn map_types(&self) -> Option<(&VeltranoType, &VeltranoType)> {
+        let collection = self.collection()?;
+        match (&collection.constructor, collection.args.as_slice()) {
+            (TypeConstructor::HashMap | TypeConstructor::BTreeMap, [key, value]) => {
+                Some((key, value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Element type of a HashSet, looking through one level of Ref/MutRef/Own
+    pub fn set_element_type(&self) -> Option<&VeltranoType> {
+        let collection = self.collection()?;
+        match &collection.constructor {
+            TypeConstructor::HashSet => collection.inner(),
+            _ => None,
+        }
+    }
+
+    /// Trait and trait arguments of a trait object or `impl Trait`, looking
+    /// through references, Own and Box
+    pub fn trait_bound(&self) -> Option<(&str, &[VeltranoType])> {
+        match &self.constructor {
+            TypeConstructor::Dyn(trait_name) | TypeConstructor::Impl(trait_name) => {
+                Some((trait_name, &self.args))
+            }
+            TypeConstructor::Ref
+            | TypeConstructor::MutRef
+            | TypeConstructor::Own
+            | TypeConstructor::Box => self.inner()?.trait_bound(),
+            _ => None,
+        }
+    }
+
+    /// Data class of a value used by reference (`Name`) or owned (`Own<Name>`)
+    pub fn data_class_name(&self) -> Option<&str> {
+        match &self.constructor {
+            TypeConstructor::Custom(name) => Some(name),
+            TypeConstructor::Own => match &self.inner()?.constructor {
+                TypeConstructor::Custom(name) => Some(name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// How a component is bound when destructuring through a reference.
+    ///
+    /// Returns `None` if the component can be copied out of the borrowed value
+    /// (Copy values and naturally referenced types), or `Some(T)` with the type
+    /// of the `ref` binding otherwise: `Own<T>` components yield `T`, anything
+    /// else yields `Ref<T>`.
+    pub fn borrowed_component_type(
+        &self,
+        trait_checker: &mut crate::rust_interop::RustInteropRegistry,
+    ) -> Option<VeltranoType> {
+        match &self.constructor {
+            TypeConstructor::Own => self.inner().cloned(),
+            // Naturally referenced types are already references in Rust
+            TypeConstructor::Str
+            | TypeConstructor::String
+            | TypeConstructor::Ref
+            | TypeConstructor::Custom(_) => None,
+            _ if self.implements_copy(trait_checker) => None,
+            _ => Some(VeltranoType::ref_(self.clone())),
+        }
+    }
+}
+
+/// Map a tuple component name (`first`/`second`/`third` or a numeric index) to its index
+pub fn tuple_component_index(field: &str) -> Option<usize> {
+    match field {
+        "first" => Some(0),
+        "second" => Some(1),
+        "third" => Some(2),
+        _ => field.parse().ok(),
+    }
 }
 
 /// Function signature for type checking
@@ -514,6 +1042,15 @@ pub struct FunctionSignature {
     pub name: String,
     pub parameters: Vec<VeltranoType>,
     pub return_type: VeltranoType,
+    pub lifetime_params: Vec<String>,
+    /// Parameter names for named arguments (empty for built-ins)
+    pub parameter_names: Vec<String>,
+    /// Whether calls return a Future of `return_type` (`suspend fun`)
+    pub is_async: bool,
+    /// Whether calls must appear inside an `unsafe { }` block (`extern "C" fun`)
+    pub is_unsafe: bool,
+    /// Whether each parameter has a default value
+    pub parameter_defaults: Vec<bool>,
 }
 
 /// Method signature for type checking
@@ -530,12 +1067,37 @@ pub struct _MethodSignature {
 pub struct DataClassDefinition {
     pub _name: String,
     pub fields: Vec<DataClassFieldSignature>,
+    pub methods: Vec<DataClassMethodSignature>,
+}
+
+impl DataClassDefinition {
+    /// Member function with the given name
+    pub fn method(&self, name: &str) -> Option<&DataClassMethodSignature> {
+        self.methods
+            .iter()
+            .find(|method| method.signature.name == name)
+    }
+
+    /// Member function declared as `operator fun` with the given name
+    pub fn operator(&self, name: &str) -> Option<&FunctionSignature> {
+        self.method(name)
+            .filter(|method| method.is_operator)
+            .map(|method| &method.signature)
+    }
+}
+
+/// Member function of a data class; `this` is the borrowed receiver
+#[derive(Debug, Clone)]
+pub struct DataClassMethodSignature {
+    pub signature: FunctionSignature,
+    pub is_operator: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct DataClassFieldSignature {
     pub name: String,
     pub field_type: VeltranoType,
+    pub has_default: bool,
 }
 
 /// Type environment for tracking variables, functions, and data classes
@@ -568,6 +1130,23 @@ impl TypeEnvironment {
         self.variables.get(name)
     }
 
+    /// Depth of the innermost scope declaring a variable; globals are at depth 0
+    pub fn variable_scope_depth(&self, name: &str) -> Option<usize> {
+        match self
+            .scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(name))
+        {
+            Some(index) => Some(index + 1),
+            None => self.variables.contains_key(name).then_some(0),
+        }
+    }
+
+    /// Depth of the current scope
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len()
+    }
+
     pub fn declare_variable(&mut self, name: String, typ: VeltranoType) {
         if let Some(current_scope) = self.scopes.last_mut() {
             current_scope.insert(name, typ);
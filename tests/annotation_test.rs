@@ -0,0 +1,143 @@
+//! Tests for @Derive and @Rust annotations on functions, data classes and fields
+
+mod common;
+
+use common::{
+    assert_parse_error, assert_transpilation_match, assert_type_check_error, transpile_and_compile,
+    TestContext,
+};
+
+#[test]
+fn test_derive_annotation_extends_default_derives() {
+    assert_transpilation_match(
+        r#"@Derive(PartialEq, Clone, Hash)
+data class Point(val x: I64, val y: I64)"#,
+        r#"#[derive(Debug, Clone, PartialEq, Hash, Copy, Eq)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={}, y={})", self.x, self.y)
+    }
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_rust_annotations_on_functions_classes_and_fields() {
+    assert_transpilation_match(
+        r#"@Rust("must_use")
+@Derive(Default)
+data class Config(
+    @Rust("allow(dead_code)")
+    val retries: I64,
+    val verbose: Bool
+)
+
+@Rust("inline")
+fun double(x: I64): I64 {
+    return x * 2
+}"#,
+        r#"#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct Config {
+    #[allow(dead_code)]
+    pub retries: i64,
+    pub verbose: bool,
+}
+
+impl std::fmt::Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Config(retries={}, verbose={})", self.retries, self.verbose)
+    }
+}
+
+#[inline]
+fn double(x: i64) -> i64 {
+    return x * 2;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_rust_annotation_unescapes_string() {
+    assert_transpilation_match(
+        r#"@Rust("doc = \"Adds one\"")
+fun inc(x: I64): I64 {
+    return x + 1
+}"#,
+        r#"#[doc = "Adds one"]
+fn inc(x: i64) -> i64 {
+    return x + 1;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_annotations_compile() {
+    transpile_and_compile(
+        r#"@Derive(PartialEq, Eq, Hash, Copy)
+data class Point(val x: I64, val y: I64)
+
+@Rust("inline(always)")
+fun norm(p: Point): I64 {
+    return p.x * p.x + p.y * p.y
+}
+
+fun main() {
+    val p = Point(x = 3, y = 4)
+    println("{}", norm(p))
+}"#,
+        &TestContext::default().with_name("annotations_compile"),
+    )
+    .expect("annotated code should compile");
+}
+
+#[test]
+fn test_annotation_errors() {
+    assert_type_check_error(
+        r#"@Derive(Display)
+data class Point(val x: I64)"#,
+        &TestContext::default().expect_error("trait 'Display' cannot be derived"),
+    );
+
+    assert_type_check_error(
+        r#"@Derive(Serialize)
+data class Point(val x: I64)"#,
+        &TestContext::default().expect_error("unknown trait 'Serialize'"),
+    );
+
+    assert_type_check_error(
+        r#"@Derive(Hash)
+fun f() {
+}"#,
+        &TestContext::default().expect_error("cannot be applied to functions"),
+    );
+
+    assert_type_check_error(
+        r#"@Rust(inline)
+fun f() {
+}"#,
+        &TestContext::default().expect_error("expected a single string"),
+    );
+
+    assert_type_check_error(
+        r#"@Inline
+fun f() {
+}"#,
+        &TestContext::default().expect_error("Invalid annotation @Inline: unknown annotation"),
+    );
+
+    assert_parse_error(
+        r#"@Cfg(test)
+import i64.abs"#,
+        &TestContext::default()
+            .expect_error("Annotations can only be applied to declarations and statements"),
+    );
+}
@@ -32,6 +32,7 @@ fn test_contains_calls() {
         operator: BinaryOp::Add,
         comment_after_operator: None,
         right: Box::new(loc(Expr::Literal(LiteralExpr::Int(42)))),
+        id: 0,
     }));
     assert!(AstQuery::contains_calls(&binary));
 }
@@ -45,6 +46,7 @@ fn test_collect_identifiers() {
         operator: BinaryOp::Add,
         comment_after_operator: None,
         right: Box::new(loc(Expr::Identifier("y".to_string()))),
+        id: 0,
     }));
 
     let ids = AstQuery::collect_identifiers(&expr);
@@ -59,6 +61,8 @@ fn test_collect_identifiers() {
         args: vec![loc(Expr::Identifier("arg".to_string()))],
         inline_comment: None,
         id: 0,
+        named_args: vec![],
+        is_multiline: false,
     }));
 
     let ids = AstQuery::collect_identifiers(&method_call);
@@ -105,6 +109,8 @@ fn test_uses_bump_allocation() {
         args: vec![],
         inline_comment: None,
         id: 0,
+        named_args: vec![],
+        is_multiline: false,
     }));
     assert!(AstQuery::uses_bump_allocation(&bump_ref));
 
@@ -115,6 +121,8 @@ fn test_uses_bump_allocation() {
         args: vec![],
         inline_comment: None,
         id: 0,
+        named_args: vec![],
+        is_multiline: false,
     }));
     assert!(!AstQuery::uses_bump_allocation(&regular_method));
 
@@ -125,6 +133,7 @@ fn test_uses_bump_allocation() {
         operator: BinaryOp::Add,
         comment_after_operator: None,
         right: Box::new(loc(Expr::Literal(LiteralExpr::Int(42)))),
+        id: 0,
     }));
     assert!(AstQuery::uses_bump_allocation(&binary_with_bump));
 }
@@ -141,6 +150,8 @@ fn test_stmt_uses_bump_allocation() {
             args: vec![],
             inline_comment: None,
             id: 0,
+            named_args: vec![],
+            is_multiline: false,
         }))),
     });
     assert!(AstQuery::stmt_uses_bump_allocation(&var_with_bump));
@@ -161,6 +172,8 @@ fn test_stmt_uses_bump_allocation() {
             args: vec![],
             inline_comment: None,
             id: 0,
+            named_args: vec![],
+            is_multiline: false,
         })),
         then_branch: Box::new(Stmt::Block(vec![])),
         else_branch: None,
@@ -182,9 +195,14 @@ fn test_function_requires_bump() {
                 args: vec![],
                 inline_comment: None,
                 id: 0,
+                named_args: vec![],
+                is_multiline: false,
             },
         )))])),
-        has_hidden_bump: false,
+        annotations: vec![],
+        lifetime_params: vec![],
+        is_async: false,
+        location: SourceLocation::new(1, 1),
     };
     assert!(AstQuery::function_requires_bump(&fun_with_bump));
 
@@ -196,7 +214,10 @@ fn test_function_requires_bump() {
         body: Box::new(Stmt::Block(vec![Stmt::Return(Some(loc(Expr::Literal(
             LiteralExpr::Int(42),
         ))))])),
-        has_hidden_bump: false,
+        annotations: vec![],
+        lifetime_params: vec![],
+        is_async: false,
+        location: SourceLocation::new(1, 1),
     };
     assert!(!AstQuery::function_requires_bump(&fun_without_bump));
 }
@@ -242,7 +263,10 @@ fn test_find_function_decls() {
             params: vec![],
             return_type: None,
             body: Box::new(Stmt::Block(vec![])),
-            has_hidden_bump: false,
+            annotations: vec![],
+            lifetime_params: vec![],
+            is_async: false,
+            location: SourceLocation::new(1, 1),
         }),
         Stmt::If(IfStmt {
             condition: loc(Expr::Literal(LiteralExpr::Bool(true))),
@@ -251,7 +275,10 @@ fn test_find_function_decls() {
                 params: vec![],
                 return_type: None,
                 body: Box::new(Stmt::Block(vec![])),
-                has_hidden_bump: false,
+                annotations: vec![],
+                lifetime_params: vec![],
+                is_async: false,
+                location: SourceLocation::new(1, 1),
             })),
             else_branch: None,
         }),
@@ -276,6 +303,7 @@ fn test_collect_variable_references() {
                 operator: BinaryOp::Add,
                 comment_after_operator: None,
                 right: Box::new(loc(Expr::Identifier("b".to_string()))),
+                id: 0,
             }))),
         }),
         Stmt::If(IfStmt {
@@ -286,6 +314,8 @@ fn test_collect_variable_references() {
                 args: vec![loc(Expr::Identifier("e".to_string()))],
                 inline_comment: None,
                 id: 0,
+                named_args: vec![],
+                is_multiline: false,
             })))),
             else_branch: None,
         }),
@@ -314,14 +344,20 @@ fn test_find_program_functions() {
                 params: vec![],
                 return_type: None,
                 body: Box::new(Stmt::Block(vec![])),
-                has_hidden_bump: false,
+                annotations: vec![],
+                lifetime_params: vec![],
+                is_async: false,
+                location: SourceLocation::new(1, 1),
             }),
             Stmt::FunDecl(FunDeclStmt {
                 name: "helper".to_string(),
                 params: vec![],
                 return_type: None,
                 body: Box::new(Stmt::Block(vec![])),
-                has_hidden_bump: false,
+                annotations: vec![],
+                lifetime_params: vec![],
+                is_async: false,
+                location: SourceLocation::new(1, 1),
             }),
         ],
     };
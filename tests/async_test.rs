@@ -0,0 +1,102 @@
+//! Tests for `suspend fun` declarations and `.await()`
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+use veltrano::rust_interop::{RustType, RustTypeParser, SynQuerier};
+use veltrano::types::VeltranoType;
+
+#[test]
+fn test_suspend_fun_generates_async_fn() {
+    assert_transpilation_match(
+        r#"suspend fun fetch(id: I64): I64 {
+    return id * 2
+}
+
+async fun total(): I64 {
+    val first = fetch(1).await()
+    val pending = fetch(2)
+    return first + pending.await()
+}"#,
+        r#"async fn fetch(id: i64) -> i64 {
+    return id * 2;
+}
+async fn total() -> i64 {
+    let first = fetch(1).await;
+    let pending = fetch(2);
+    return first + pending.await;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_async_compiles() {
+    transpile_and_compile(
+        r#"suspend fun fetch(id: I64): I64 {
+    return id * 2
+}
+
+suspend fun total(): I64 {
+    val first = fetch(1).await()
+    return first + fetch(2).await()
+}
+
+fun main() {
+    val pending = total()
+}"#,
+        &TestContext::default().with_name("async_compiles"),
+    )
+    .expect("async functions awaiting each other should compile");
+}
+
+#[test]
+fn test_await_errors() {
+    let fetch = "suspend fun fetch(id: I64): I64 {\n    return id\n}\n";
+
+    assert_type_check_error(
+        &format!("{}fun main() {{\n    val a = fetch(1).await()\n}}", fetch),
+        &TestContext::default().expect_error("only allowed inside a suspend fun"),
+    );
+    assert_type_check_error(
+        &format!(
+            "{}suspend fun run(): I64 {{\n    fun helper(): I64 {{\n        return fetch(1).await()\n    }}\n    return helper()\n}}",
+            fetch
+        ),
+        &TestContext::default().expect_error("only allowed inside a suspend fun"),
+    );
+    assert_type_check_error(
+        "suspend fun run(): I64 {\n    val x: I64 = 3\n    return x.await()\n}",
+        &TestContext::default().expect_error("i64 does not implement Future"),
+    );
+    // Calling a suspend fun does not run it; the future must be awaited
+    assert_type_check_error(
+        &format!(
+            "{}suspend fun run(): I64 {{\n    val a: I64 = fetch(1)\n    return a\n}}",
+            fetch
+        ),
+        &TestContext::default().expect_error("type mismatch"),
+    );
+}
+
+#[test]
+fn test_imported_async_functions_return_futures() {
+    let querier = SynQuerier::new(None).expect("syn querier");
+    let item = syn::parse_str::<syn::ItemFn>("pub async fn fetch(id: i64) -> i64 { id }")
+        .expect("valid async fn");
+    let info = querier.extract_function(&item, "service").unwrap();
+
+    let return_type = info.return_type.parsed.expect("parsed return type");
+    assert_eq!(return_type, RustType::Future(Box::new(RustType::I64)));
+    assert_eq!(
+        return_type.to_veltrano_type(),
+        Ok(VeltranoType::future(VeltranoType::i64()))
+    );
+
+    assert_eq!(
+        RustTypeParser::parse("impl Future<Output = String>"),
+        Ok(RustType::Future(Box::new(RustType::String)))
+    );
+}
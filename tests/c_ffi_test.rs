@@ -0,0 +1,128 @@
+//! Tests for `@CExport` functions and `extern "C"` imports
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_c_export_generation() {
+    assert_transpilation_match(
+        r#"@CExport
+fun add(a: I32, b: I32): I32 {
+    return a + b
+}
+
+@CExport("velt_is_null")
+fun isNull(p: Ptr<U64>): Bool {
+    return false
+}
+
+@CExport
+fun addOne(x: I64): I64 {
+    return x + 1
+}"#,
+        r#"#[no_mangle]
+pub extern "C" fn add(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+#[export_name = "velt_is_null"]
+pub extern "C" fn is_null(p: *const u64) -> bool {
+    return false;
+}
+#[export_name = "addOne"]
+pub extern "C" fn add_one(x: i64) -> i64 {
+    return x + 1;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_extern_c_declaration() {
+    assert_transpilation_match(
+        r#"extern "C" fun labs(x: I64): I64
+extern "C" fun strLen(s: Ptr<U64>): USize
+
+fun main() {
+    unsafe {
+        val a = labs(-3)
+    }
+}"#,
+        r#"extern "C" {
+    fn labs(x: i64) -> i64;
+}
+extern "C" {
+    #[link_name = "strLen"]
+    fn str_len(s: *const u64) -> usize;
+}
+fn main() {
+    unsafe {
+        let a = labs(-3);
+    }
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_c_ffi_compiles() {
+    transpile_and_compile(
+        r#"extern "C" fun labs(x: I64): I64
+
+@CExport
+fun magnitude(x: I64, enabled: Bool): I64 {
+    if (enabled) {
+        unsafe {
+            return labs(x)
+        }
+    }
+    return x
+}
+
+fun main() {
+    println("{}", magnitude(-4, true))
+}"#,
+        &TestContext::default().with_name("c_ffi_compiles"),
+    )
+    .expect("exported and imported C functions should compile");
+}
+
+#[test]
+fn test_c_ffi_errors() {
+    assert_type_check_error(
+        "@CExport\nfun greet(name: Str): I64 {\n    return 0\n}",
+        &TestContext::default().expect_error(
+            "Parameter 'name' of C function 'greet' has type &str, which is not FFI-safe",
+        ),
+    );
+    assert_type_check_error(
+        "extern \"C\" fun getenv(name: Ptr<U64>): String",
+        &TestContext::default().expect_error("Return type of C function 'getenv'"),
+    );
+    // Pointers to unsized values carry a length or vtable
+    assert_type_check_error(
+        "extern \"C\" fun puts(s: Ptr<Str>): I32",
+        &TestContext::default().expect_error(
+            "Parameter 's' of C function 'puts' has type *const &str, which is not FFI-safe",
+        ),
+    );
+    assert_type_check_error(
+        "extern \"C\" fun show(value: MutPtr<Ref<Dyn<Display>>>): I32",
+        &TestContext::default().expect_error("Parameter 'value' of C function 'show'"),
+    );
+    assert_type_check_error(
+        "extern \"C\" fun labs(x: I64): I64\n\nfun main() {\n    val a = labs(-3)\n}",
+        &TestContext::default()
+            .expect_error("Call to unsafe function 'labs' outside an unsafe block"),
+    );
+    assert_type_check_error(
+        "@CExport(\"not a symbol\")\nfun run() {\n}",
+        &TestContext::default().expect_error("Invalid annotation @CExport"),
+    );
+    assert_type_check_error(
+        "data class Point(val x: I64) {\n    @CExport\n    fun getX(): I64 {\n        return this.x\n    }\n}",
+        &TestContext::default().expect_error("cannot be applied to member functions"),
+    );
+}
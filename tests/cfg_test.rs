@@ -0,0 +1,141 @@
+//! Tests for `@Cfg` conditional compilation annotations
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_cfg_generation() {
+    assert_transpilation_match(
+        r#"@Cfg("feature = \"json\"")
+data class Payload(val id: I64) {
+    fun next(): I64 {
+        return this.id + 1
+    }
+}
+
+@Cfg(test)
+fun fixture(): I64 {
+    return 42
+}
+
+fun main() {
+    @Cfg(debug_assertions)
+    val verbose = true
+    @Cfg("not(unix)")
+    println("not on unix")
+}"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg(feature = "json")]
+pub struct Payload {
+    pub id: i64,
+}
+
+#[cfg(feature = "json")]
+impl Payload {
+    pub fn next(&self) -> i64 {
+        return self.id + 1;
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::fmt::Display for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Payload(id={})", self.id)
+    }
+}
+
+#[cfg(test)]
+fn fixture() -> i64 {
+    return 42;
+}
+fn main() {
+    #[cfg(debug_assertions)]
+    let verbose = true;
+    #[cfg(not(unix))]
+    println!("not on unix");
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_exclusive_cfgs_share_a_name() {
+    transpile_and_compile(
+        r#"@Cfg(unix)
+fun pathSeparator(): I64 {
+    return 47
+}
+
+@Cfg("not(unix)")
+fun pathSeparator(): I64 {
+    return 92
+}
+
+@Cfg("target_os = \"linux\"")
+data class Platform(val code: I64)
+
+@Cfg("all(unix, not(target_os = \"linux\"))")
+data class Platform(val code: I64)
+
+@Cfg("all(not(unix), not(target_os = \"linux\"))")
+data class Platform(val code: I64)
+
+fun main() {
+    println("{}", pathSeparator())
+}"#,
+        &TestContext::default().with_name("exclusive_cfgs_share_a_name"),
+    )
+    .expect("items under exclusive cfgs should compile");
+}
+
+#[test]
+fn test_duplicate_definitions() {
+    assert_type_check_error(
+        "fun helper() {\n}\n\nfun helper() {\n}",
+        &TestContext::default().expect_error("Duplicate definition of function 'helper'"),
+    );
+    // Features are additive, so both definitions can be enabled together
+    assert_type_check_error(
+        "@Cfg(\"feature = \\\"a\\\"\")\nfun helper() {\n}\n\n@Cfg(\"feature = \\\"b\\\"\")\nfun helper() {\n}",
+        &TestContext::default().expect_error("Duplicate definition of function 'helper'"),
+    );
+    assert_type_check_error(
+        "@Cfg(test)\ndata class Point(val x: I64)\n\ndata class Point(val x: I64)",
+        &TestContext::default().expect_error("Duplicate definition of data class 'Point'"),
+    );
+}
+
+#[test]
+fn test_exclusive_cfg_variants_need_the_same_signature() {
+    assert_type_check_error(
+        "@Cfg(unix)\nfun pick(a: I64): I64 {\n    return a\n}\n\n@Cfg(\"not(unix)\")\nfun pick(a: I64, b: I64): I64 {\n    return b\n}\n\nfun main() {\n    println(\"{}\", pick(1))\n}",
+        &TestContext::default().expect_error(
+            "Definitions of function 'pick' under different @Cfg conditions have different signatures",
+        ),
+    );
+    assert_type_check_error(
+        "@Cfg(unix)\ndata class Platform(val code: I64)\n\n@Cfg(\"not(unix)\")\ndata class Platform(val name: Str)",
+        &TestContext::default().expect_error(
+            "Definitions of data class 'Platform' under different @Cfg conditions have different signatures",
+        ),
+    );
+}
+
+#[test]
+fn test_cfg_annotation_errors() {
+    assert_type_check_error(
+        "@Cfg(\"all(unix\")\nfun f() {\n}",
+        &TestContext::default().expect_error("expected a single cfg predicate"),
+    );
+    assert_type_check_error(
+        "data class Point(@Cfg(test) val x: I64)",
+        &TestContext::default().expect_error("cannot be applied to data class fields"),
+    );
+    assert_type_check_error(
+        "fun main() {\n    @Derive(Clone)\n    val x = 1\n}",
+        &TestContext::default().expect_error("cannot be applied to statements"),
+    );
+}
@@ -0,0 +1,143 @@
+//! Tests for collection literals (vecOf, arrayOf, Array) and index expressions
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_collection_literals() {
+    assert_transpilation_match(
+        r#"fun main() {
+    val xs = vecOf(1, 2, 3)
+    val arr: Array<I64, 3> = arrayOf(4, 5, 6)
+    val zeros = Array(4, 0)
+    val empty: Own<Vec<Str>> = vecOf()
+}"#,
+        r#"fn main() {
+    let xs = vec![1, 2, 3];
+    let arr: [i64; 3] = [4, 5, 6];
+    let zeros = [0; 4];
+    let empty: Vec<&str> = vec![];
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_index_expressions() {
+    // Copy elements are copied, other elements are borrowed
+    assert_transpilation_match(
+        r#"fun get(xs: Own<Vec<I64>>, i: USize): I64 {
+    return xs[i] + xs[0]
+}
+
+fun main() {
+    val names: Own<Vec<Own<String>>> = vecOf("a".toString())
+    val name = names[0]
+}"#,
+        r#"fn get(xs: Vec<i64>, i: usize) -> i64 {
+    return xs[i] + xs[0];
+}
+fn main() {
+    let names: Vec<String> = vec![ToString::to_string("a")];
+    let name = &names[0];
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_index_element_type() {
+    // The borrowed element of Vec<Own<String>> is String, not Own<String>
+    assert_type_check_error(
+        r#"fun main() {
+    val names: Own<Vec<Own<String>>> = vecOf("a".toString())
+    val name: Own<String> = names[0]
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+}
+
+#[test]
+fn test_collections_compile() {
+    transpile_and_compile(
+        r#"data class Point(val x: I64, val y: I64)
+
+fun main() {
+    val xs = vecOf(1, 2, 3)
+    val arr = arrayOf(4, 5, 6)
+    val zeros = Array(2, 7)
+    val names: Own<Vec<Own<String>>> = vecOf("a".toString(), "b".toString())
+    val points = arrayOf(Point(x = 1, y = 2))
+    val point = points[0]
+    println("{} {} {} {} {}", xs[1], arr[2], zeros[1], names[1], point.y)
+}"#,
+        &TestContext::default().with_name("collections_compile"),
+    )
+    .expect("collection code should compile");
+}
+
+#[test]
+fn test_nested_index_compile() {
+    transpile_and_compile(
+        r#"fun main() {
+    val nested = vecOf(vecOf(1, 2), vecOf(3))
+    val first: I64 = nested[0][0]
+    val row = nested[1]
+    println("{} {:?} {} {}", first, row, nested[1][0] + first, nested[0].length())
+}"#,
+        &TestContext::default().with_name("nested_index_compile"),
+    )
+    .expect("only the outermost element is borrowed");
+}
+
+#[test]
+fn test_index_errors() {
+    assert_type_check_error(
+        r#"fun get(xs: Own<Vec<I64>>, i: I64): I64 {
+    return xs[i]
+}"#,
+        &TestContext::default().expect_error("Index must be USize"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val x = 42
+    val y = x[0]
+}"#,
+        &TestContext::default().expect_error("cannot be indexed"),
+    );
+}
+
+#[test]
+fn test_collection_literal_errors() {
+    assert_type_check_error(
+        r#"fun main() {
+    val xs = vecOf(1, true)
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val xs = vecOf()
+}"#,
+        &TestContext::default().expect_error("Cannot infer the element type of an empty vecOf()"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val arr: Array<I64, 2> = arrayOf(1, 2, 3)
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val arr = Array(2, "a".toString())
+}"#,
+        &TestContext::default().expect_error("requires a Copy element type"),
+    );
+}
@@ -24,6 +24,7 @@ pub struct TestContext {
     pub test_name: String,
     pub expected_error: Option<String>,
     pub remove_error_lines: bool,
+    pub script: bool,
 }
 
 impl Default for TestContext {
@@ -37,6 +38,7 @@ impl Default for TestContext {
             test_name: "test".to_string(),
             expected_error: None,
             remove_error_lines: false,
+            script: false,
         }
     }
 }
@@ -75,6 +77,12 @@ impl TestContext {
         self
     }
 
+    /// Treat the code as a script with top-level statements
+    pub fn script(mut self) -> Self {
+        self.script = true;
+        self
+    }
+
     /// Set remove_error_lines flag
     pub fn remove_error_lines(mut self, remove: bool) -> Self {
         self.remove_error_lines = remove;
@@ -91,17 +99,48 @@ pub fn parse_veltrano_code(code: &str, config: Config) -> Result<Program, Veltra
     parser.parse()
 }
 
-/// Generate Rust code from an AST program with optional method resolutions
+/// Parse Veltrano code the way the test context asks for, as a script or not
+fn parse_with_context(code: &str, ctx: &TestContext) -> Result<Program, VeltranoError> {
+    let program = parse_veltrano_code(code, ctx.config.clone())?;
+    if ctx.script {
+        program.into_script()
+    } else {
+        Ok(program)
+    }
+}
+
+/// Type information gathered by the type checker for code generation
+type TypeCheckResults = (
+    std::collections::HashMap<usize, veltrano::type_checker::MethodResolution>,
+    std::collections::HashMap<usize, veltrano::types::VeltranoType>,
+    std::collections::HashMap<usize, Vec<Option<String>>>,
+    std::collections::HashMap<usize, veltrano::type_checker::OperatorResolution>,
+    std::collections::HashMap<String, Vec<String>>,
+    std::collections::HashSet<String>,
+);
+
+/// Generate Rust code from an AST program with optional type checker results
 fn generate_rust_code(
     program: &Program,
     config: Config,
-    method_resolutions: Option<
-        std::collections::HashMap<usize, veltrano::type_checker::MethodResolution>,
-    >,
+    type_check_results: Option<TypeCheckResults>,
 ) -> String {
     let mut codegen = CodeGenerator::with_config(config);
-    if let Some(resolutions) = method_resolutions {
+    if let Some((
+        resolutions,
+        node_types,
+        lifetime_bindings,
+        operator_resolutions,
+        data_class_derives,
+        hidden_bump_functions,
+    )) = type_check_results
+    {
         codegen.set_method_resolutions(resolutions);
+        codegen.set_node_types(node_types);
+        codegen.set_lifetime_bindings(lifetime_bindings);
+        codegen.set_operator_resolutions(operator_resolutions);
+        codegen.set_data_class_derives(data_class_derives);
+        codegen.set_hidden_bump_functions(hidden_bump_functions);
     }
     codegen
         .generate(program)
@@ -119,8 +158,20 @@ pub fn parse_and_type_check(
     ),
     VeltranoError,
 > {
-    let program = parse_veltrano_code(code, config)?;
+    parse_and_type_check_with_results(code, config)
+        .map(|(program, (resolutions, ..))| (program, resolutions))
+}
 
+/// Parse and type check, keeping everything the code generator needs
+fn parse_and_type_check_with_results(
+    code: &str,
+    config: Config,
+) -> Result<(Program, TypeCheckResults), VeltranoError> {
+    type_check_with_results(parse_veltrano_code(code, config)?)
+}
+
+/// Type check a parsed program, keeping everything the code generator needs
+fn type_check_with_results(program: Program) -> Result<(Program, TypeCheckResults), VeltranoError> {
     let mut type_checker = VeltranoTypeChecker::new();
     type_checker.check_program(&program).map_err(|errors| {
         // Convert the first TypeCheckError to VeltranoError
@@ -136,26 +187,36 @@ pub fn parse_and_type_check(
             })
     })?;
     let resolutions = type_checker.get_method_resolutions().clone();
-
-    Ok((program, resolutions))
+    let node_types = type_checker.get_node_types().clone();
+    let lifetime_bindings = type_checker.get_lifetime_bindings().clone();
+    let operator_resolutions = type_checker.get_operator_resolutions().clone();
+    let data_class_derives = type_checker.get_data_class_derives().clone();
+    let hidden_bump_functions = type_checker.get_hidden_bump_functions().clone();
+
+    Ok((
+        program,
+        (
+            resolutions,
+            node_types,
+            lifetime_bindings,
+            operator_resolutions,
+            data_class_derives,
+            hidden_bump_functions,
+        ),
+    ))
 }
 
 /// Shared utility to perform full transpilation pipeline: lex → parse → type check → codegen
 pub fn transpile(code: &str, ctx: &TestContext) -> Result<String, String> {
-    let (program, resolutions) = if ctx.skip_type_check {
-        (
-            parse_veltrano_code(code, ctx.config.clone()).map_err(|e| e.to_string())?,
-            std::collections::HashMap::new(),
-        )
+    let program = parse_with_context(code, ctx).map_err(|e| e.to_string())?;
+    let (program, results) = if ctx.skip_type_check {
+        (program, None)
     } else {
-        parse_and_type_check(code, ctx.config.clone()).map_err(|e| e.to_string())?
+        let (program, results) = type_check_with_results(program).map_err(|e| e.to_string())?;
+        (program, Some(results))
     };
 
-    Ok(generate_rust_code(
-        &program,
-        ctx.config.clone(),
-        Some(resolutions),
-    ))
+    Ok(generate_rust_code(&program, ctx.config.clone(), results))
 }
 
 /// Parse and type check, returning ALL type check errors
@@ -357,7 +418,7 @@ pub fn compile_rust_code(rust_code: &str, ctx: &TestContext) -> Result<(), Strin
 
 /// Helper to assert parsing fails with optional specific error message
 pub fn assert_parse_error(code: &str, ctx: &TestContext) -> VeltranoError {
-    match parse_veltrano_code(code, ctx.config.clone()) {
+    match parse_with_context(code, ctx) {
         Ok(_) => panic!("Expected parsing to fail, but it succeeded"),
         Err(error) => {
             let error_string = error.to_string();
@@ -413,7 +474,7 @@ pub fn assert_transpilation_output(
 
 /// Helper to assert type checking fails with optional specific error message
 pub fn assert_type_check_error(code: &str, ctx: &TestContext) -> String {
-    match parse_and_type_check(code, ctx.config.clone()) {
+    match parse_with_context(code, ctx).and_then(type_check_with_results) {
         Ok(_) => panic!("Expected type checking to fail, but it succeeded"),
         Err(error) => {
             let error_message = format!("{}: {}", error.kind, error.message);
@@ -0,0 +1,100 @@
+//! Tests for rejecting references to locals returned from functions
+
+mod common;
+
+use common::{assert_type_check_error, parse_and_type_check, transpile_and_compile, TestContext};
+use veltrano::config::Config;
+
+#[test]
+fn test_returning_reference_to_local_is_rejected() {
+    let code = "fun name(): String {\n    val s = \"x\".toString()\n    return s.ref()\n}";
+    let error = parse_and_type_check(code, Config::default()).expect_err("s dies on return");
+    assert_eq!(
+        error.message,
+        "Cannot return a reference to local variable 's'"
+    );
+    assert!(error
+        .context
+        .help
+        .is_some_and(|help| help.contains("s.bumpRef()")));
+}
+
+#[test]
+fn test_reference_bound_to_variable_keeps_its_origin() {
+    let code =
+        "fun name(): String {\n    val s = \"x\".toString()\n    val r = s.ref()\n    val alias = r\n    return alias\n}";
+    let error = parse_and_type_check(code, Config::default()).expect_err("alias borrows s");
+    let returned_at = error.context.span.expect("return location");
+    assert_eq!(returned_at.start.line, 5);
+    let (borrowed_at, _) = error.context.related.expect("borrow location");
+    assert_eq!((borrowed_at.start.line, borrowed_at.start.column), (3, 13));
+}
+
+#[test]
+fn test_returning_reference_to_parameter_slot_or_temporary_is_rejected() {
+    assert_type_check_error(
+        "fun wrap(name: String): Ref<String> {\n    return name.ref()\n}",
+        &TestContext::default().expect_error("local variable 'name'"),
+    );
+    assert_type_check_error(
+        "fun make(): String {\n    return \"x\".toString().ref()\n}",
+        &TestContext::default().expect_error("a temporary value"),
+    );
+}
+
+#[test]
+fn test_origins_flow_through_values_holding_references() {
+    let holder = "data class Holder(val s: String)\n\n";
+    assert_type_check_error(
+        &format!(
+            "{}fun name(): String {{\n    val s = \"x\".toString()\n    val h = Holder(s = s.ref())\n    return h.s\n}}",
+            holder
+        ),
+        &TestContext::default().expect_error("Cannot return a reference to local variable 's'"),
+    );
+    assert_type_check_error(
+        "fun first(): String {\n    val v: Own<Vec<Own<String>>> = vecOf(\"x\".toString())\n    return v.ref()[0]\n}",
+        &TestContext::default().expect_error("Cannot return a reference to local variable 'v'"),
+    );
+    assert_type_check_error(
+        &format!(
+            "{}fun keep(): Holder {{\n    val s = \"x\".toString()\n    val h = Holder(s = s.ref())\n    return h.bumpRef()\n}}",
+            holder
+        ),
+        &TestContext::default().expect_error("Cannot return a reference to local variable 's'"),
+    );
+    assert_type_check_error(
+        "fun pair(): String {\n    val s = \"x\".toString()\n    val p = s.ref() to 1\n    val all = vecOf(p)\n    return all[0].first\n}",
+        &TestContext::default().expect_error("Cannot return a reference to local variable 's'"),
+    );
+}
+
+#[test]
+fn test_parameter_and_bump_references_can_be_returned() {
+    transpile_and_compile(
+        r#"data class Person(val name: Own<String>, val age: I64)
+
+fun pick(first: String, second: String): String {
+    return first
+}
+
+fun personName(person: Person): String {
+    return person.name.ref()
+}
+
+fun make(): String {
+    val s = "made".toString()
+    val allocated = s.bumpRef()
+    return allocated
+}
+
+fun main() {
+    val a = "a".toString()
+    val b = "b".toString()
+    val person = Person(name = "Ada".toString(), age = 36)
+    println("{} {} {}", pick(a.ref(), b.ref()), personName(person.ref()), make())
+}"#,
+        &TestContext::default().with_name("dangling_reference_compile"),
+    )
+    .expect("references borrowed from the caller should compile");
+}
@@ -0,0 +1,173 @@
+//! Tests for `copy()` with named overrides on data classes
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+use veltrano::config::Config;
+
+#[test]
+fn test_copy_uses_functional_update() {
+    // Copy classes are reused by value; other classes are cloned
+    assert_transpilation_match(
+        r#"data class Point(val x: I64, val y: I64)
+data class Tally(val counts: Own<Vec<I64>>, val total: I64)
+
+fun main() {
+    val origin = Point(x = 0, y = 0)
+    val moved = origin.copy(x = 5)
+    val tally = Tally(counts = vecOf(1, 2), total = 3)
+    val total: I64 = 4
+    val bumped = tally.copy(.total)
+}"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={}, y={})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tally {
+    pub counts: Vec<i64>,
+    pub total: i64,
+}
+
+impl std::fmt::Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Tally(counts={:?}, total={})", self.counts, self.total)
+    }
+}
+
+fn main() {
+    let origin = Point { x: 0, y: 0 };
+    let moved = Point { x: 5, ..origin };
+    let tally = Tally { counts: vec![1, 2], total: 3 };
+    let total: i64 = 4;
+    let bumped = Tally { total, ..tally.clone() };
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_copy_with_references_is_field_by_field() {
+    assert_transpilation_match(
+        r#"data class Person(val name: Str, val age: I64, val tags: Own<Vec<I64>>)
+
+fun main() {
+    val ann = Person(name = "Ann", age = 30, tags = vecOf(1))
+    val p = ann.ref()
+    val older = p.copy(
+        age = p.age + 1 // one year older
+    )
+}"#,
+        r#"#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub age: i64,
+    pub tags: Vec<i64>,
+}
+
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, age={}, tags={:?})", self.name, self.age, self.tags)
+    }
+}
+
+fn main() {
+    let ann = Person { name: "Ann", age: 30, tags: vec![1] };
+    let p = &ann;
+    let older = Person {
+        age: p.age + 1, // one year older
+        name: p.name,
+        tags: p.tags.clone(),
+    };
+}"#,
+        &TestContext::with_config(Config {
+            preserve_comments: true,
+        }),
+    );
+}
+
+#[test]
+fn test_copy_compiles() {
+    transpile_and_compile(
+        r#"data class Point(val x: I64, val y: I64)
+data class Person(val name: Str, val age: I64, val tags: Own<Vec<I64>>)
+data class Tally(val counts: Own<Vec<I64>>, val total: I64)
+
+fun main() {
+    val origin = Point(x = 0, y = 0)
+    val moved = origin.copy(y = 2)
+    val ann = Person(name = "Ann", age = 30, tags = vecOf(1))
+    val older = ann.ref().copy(age = 31)
+    val renamed = older.ref().copy(name = "Bea")
+    val tally = Tally(counts = vecOf(1, 2), total = 3)
+    val total: I64 = 4
+    val bumped = tally.ref().copy(.total)
+    println("{:?} {:?} {:?} {:?} {:?}", origin, moved, ann, renamed, bumped)
+}"#,
+        &TestContext::default().with_name("data_class_copy_compiles"),
+    )
+    .expect("copy() with named overrides should compile");
+}
+
+#[test]
+fn test_copy_errors() {
+    let classes = "data class Point(val x: I64, val y: I64)\n";
+
+    assert_type_check_error(
+        &format!(
+            "{}fun main() {{\n    val p = Point(x = 1, y = 2)\n    val q = p.copy(z = 3)\n}}",
+            classes
+        ),
+        &TestContext::default().expect_error("Field 'z' not found"),
+    );
+    assert_type_check_error(
+        &format!(
+            "{}fun main() {{\n    val p = Point(x = 1, y = 2)\n    val q = p.copy(x = true)\n}}",
+            classes
+        ),
+        &TestContext::default().expect_error("type mismatch"),
+    );
+    assert_type_check_error(
+        &format!(
+            "{}fun main() {{\n    val p = Point(x = 1, y = 2)\n    val q = p.copy(3)\n}}",
+            classes
+        ),
+        &TestContext::default().expect_error("must be overridden by name"),
+    );
+    assert_type_check_error(
+        "fun main() {\n    val n: I64 = 1\n    val m = n.copy(x = 2)\n}",
+        &TestContext::default().expect_error("copy"),
+    );
+}
+
+#[test]
+fn test_copy_methods_that_are_not_data_class_copies() {
+    transpile_and_compile(
+        r#"import String.clone as copy
+
+data class Counter(val count: I64) {
+    fun copy(): I64 {
+        return this.count
+    }
+}
+
+fun main() {
+    val name = "Ada".toString()
+    val again = name.ref().copy()
+    val counter = Counter(count = 3)
+    println("{} {}", again, counter.copy())
+}"#,
+        &TestContext::default().with_name("copy_methods"),
+    )
+    .expect("an imported or member copy is an ordinary method call");
+}
@@ -0,0 +1,168 @@
+//! Tests for derives inferred from data class fields and Copy data classes
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_derives_inferred_from_fields() {
+    // Data classes of Copy classes are Copy too; unknown types are only cloned by reference
+    assert_transpilation_match(
+        r#"data class Point(val x: I64, val y: I64)
+data class Line(val start: Point, val end: Point)
+data class Person(val name: Str, val tags: Own<Vec<Own<String>>>)
+data class Wrapper(val item: MyType)"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={}, y={})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl std::fmt::Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line(start={}, end={})", self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub tags: Vec<String>,
+}
+
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, tags={:?})", self.name, self.tags)
+    }
+}
+
+#[derive(Clone)]
+pub struct Wrapper<'a> {
+    pub item: &'a MyType,
+}
+
+impl<'a> std::fmt::Display for Wrapper<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Wrapper(item={:?})", self.item)
+    }
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_copy_data_classes_are_values() {
+    assert_transpilation_match(
+        r#"data class Point(val x: I64, val y: I64)
+
+fun shift(p: Point, dx: I64): Point {
+    return Point(x = p.x + dx, y = p.y)
+}
+
+fun main() {
+    val origin = Point(x = 0, y = 0)
+    val moved = shift(origin, 2)
+    val same = origin == moved
+}"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={}, y={})", self.x, self.y)
+    }
+}
+
+fn shift(p: Point, dx: i64) -> Point {
+    return Point { x: p.x + dx, y: p.y };
+}
+fn main() {
+    let origin = Point { x: 0, y: 0 };
+    let moved = shift(origin, 2);
+    let same = origin == moved;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_derived_traits_compile() {
+    transpile_and_compile(
+        r#"data class Point(val x: I64, val y: I64)
+data class Label(val text: Own<String>, val at: Point)
+
+fun area(a: Point, b: Point): I64 {
+    return (b.x - a.x) * (b.y - a.y)
+}
+
+fun main() {
+    val a = Point(x = 1, y = 2)
+    val b = Point(x = 4, y = 6)
+    val first = Label(text = "start".toString(), at = a)
+    val second = Label(text = "start".toString(), at = a)
+    println("{} {} {}", area(a, b), a == b, first == second)
+}"#,
+        &TestContext::default().with_name("derived_traits_compile"),
+    )
+    .expect("data classes with inferred derives should compile");
+}
+
+#[test]
+fn test_debug_and_clone_need_every_field() {
+    // `&dyn Display` can be cloned but not debugged, `Box<dyn Display>` neither
+    transpile_and_compile(
+        r#"data class Shown(val label: Dyn<Display>, val count: I64)
+data class Boxed(val inner: Own<Box<Dyn<Display>>>, val name: Str)
+
+fun main() {
+    val n = 3
+    val shown = Shown(label = n.ref(), count = 1)
+    val again = shown.copy(count = 2)
+    val boxed = Boxed(inner = Box(5), name = "five")
+    println("{} {} {}", shown, again, boxed)
+}"#,
+        &TestContext::default().with_name("partial_derives_compile"),
+    )
+    .expect("only the derives every field supports are emitted");
+
+    assert_type_check_error(
+        r#"data class Boxed(val inner: Own<Box<Dyn<Display>>>, val name: Str)
+
+fun main() {
+    val boxed = Boxed(inner = Box(5), name = "five")
+    val other = boxed.copy(name = "other")
+}"#,
+        &TestContext::default().expect_error(
+            "Invalid arguments for 'Boxed.copy': copies are cloned, but not every field of 'Boxed' implements Clone",
+        ),
+    );
+}
+
+#[test]
+fn test_copy_data_class_rejects_own() {
+    assert_type_check_error(
+        r#"data class Point(val x: I64, val y: I64)
+
+fun make(): Own<Point> {
+    return Point(x = 1, y = 2)
+}"#,
+        &TestContext::default().expect_error("Types that implement Copy are always owned"),
+    );
+}
@@ -0,0 +1,125 @@
+//! Tests for the generated `Display` impls of data classes and `override fun toString()`
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_display_lists_fields() {
+    // Fields without Display fall back to their Debug format
+    assert_transpilation_match(
+        r#"data class Person(val name: Str, val age: I64, val tags: Own<Vec<I64>>)
+
+fun describe(person: Person): Own<String> {
+    return person.toString()
+}"#,
+        r#"#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub age: i64,
+    pub tags: Vec<i64>,
+}
+
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, age={}, tags={:?})", self.name, self.age, self.tags)
+    }
+}
+
+fn describe<'a>(bump: &'a bumpalo::Bump, person: &'a Person<'a>) -> String {
+    return ToString::to_string(person);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_display_labels_use_veltrano_field_names() {
+    assert_transpilation_match(
+        r#"data class Company(val employeeCount: I64)"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Company {
+    pub employee_count: i64,
+}
+
+impl std::fmt::Display for Company {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Company(employeeCount={})", self.employee_count)
+    }
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_to_string_override() {
+    assert_transpilation_match(
+        r#"data class Money(val cents: I64) {
+    override fun toString(): Own<String> {
+        return "money".toString()
+    }
+}"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    pub cents: i64,
+}
+
+impl Money {
+    pub fn display_string(&self) -> String {
+        return ToString::to_string("money");
+    }
+}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.display_string())
+    }
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_display_compiles() {
+    transpile_and_compile(
+        r#"data class Point(val x: I64, val y: I64)
+data class Person(val name: Str, val home: Point, val tags: Own<Vec<I64>>)
+data class Money(val cents: I64) {
+    override fun toString(): Own<String> {
+        return "money".toString()
+    }
+}
+
+fun main() {
+    val home = Point(x = 1, y = 2)
+    val person = Person(name = "Ann", home = home, tags = vecOf(1, 2))
+    val money = Money(cents = 150)
+    val text = money.toString()
+    println("{} {} {} {}", home, person, money, text)
+}"#,
+        &TestContext::default().with_name("data_class_display_compiles"),
+    )
+    .expect("data classes should implement Display");
+}
+
+#[test]
+fn test_invalid_overrides() {
+    assert_type_check_error(
+        r#"data class Money(val cents: I64) {
+    override fun hashCode(): I64 {
+        return 0
+    }
+}"#,
+        &TestContext::default().expect_error("only toString can be overridden"),
+    );
+    assert_type_check_error(
+        r#"data class Money(val cents: I64) {
+    override fun toString(): I64 {
+        return 0
+    }
+}"#,
+        &TestContext::default().expect_error("must take no parameters and return Own<String>"),
+    );
+}
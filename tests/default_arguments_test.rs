@@ -0,0 +1,204 @@
+//! Tests for default parameter values and named arguments
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_named_arguments_and_defaults_for_functions() {
+    // Arguments are reordered and defaults filled in at the call site
+    assert_transpilation_match(
+        r#"fun scale(value: I64, factor: I64 = 2, offset: I64 = 0): I64 {
+    return value * factor + offset
+}
+
+fun main() {
+    val a = scale(5)
+    val b = scale(offset = 1, value = 5)
+    val c = scale(5, offset = 3)
+    val value = 7
+    val d = scale(.value, factor = 3)
+}"#,
+        r#"fn scale(value: i64, factor: i64, offset: i64) -> i64 {
+    return value * factor + offset;
+}
+fn main() {
+    let a = scale(5, 2, 0);
+    let b = scale(5, 2, 1);
+    let c = scale(5, 2, 3);
+    let value = 7;
+    let d = scale(value, 3, 0);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_data_class_field_defaults() {
+    assert_transpilation_match(
+        r#"data class Settings(val retries: I64 = 3, val verbose: Bool = false)
+
+fun main() {
+    val a = Settings(verbose = true)
+    val b = Settings()
+}"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Settings {
+    pub retries: i64,
+    pub verbose: bool,
+}
+
+impl std::fmt::Display for Settings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Settings(retries={}, verbose={})", self.retries, self.verbose)
+    }
+}
+
+fn main() {
+    let a = Settings { verbose: true, retries: 3 };
+    let b = Settings { retries: 3, verbose: false };
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_default_arguments_compile() {
+    transpile_and_compile(
+        r#"data class Endpoint(val host: Str, val port: I64 = 80)
+
+fun connect(host: Str, port: I64 = 80, secure: Bool = false): I64 {
+    if (secure) {
+        return port + 1
+    }
+    return port
+}
+
+fun main() {
+    val host = "example.com"
+    val endpoint = Endpoint(.host)
+    println("{} {} {}", connect("x"), connect(port = 8080, host = "x"), connect(.host, secure = true))
+    println("{} {}", endpoint.host, endpoint.port)
+}"#,
+        &TestContext::default().with_name("default_arguments_compile"),
+    )
+    .expect("code with default arguments should compile");
+}
+
+#[test]
+fn test_argument_binding_errors() {
+    assert_type_check_error(
+        r#"fun connect(host: Str, port: I64 = 80): I64 {
+    return port
+}
+
+fun main() {
+    val p = connect(port = 1)
+}"#,
+        &TestContext::default()
+            .expect_error("Invalid arguments for 'connect': missing argument for parameter 'host'"),
+    );
+
+    assert_type_check_error(
+        r#"fun connect(host: Str, port: I64 = 80): I64 {
+    return port
+}
+
+fun main() {
+    val p = connect("x", timeout = 1)
+}"#,
+        &TestContext::default().expect_error("no parameter named 'timeout'"),
+    );
+
+    assert_type_check_error(
+        r#"fun connect(host: Str, port: I64 = 80): I64 {
+    return port
+}
+
+fun main() {
+    val p = connect("x", host = "y")
+}"#,
+        &TestContext::default().expect_error("parameter 'host' is given more than once"),
+    );
+
+    assert_type_check_error(
+        r#"fun connect(host: Str, port: I64 = 80): I64 {
+    return port
+}
+
+fun main() {
+    val p = connect(port = 1, "x")
+}"#,
+        &TestContext::default()
+            .expect_error("positional arguments must come before named arguments"),
+    );
+
+    assert_type_check_error(
+        r#"fun connect(host: Str, port: I64 = "eighty"): I64 {
+    return port
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+
+    assert_type_check_error(
+        r#"data class Endpoint(val host: Str, val port: I64 = 80)
+
+fun main() {
+    val e = Endpoint(port = 1)
+}"#,
+        &TestContext::default().expect_error("missing argument for parameter 'host'"),
+    );
+}
+
+#[test]
+fn test_out_of_order_named_arguments_with_side_effects() {
+    // Calls written out of parameter order are bound to temporaries in source order
+    let code = r#"fun first(): I64 {
+    println("first")
+    return 1
+}
+
+fun next(): I64 {
+    println("next")
+    return 2
+}
+
+fun triple(a: I64, b: I64, c: I64): I64 {
+    return a * 100 + b * 10 + c
+}
+
+fun main() {
+    val one = 1
+    val p = triple(first(), c = first(), b = next())
+    val q = triple(one, c = next(), b = 3)
+    val r = triple(a = first(), b = next(), c = one)
+}"#;
+    assert_transpilation_match(
+        code,
+        r#"fn first() -> i64 {
+    println!("first");
+    return 1;
+}
+fn next() -> i64 {
+    println!("next");
+    return 2;
+}
+fn triple(a: i64, b: i64, c: i64) -> i64 {
+    return a * 100 + b * 10 + c;
+}
+fn main() {
+    let one = 1;
+    let p = { let __a = first(); let __c = first(); let __b = next(); triple(__a, __b, __c) };
+    let q = triple(one, 3, next());
+    let r = triple(first(), next(), one);
+}"#,
+        &TestContext::default(),
+    );
+    transpile_and_compile(
+        code,
+        &TestContext::default().with_name("reordered_named_arguments_with_side_effects"),
+    )
+    .expect("reordered arguments with side effects should compile");
+}
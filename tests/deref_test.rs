@@ -0,0 +1,130 @@
+//! Tests for the `.deref()` operator method and assignment through MutRef
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, parse_and_type_check,
+    transpile_and_compile, TestContext,
+};
+use veltrano::config::Config;
+
+#[test]
+fn test_deref_strips_one_reference_level() {
+    assert_transpilation_match(
+        r#"fun read(count: Ref<I64>, name: Ref<String>, slot: MutRef<I64>): I64 {
+    val copied: String = name.deref()
+    println("{}", copied)
+    return count.deref() + slot.deref()
+}"#,
+        r#"fn read<'a>(bump: &'a bumpalo::Bump, count: &'a i64, name: &'a &'a String, slot: &'a mut i64) -> i64 {
+    let copied: &'a String = *name;
+    println!("{}", copied);
+    return *count + *slot;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_assignment_through_mut_ref() {
+    assert_transpilation_match(
+        r#"fun increment(counter: MutRef<I64>) {
+    counter.deref() = counter.deref() + 1
+}"#,
+        r#"fn increment(counter: &mut i64) {
+    *counter = *counter + 1;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_deref_compiles() {
+    transpile_and_compile(
+        r#"fun increment(counter: MutRef<I64>) {
+    val current = counter.deref()
+    counter.deref() = current + 1
+}
+
+fun rename(name: MutRef<Own<String>>, replacement: Own<String>) {
+    name.deref() = replacement
+}
+
+fun main() {
+    val counter = MutRef(41)
+    increment(counter)
+    val name = MutRef("Ada".toString())
+    rename(name, "Grace".toString())
+    println("{} {}", counter.deref(), name)
+}"#,
+        &TestContext::default().with_name("deref_compile"),
+    )
+    .expect("deref should compile");
+}
+
+#[test]
+fn test_deref_receiver_is_parenthesized() {
+    let code = r#"data class Point(val x: I64, val y: I64) {
+    fun sum(): I64 {
+        return this.x + this.y
+    }
+}
+
+fun read(p: Ref<Point>, values: Ref<Array<I64, 2>>): I64 {
+    val x = p.deref().x
+    return x + p.deref().sum() + values.deref()[1]
+}
+
+fun main() {
+    val p = Point(x = 1, y = 2)
+    val values = arrayOf(3, 4)
+    println("{}", read(p.ref(), values.ref()))
+}"#;
+    let rust_code = transpile_and_compile(
+        code,
+        &TestContext::default().with_name("deref_receiver_compile"),
+    )
+    .expect("p.deref().x should compile");
+    assert!(rust_code.contains("let x = (*p).x;"));
+    assert!(rust_code.contains("return x + (*p).sum() + (*values)[1];"));
+}
+
+#[test]
+fn test_deref_rejects_non_reference() {
+    assert_type_check_error(
+        "fun main() {\n    val n = 5\n    val m = n.deref()\n}",
+        &TestContext::default().expect_error("i64 is not a reference"),
+    );
+}
+
+#[test]
+fn test_deref_rejects_non_copy_pointee() {
+    for code in [
+        "fun take(name: Ref<Own<String>>) {\n    val moved = name.deref()\n}",
+        "fun take(name: String) {\n    val moved = name.deref()\n}",
+    ] {
+        let error = parse_and_type_check(code, Config::default())
+            .expect_err("deref of a non-Copy pointee should fail");
+        assert!(error.message.contains("does not implement Copy"));
+        assert!(error
+            .context
+            .help
+            .is_some_and(|help| help.contains(".clone()")));
+    }
+}
+
+#[test]
+fn test_assignment_requires_mut_ref_deref() {
+    assert_type_check_error(
+        "fun set(slot: Ref<I64>) {\n    slot.deref() = 5\n}",
+        &TestContext::default().expect_error("not a mutable reference"),
+    );
+    assert_type_check_error(
+        "fun set(slot: MutRef<I64>) {\n    slot = 5\n}",
+        &TestContext::default().expect_error("only `.deref()` of a MutRef"),
+    );
+    assert_type_check_error(
+        "fun set(slot: MutRef<I64>) {\n    slot.deref() = true\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+}
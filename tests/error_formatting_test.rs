@@ -36,6 +36,22 @@ fn test_error_formatter_with_context() {
     assert!(output.contains("help: Try: x: i32"));
 }
 
+#[test]
+fn test_error_formatter_with_related_location() {
+    let source = "val s = name\nconsume(s)\nprintln(s)";
+    let error = VeltranoError::new(ErrorKind::TypeError, "Use of moved value 's'")
+        .with_span(Span::single(SourceLocation::new(3, 9)))
+        .with_related(Span::single(SourceLocation::new(2, 9)), "value moved here");
+
+    let formatter = ErrorFormatter::new(&error, source).with_color(false);
+
+    let output = formatter.format();
+
+    assert!(output.contains("3 | println(s)"));
+    assert!(output.contains("note: 2:9: value moved here"));
+    assert!(output.contains("2 | consume(s)"));
+}
+
 #[test]
 fn test_error_formatter_multiline_span() {
     let source = "fun test(\n    x\n) { x }";
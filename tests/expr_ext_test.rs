@@ -18,6 +18,7 @@ fn test_walk_pre_order() {
         operator: BinaryOp::Add,
         comment_after_operator: None,
         right: Box::new(loc(Expr::Literal(LiteralExpr::Int(2)))),
+        id: 0,
     }));
 
     let mut visited = Vec::new();
@@ -47,6 +48,7 @@ fn test_walk_post_order() {
         operator: BinaryOp::Add,
         comment_after_operator: None,
         right: Box::new(loc(Expr::Literal(LiteralExpr::Int(2)))),
+        id: 0,
     }));
 
     let mut visited = Vec::new();
@@ -79,6 +81,7 @@ fn test_walk_early_exit() {
                 operator: BinaryOp::Add,
                 comment_after_operator: None,
                 right: Box::new(loc(Expr::Literal(LiteralExpr::Int(2)))),
+                id: 0,
             })),
             veltrano::ArgumentComment {
                 before: None,
@@ -116,6 +119,8 @@ fn test_find_subexpressions() {
         ],
         inline_comment: None,
         id: 0,
+        named_args: vec![],
+        is_multiline: false,
     }));
 
     // Find all identifiers
@@ -142,6 +147,7 @@ fn test_any_subexpr() {
         operator: BinaryOp::Add,
         comment_after_operator: None,
         right: Box::new(loc(Expr::Identifier("x".to_string()))),
+        id: 0,
     }));
 
     // Check if any sub-expression is an integer literal
@@ -160,6 +166,7 @@ fn test_all_subexprs() {
         operator: BinaryOp::Add,
         comment_after_operator: None,
         right: Box::new(loc(Expr::Identifier("b".to_string()))),
+        id: 0,
     }));
 
     // Check if all sub-expressions are identifiers or binary
@@ -182,6 +189,7 @@ fn test_complex_traversal() {
                     operator: BinaryOp::Add,
                     comment_after_operator: None,
                     right: Box::new(loc(Expr::Identifier("b".to_string()))),
+                    id: 0,
                 })),
                 veltrano::ArgumentComment {
                     before: None,
@@ -195,6 +203,8 @@ fn test_complex_traversal() {
                     args: vec![loc(Expr::Identifier("d".to_string()))],
                     inline_comment: None,
                     id: 0,
+                    named_args: vec![],
+                    is_multiline: false,
                 })),
                 veltrano::ArgumentComment {
                     before: None,
@@ -0,0 +1,208 @@
+//! Tests for function types and function references
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_function_types_and_references() {
+    assert_transpilation_match(
+        r#"fun add(a: I64, b: I64): I64 {
+    return a + b
+}
+
+fun apply(op: (I64, I64) -> I64, x: I64, y: I64): I64 {
+    return op(x, y)
+}
+
+fun runTwice(action: () -> Unit) {
+    action()
+    action()
+}
+
+fun tick() {
+    println("tick")
+}
+
+fun main() {
+    val plus: (I64, I64) -> I64 = ::add
+    println("{}", apply(plus, 1, 2))
+    println("{}", apply(::add, 3, 4))
+    runTwice(::tick)
+}"#,
+        r#"fn add(a: i64, b: i64) -> i64 {
+    return a + b;
+}
+fn apply(op: impl Fn(i64, i64) -> i64, x: i64, y: i64) -> i64 {
+    return op(x, y);
+}
+fn run_twice(action: impl Fn()) {
+    action();
+    action();
+}
+fn tick() {
+    println!("tick");
+}
+fn main() {
+    let plus: fn(i64, i64) -> i64 = add;
+    println!("{}", apply(plus, 1, 2));
+    println!("{}", apply(add, 3, 4));
+    run_twice(tick);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_references_to_bump_functions_and_members() {
+    assert_transpilation_match(
+        r#"fun greet(name: Str): Str {
+    return name
+}
+
+data class Point(val x: I64) {
+    fun scaled(factor: I64): I64 {
+        return this.x * factor
+    }
+}
+
+fun main() {
+    val hello: (Str) -> Str = ::greet
+    println("{}", hello("hi"))
+    val scale = Point::scaled
+    println("{}", scale(Point(x = 2), 3))
+}"#,
+        r#"fn greet<'a>(bump: &'a bumpalo::Bump, name: &'a str) -> &'a str {
+    return name;
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+}
+
+impl Point {
+    pub fn scaled(&self, factor: i64) -> i64 {
+        return self.x * factor;
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={})", self.x)
+    }
+}
+
+fn main() {
+    let bump = &bumpalo::Bump::new();
+    let hello = move |name| greet(bump, name);
+    println!("{}", hello("hi"));
+    let scale = |receiver: Point, factor| receiver.scaled(factor);
+    println!("{}", scale(Point { x: 2 }, 3));
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_function_references_compile() {
+    transpile_and_compile(
+        r#"fun greet(name: Str): Str {
+    return name
+}
+
+fun transform(f: (Str) -> Str, s: Str): Str {
+    return f(s)
+}
+
+fun double(x: I64): I64 {
+    return x * 2
+}
+
+data class Point(val x: I64) {
+    fun scaled(factor: I64): I64 {
+        return this.x * factor
+    }
+}
+
+data class Person(val name: String, val age: I64) {
+    fun nextAge(): I64 {
+        return this.age + 1
+    }
+}
+
+data class Handler(val onValue: (I64) -> I64)
+
+fun pickDouble(): (I64) -> I64 {
+    return ::double
+}
+
+fun main() {
+    println("{}", transform(::greet, "hi"))
+    val doubler = ::double
+    val handler = Handler(onValue = doubler)
+    val twice = pickDouble()
+    println("{}", twice(4))
+    val scale: (Point, I64) -> I64 = Point::scaled
+    println("{}", scale(Point(x = 2), 3))
+    val older = Person::nextAge
+    val person = Person(name = "Ada".toString().bumpRef(), age = 36)
+    println("{}", older(person.ref()))
+}"#,
+        &TestContext::default().with_name("function_references_compile"),
+    )
+    .expect("function references should compile");
+}
+
+#[test]
+fn test_function_value_errors() {
+    assert_type_check_error(
+        "fun apply(op: (I64) -> I64): I64 {\n    return op(\"one\")\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+    assert_type_check_error(
+        "fun apply(op: (I64) -> I64): I64 {\n    return op(1, 2)\n}",
+        &TestContext::default()
+            .expect_error("Function 'op' expects 1 arguments, but 2 were provided"),
+    );
+    assert_type_check_error(
+        "fun main() {\n    val f = ::missing\n}",
+        &TestContext::default().expect_error("Function 'missing' not found"),
+    );
+    assert_type_check_error(
+        "fun greet(name: Str): Str {\n    return name\n}\n\nfun main() {\n    val f: (I64) -> I64 = ::greet\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+    assert_type_check_error(
+        "extern \"C\" fun labs(x: I64): I64\n\nfun main() {\n    val f = ::labs\n}",
+        &TestContext::default().expect_error(
+            "Cannot reference function 'labs': unsafe functions can only be called inside an unsafe block",
+        ),
+    );
+    assert_type_check_error(
+        "data class Point(val x: I64)\n\nfun main() {\n    val f = Point::norm\n}",
+        &TestContext::default().expect_error("norm"),
+    );
+}
+
+#[test]
+fn test_bump_function_references_stay_parameters() {
+    // A reference to a function taking a bump captures it, which a function pointer can't
+    let functions = "fun greet(name: Str): Str {\n    return name\n}\n\ndata class Handler(val onName: (Str) -> Str)\n\n";
+    assert_type_check_error(
+        &format!("{}fun pick(): (Str) -> Str {{\n    return ::greet\n}}", functions),
+        &TestContext::default().expect_error(
+            "Cannot reference function 'greet': it takes a bump parameter, so its reference captures the caller's bump and can't be returned",
+        ),
+    );
+    assert_type_check_error(
+        &format!(
+            "{}fun main() {{\n    val hello = ::greet\n    val handler = Handler(onName = hello)\n}}",
+            functions
+        ),
+        &TestContext::default().expect_error(
+            "Cannot reference function 'greet': it takes a bump parameter, so its reference captures the caller's bump and can't be stored in a data class field",
+        ),
+    );
+}
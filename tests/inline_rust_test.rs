@@ -0,0 +1,113 @@
+//! Tests for `rust { ... }` blocks and `rust<T>("...")` expressions
+
+mod common;
+
+use common::{
+    assert_parse_error, assert_transpilation_match, assert_type_check_error, transpile_and_compile,
+    TestContext,
+};
+
+#[test]
+fn test_rust_blocks_at_item_and_statement_level() {
+    assert_transpilation_match(
+        r#"rust {
+    fn clamp(x: i64) -> i64 {
+        x.max(0).min(10)
+    }
+}
+
+fun main() {
+    val rawValue = 42
+    rust {
+        let clamped = clamp(rawValue);
+        println!("{}", clamped);
+    }
+}"#,
+        r#"fn clamp(x: i64) -> i64 {
+    x.max(0).min(10)
+}
+fn main() {
+    let raw_value = 42;
+    let clamped = clamp(raw_value);
+    println!("{}", clamped);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_rust_expression_with_declared_type() {
+    assert_transpilation_match(
+        r#"fun addWrapping(leftSide: I64, rightSide: I64): I64 {
+    val sum: I64 = rust<I64>("leftSide.wrapping_add(rightSide)")
+    return sum
+}"#,
+        r#"fn add_wrapping(left_side: i64, right_side: i64) -> i64 {
+    let sum: i64 = left_side.wrapping_add(right_side);
+    return sum;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_embedded_literals_and_comments_are_not_renamed() {
+    // Only identifiers declared in Veltrano are renamed; braces in literals don't end the block
+    assert_transpilation_match(
+        r#"fun main() {
+    val userName = "a"
+    rust {
+        let label = "userName {";
+        let brace = '}'; // userName }
+        println!("{} {} {}", label, brace, userName);
+    }
+}"#,
+        r#"fn main() {
+    let user_name = "a";
+    let label = "userName {";
+    let brace = '}'; // userName }
+    println!("{} {} {}", label, brace, user_name);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_inline_rust_compiles() {
+    transpile_and_compile(
+        r#"rust {
+    fn checked_double(x: i64) -> Option<i64> {
+        x.checked_mul(2)
+    }
+}
+
+fun main() {
+    val bigValue = rust<I64>("i64::MAX")
+    val doubled = rust<Bool>("checked_double(bigValue).is_none()")
+    rust {
+        assert!(doubled);
+    }
+    println("{} {}", bigValue, doubled)
+}"#,
+        &TestContext::default().with_name("inline_rust_compile"),
+    )
+    .expect("inline Rust code should compile");
+}
+
+#[test]
+fn test_inline_rust_errors() {
+    // The declared type is what the type checker sees
+    assert_type_check_error(
+        r#"fun main() {
+    val x: Bool = rust<I64>("1 + 1")
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+
+    assert_parse_error(
+        r#"fun main() {
+    val x = rust<I64>(y)
+}"#,
+        &TestContext::default().expect_error("Expected string literal with Rust code"),
+    );
+}
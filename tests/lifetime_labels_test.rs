@@ -0,0 +1,207 @@
+//! Tests for explicit lifetime labels (`fun<@a>`, `Ref<Str@a>`, `data class Name<@a>`)
+
+mod common;
+
+use common::{
+    assert_parse_error, assert_transpilation_match, assert_type_check_error, transpile_and_compile,
+    TestContext,
+};
+
+#[test]
+fn test_labeled_function_gets_bump_per_allocated_label() {
+    // Only labels the body allocates into take a bump parameter
+    assert_transpilation_match(
+        r#"fun<@a> createRef(value: Str): Ref<Str@a> {
+    return value.bumpRef()
+}
+
+fun<@a, @b> pickSecond(first: Ref<Str@a>, second: Ref<Str@b>): Ref<Str@b> {
+    return second
+}"#,
+        r#"fn create_ref<'a>(__bump_a: &'a bumpalo::Bump, value: &'a str) -> &'a &'a str {
+    return __bump_a.alloc(value);
+}
+fn pick_second<'a, 'b>(first: &'a &'a str, second: &'b &'b str) -> &'b &'b str {
+    return second;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_calls_pass_the_bump_of_the_bound_lifetime() {
+    // Labels bound by the arguments pick the caller's arena, unbound ones the
+    // arena of the returned lifetime
+    assert_transpilation_match(
+        r#"fun<@a> createRef(value: Str): Ref<Str@a> {
+    return value.bumpRef()
+}
+
+fun<@a> echo(value: Str@a): Ref<Str@a> {
+    return value.bumpRef()
+}
+
+fun<@a, @b> wrap(x: Str@a, y: Str@b): Ref<Str@b> {
+    val kept = echo(x)
+    return createRef(y)
+}"#,
+        r#"fn create_ref<'a>(__bump_a: &'a bumpalo::Bump, value: &'a str) -> &'a &'a str {
+    return __bump_a.alloc(value);
+}
+fn echo<'a>(__bump_a: &'a bumpalo::Bump, value: &'a str) -> &'a &'a str {
+    return __bump_a.alloc(value);
+}
+fn wrap<'a, 'b>(__bump_a: &'a bumpalo::Bump, __bump_b: &'b bumpalo::Bump, x: &'a str, y: &'b str) -> &'b &'b str {
+    let kept = echo(__bump_a, x);
+    return create_ref(__bump_b, y);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_own_lifetime_uses_local_arena() {
+    assert_transpilation_match(
+        r#"fun<@a> keep(value: Ref<Str@a>): Ref<Str@a> {
+    val scratch: Ref@keep<Ref<Str@a>> = value.bumpRef()
+    return value
+}"#,
+        r#"fn keep<'a>(value: &'a &'a str) -> &'a &'a str {
+    let bump = &bumpalo::Bump::new();
+    let scratch: &'_ &'a &'a str = bump.alloc(value);
+    return value;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_labeled_data_class() {
+    assert_transpilation_match(
+        r#"data class Pair2<@a, @b>(val first: Ref<Str@a>, val second: Ref<Str@b>, val count: I64)
+
+fun<@x> firstOf(pair: Pair2@x): Ref<Str@x> {
+    return pair.first
+}"#,
+        r#"#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pair2<'a, 'b> {
+    pub first: &'a &'a str,
+    pub second: &'b &'b str,
+    pub count: i64,
+}
+
+impl<'a, 'b> std::fmt::Display for Pair2<'a, 'b> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pair2(first={}, second={}, count={})", self.first, self.second, self.count)
+    }
+}
+
+fn first_of<'x>(pair: &'x Pair2<'x, 'x>) -> &'x &'x str {
+    return pair.first;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_lifetime_labels_compile() {
+    transpile_and_compile(
+        r#"data class Container<@a>(val text: Ref<Str@a>, val count: I64)
+
+fun<@a> createRef(value: Str): Ref<Str@a> {
+    return value.bumpRef()
+}
+
+fun<@a, @b> pickSecond(first: Ref<Str@a>, second: Ref<Str@b>): Ref<Str@b> {
+    val scratch = first.bumpRef()
+    return second
+}
+
+fun<@x> makeContainer(s: Str): Container@x {
+    return Container(text = s.bumpRef(), count = 1).bumpRef()
+}
+
+fun<@a> same(value: Str@a): Str@a {
+    return value
+}
+
+fun main() {
+    val r = createRef("hello")
+    val c = makeContainer("world")
+    val pass = ::same
+    println("{} {} {} {}", pickSecond(r, r), c.text, c.count, pass("again"))
+}"#,
+        &TestContext::default().with_name("lifetime_labels_compile"),
+    )
+    .expect("labeled code should compile");
+}
+
+#[test]
+fn test_lifetime_mismatch_errors() {
+    assert_type_check_error(
+        r#"fun<@a, @b> pick(first: Ref<Str@a>, second: Ref<Str@b>): Ref<Str@a> {
+    return second
+}"#,
+        &TestContext::default()
+            .expect_error("Lifetime mismatch: expected a reference with lifetime @a, found @b"),
+    );
+
+    // Unlabeled references in a labeled signature take the first label
+    assert_type_check_error(
+        r#"fun<@a, @b> pick(first: Ref<Str>, second: Ref<Str@b>): Ref<Str@b> {
+    return first
+}"#,
+        &TestContext::default().expect_error("Lifetime mismatch"),
+    );
+
+    assert_type_check_error(
+        r#"fun<@a, @b> example(ref1: Ref<Str@a>) {
+    val ref2: Ref<Str@b> = ref1
+}"#,
+        &TestContext::default().expect_error("expected a reference with lifetime @b, found @a"),
+    );
+}
+
+#[test]
+fn test_own_lifetime_cannot_escape() {
+    assert_type_check_error(
+        r#"fun badExample(value: Str): Ref<Str@badExample> {
+    return value.bumpRef()
+}"#,
+        &TestContext::default().expect_error("cannot escape"),
+    );
+
+    assert_type_check_error(
+        r#"fun leak(value: Ref<Str>): Ref<Str> {
+    val local: Ref<Str@leak> = value
+    return local
+}"#,
+        &TestContext::default().expect_error("cannot escape"),
+    );
+}
+
+#[test]
+fn test_invalid_lifetime_labels() {
+    assert_type_check_error(
+        r#"fun<@a> f(value: Ref<Str@b>) {
+}"#,
+        &TestContext::default().expect_error("Invalid lifetime label @b: lifetime is not declared"),
+    );
+
+    assert_type_check_error(
+        r#"data class Holder<@a>(val value: Ref<Str@b>)"#,
+        &TestContext::default().expect_error("Invalid lifetime label @b"),
+    );
+
+    assert_type_check_error(
+        r#"fun<@a> f(value: I64@a) {
+}"#,
+        &TestContext::default().expect_error("only reference types can carry a lifetime label"),
+    );
+
+    assert_parse_error(
+        r#"fun<a> f() {
+}"#,
+        &TestContext::default().expect_error("Expected '@' before lifetime parameter"),
+    );
+}
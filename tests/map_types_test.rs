@@ -0,0 +1,127 @@
+//! Tests for map and set types, map literals and map indexing
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+use veltrano::rust_interop::RustInteropRegistry;
+use veltrano::types::VeltranoType;
+
+#[test]
+fn test_map_types_map_to_rust() {
+    let mut registry = RustInteropRegistry::new();
+    let cases = [
+        (
+            VeltranoType::hash_map(VeltranoType::i64(), VeltranoType::bool()),
+            "&std::collections::HashMap<i64, bool>",
+        ),
+        (
+            VeltranoType::own(VeltranoType::btree_map(
+                VeltranoType::own(VeltranoType::string()),
+                VeltranoType::i64(),
+            )),
+            "std::collections::BTreeMap<String, i64>",
+        ),
+        (
+            VeltranoType::own(VeltranoType::hash_set(VeltranoType::str())),
+            "std::collections::HashSet<&str>",
+        ),
+    ];
+    for (veltrano_type, expected) in cases {
+        assert_eq!(
+            veltrano_type.to_rust_type(&mut registry).to_rust_syntax(),
+            expected
+        );
+        assert!(!veltrano_type.implements_copy(&mut registry));
+    }
+}
+
+#[test]
+fn test_map_and_set_literals() {
+    assert_transpilation_match(
+        r#"fun main() {
+    val ids = mapOf(1 to "one", 2 to "two")
+    val sorted = sortedMapOf(2 to true, 1 to false)
+    val tags = setOf(1, 2, 3)
+    val empty: Own<HashMap<I64, Bool>> = mapOf()
+    println("{:?} {:?} {:?} {:?}", ids, sorted, tags, empty)
+}"#,
+        r#"fn main() {
+    let ids = std::collections::HashMap::from([(1, "one"), (2, "two")]);
+    let sorted = std::collections::BTreeMap::from([(2, true), (1, false)]);
+    let tags = std::collections::HashSet::from([1, 2, 3]);
+    let empty: std::collections::HashMap<i64, bool> = std::collections::HashMap::new();
+    println!("{:?} {:?} {:?} {:?}", ids, sorted, tags, empty);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_map_indexing_and_methods() {
+    assert_transpilation_match(
+        r#"fun main() {
+    val ids = mapOf(1 to true)
+    val found: Option<Ref<Bool>> = ids[1]
+    val names: Own<HashMap<Own<String>, I64>> = mapOf("a".toString() to 1)
+    val a = names["a"]
+    println("{:?} {:?} {} {}", found, a, names.ref().containsKey("a"), names.ref().length())
+}"#,
+        r#"fn main() {
+    let ids = std::collections::HashMap::from([(1, true)]);
+    let found: Option<&bool> = ids.get(&1);
+    let names: std::collections::HashMap<String, i64> = std::collections::HashMap::from([(ToString::to_string("a"), 1)]);
+    let a = names.get("a");
+    println!("{:?} {:?} {} {}", found, a, std::collections::HashMap::contains_key(&names, "a"), std::collections::HashMap::len(&names));
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_map_types_compile() {
+    transpile_and_compile(
+        r#"fun lookup(scores: HashMap<Own<String>, I64>, tags: HashSet<I64>): I64 {
+    println("{:?} {}", scores["alice"], tags.contains(2.ref()))
+    return 1
+}
+
+fun count(ids: HashMap<I64, Bool>, tags: HashSet<I64>): Bool {
+    val k = 1
+    // Copy keys are borrowed for the Rust methods
+    println("{:?} {} {}", ids.get(k), ids.containsKey(2), tags.contains(1))
+    return ids.isEmpty()
+}
+
+data class Registry(val scores: Own<HashMap<Own<String>, I64>>)
+
+fun main() {
+    val scores = mapOf("alice".toString() to 3, "bob".toString() to 5)
+    val tags = setOf(1, 2)
+    println("{}", lookup(scores.ref(), tags.ref()))
+    val ids = mapOf(1 to true)
+    println("{}", count(ids.ref(), tags.ref()))
+    val registry = Registry(scores = scores)
+    println("{:?}", registry)
+}"#,
+        &TestContext::default().with_name("map_types_compile"),
+    )
+    .expect("map types should compile");
+}
+
+#[test]
+fn test_map_literal_requires_pairs() {
+    assert_type_check_error(
+        "fun main() {\n    val m = mapOf(1, 2)\n}",
+        &TestContext::default().expect_error("key to value"),
+    );
+}
+
+#[test]
+fn test_map_index_checks_key_type() {
+    assert_type_check_error(
+        "fun main() {\n    val m = mapOf(1 to true)\n    val v = m[true]\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+}
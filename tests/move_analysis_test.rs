@@ -0,0 +1,166 @@
+//! Tests for use-after-move checking of `Own<T>` values
+
+mod common;
+
+use common::{assert_type_check_error, parse_and_type_check, transpile_and_compile, TestContext};
+use veltrano::config::Config;
+
+const CONSUME: &str = "fun consume(s: Own<String>) {}\n";
+
+#[test]
+fn test_use_after_move_reports_both_locations() {
+    let code = format!(
+        "{}fun main() {{\n    val s = \"a\".toString()\n    consume(s)\n    println(\"{{}}\", s)\n}}",
+        CONSUME
+    );
+    let error = parse_and_type_check(&code, Config::default()).expect_err("s is used after move");
+    assert_eq!(error.message, "Use of moved value 's'");
+    let used_at = error.context.span.expect("use location");
+    assert_eq!((used_at.start.line, used_at.start.column), (5, 19));
+    let (moved_at, label) = error.context.related.expect("move location");
+    assert_eq!((moved_at.start.line, moved_at.start.column), (4, 13));
+    assert_eq!(label, "value moved here");
+    assert!(error
+        .context
+        .help
+        .is_some_and(|help| help.contains("s.ref()")));
+}
+
+#[test]
+fn test_second_move_suggests_clone() {
+    let code = format!(
+        "{}fun main() {{\n    val s = \"a\".toString()\n    val t = s\n    consume(s)\n}}",
+        CONSUME
+    );
+    let error = parse_and_type_check(&code, Config::default()).expect_err("s is moved twice");
+    assert!(error
+        .context
+        .help
+        .is_some_and(|help| help.contains("s.clone()")));
+}
+
+#[test]
+fn test_move_in_one_branch_is_a_move_after_the_if() {
+    assert_type_check_error(
+        &format!(
+            "{}fun main() {{\n    val s = \"a\".toString()\n    if (true) {{\n        consume(s)\n    }}\n    val t = s.ref()\n}}",
+            CONSUME
+        ),
+        &TestContext::default().expect_error("Use of moved value 's'"),
+    );
+}
+
+#[test]
+fn test_move_inside_loop() {
+    let code = format!(
+        "{}fun main() {{\n    val s = \"a\".toString()\n    while (true) {{\n        consume(s)\n    }}\n}}",
+        CONSUME
+    );
+    let error =
+        parse_and_type_check(&code, Config::default()).expect_err("s moves every iteration");
+    let (_, label) = error.context.related.expect("move location");
+    assert!(label.contains("previous iteration of the loop"));
+}
+
+#[test]
+fn test_operator_taking_owned_operand_by_value_moves_it() {
+    let code = "fun main() {\n    val a = \"a\".toString()\n    val b = \"b\".toString()\n    val c = a + b.ref()\n    println(\"{} {}\", a, c)\n}";
+    let error = parse_and_type_check(code, Config::default()).expect_err("a + &b moves a");
+    assert_eq!(error.message, "Use of moved value 'a'");
+    let (moved_at, _) = error.context.related.expect("move location");
+    assert_eq!((moved_at.start.line, moved_at.start.column), (4, 13));
+
+    transpile_and_compile(
+        "fun main() {\n    val a = \"a\".toString()\n    val b = \"b\".toString()\n    val c = a + b.ref()\n    println(\"{} {}\", b, c == b)\n}",
+        &TestContext::default().with_name("operator_move_compile"),
+    )
+    .expect("b is only borrowed by + and ==");
+}
+
+#[test]
+fn test_borrows_and_branch_local_moves_are_allowed() {
+    transpile_and_compile(
+        r#"fun consume(s: Own<String>) {}
+
+data class Person(val name: Own<String>, val age: I64)
+
+fun main() {
+    val s = "a".toString()
+    val copied = s.ref().clone()
+    println("{} {}", copied, s)
+    consume(copied)
+    val person = Person(name = s, age = 3)
+    println("{}", person.age)
+    val older = person.copy(age = 4)
+    val moved = person
+    if (older.age > 3) {
+        consume("b".toString())
+    } else {
+        val local = "c".toString()
+        consume(local)
+    }
+    while (false) {
+        val local = "d".toString()
+        consume(local)
+    }
+    println("{:?} {:?}", moved, older)
+}"#,
+        &TestContext::default().with_name("move_analysis_compile"),
+    )
+    .expect("code without use-after-move should compile");
+}
+
+#[test]
+fn test_moves_on_returning_paths_end_with_them() {
+    transpile_and_compile(
+        r#"fun consume(s: Own<String>) {}
+
+fun early(c: Bool) {
+    val a = "a".toString()
+    if (c) {
+        consume(a)
+        return
+    }
+    println("{}", a)
+}
+
+fun search(c: Bool) {
+    val e = "e".toString()
+    while (c) {
+        if (c) {
+            consume(e)
+            return
+        }
+    }
+    println("{}", e)
+}
+
+fun main() {
+    early(true)
+    search(false)
+}"#,
+        &TestContext::default().with_name("returning_moves_compile"),
+    )
+    .expect("a move followed by return doesn't reach later uses");
+}
+
+#[test]
+fn test_destructuring_moves_only_for_owned_components() {
+    let person = "data class Person(val name: Own<String>, val age: I64)\n\n";
+    transpile_and_compile(
+        &format!(
+            "{}fun main() {{\n    val p = Person(name = \"Ada\".toString(), age = 36)\n    val (_, age) = p\n    val (name, older) = p\n    println(\"{{}} {{}} {{}}\", name, age, older)\n}}",
+            person
+        ),
+        &TestContext::default().with_name("copy_destructuring_compile"),
+    )
+    .expect("binding only Copy fields leaves the value in place");
+
+    assert_type_check_error(
+        &format!(
+            "{}fun main() {{\n    val p = Person(name = \"Ada\".toString(), age = 36)\n    val (name, age) = p\n    println(\"{{:?}}\", p)\n}}",
+            person
+        ),
+        &TestContext::default().expect_error("Use of moved value 'p'"),
+    );
+}
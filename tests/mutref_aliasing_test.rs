@@ -0,0 +1,108 @@
+//! Tests for rejecting conflicting `.ref()` and `.mutRef()` borrows
+
+mod common;
+
+use common::{assert_type_check_error, parse_and_type_check, TestContext};
+use veltrano::config::Config;
+
+#[test]
+fn test_second_mut_ref_while_first_is_used_is_rejected() {
+    let code = "fun main() {\n    val x: I64 = 1\n    val first = x.mutRef()\n    val second = x.mutRef()\n    first.deref() = 2\n}";
+    let error = parse_and_type_check(code, Config::default()).expect_err("first is still used");
+    assert_eq!(
+        error.message,
+        "Cannot borrow 'x' as mutable because it is already borrowed as mutable"
+    );
+    let second_at = error.context.span.expect("second borrow location");
+    assert_eq!((second_at.start.line, second_at.start.column), (4, 18));
+    let (first_at, label) = error.context.related.expect("first borrow location");
+    assert_eq!((first_at.start.line, first_at.start.column), (3, 17));
+    assert_eq!(label, "first mutable borrow here, still used at 5:5");
+    assert!(error
+        .context
+        .help
+        .is_some_and(|help| help.contains("must be the only reference")));
+}
+
+#[test]
+fn test_mut_ref_while_ref_is_held_is_rejected() {
+    assert_type_check_error(
+        "fun main() {\n    val x: I64 = 1\n    val shared = x.ref()\n    val exclusive = x.mutRef()\n    println(\"{}\", shared)\n}",
+        &TestContext::default()
+            .expect_error("Cannot borrow 'x' as mutable because it is already borrowed as shared"),
+    );
+    assert_type_check_error(
+        "fun main() {\n    val x: I64 = 1\n    val exclusive = x.mutRef()\n    val copy = MutRef(x)\n    exclusive.deref() = 2\n}",
+        &TestContext::default()
+            .expect_error("Cannot borrow 'x' as shared because it is already borrowed as mutable"),
+    );
+}
+
+#[test]
+fn test_conflicting_borrows_in_one_statement_are_rejected() {
+    let code = "fun both(a: MutRef<I64>, b: MutRef<I64>) {\n}\n\nfun main() {\n    val x: I64 = 1\n    both(x.mutRef(), x.mutRef())\n}";
+    let error = parse_and_type_check(code, Config::default()).expect_err("both borrows are live");
+    let (_, label) = error.context.related.expect("first borrow location");
+    assert_eq!(label, "first mutable borrow here");
+}
+
+#[test]
+fn test_field_borrows_conflict_with_their_object() {
+    assert_type_check_error(
+        "data class Point(val x: I64, val y: I64)\n\nfun main() {\n    val p = Point(x = 1, y = 2)\n    val whole = p.mutRef()\n    val x = p.x.ref()\n    println(\"{:?}\", whole)\n}",
+        &TestContext::default()
+            .expect_error("Cannot borrow 'p.x' as shared because it is already borrowed as mutable"),
+    );
+}
+
+#[test]
+fn test_non_overlapping_borrows_are_accepted() {
+    let valid = [
+        // The first borrow is not used after the second one is made
+        "fun main() {\n    val x: I64 = 1\n    val shared = x.ref()\n    println(\"{}\", shared)\n    val exclusive = x.mutRef()\n    exclusive.deref() = 2\n}",
+        // Shared borrows never conflict with each other
+        "fun main() {\n    val x: I64 = 1\n    val a = x.ref()\n    val b = x.ref()\n    println(\"{} {}\", a, b)\n}",
+        // Distinct fields are borrowed separately
+        "data class Point(val x: I64, val y: I64)\n\nfun main() {\n    val p = Point(x = 1, y = 2)\n    val x = p.x.mutRef()\n    val y = p.y.mutRef()\n    println(\"{} {}\", x, y)\n}",
+        // A borrow held in a block ends with it
+        "fun main() {\n    val x: I64 = 1\n    {\n        val inner = x.mutRef()\n        inner.deref() = 2\n    }\n    val outer = x.mutRef()\n    outer.deref() = 3\n}",
+    ];
+    for code in valid {
+        assert!(
+            parse_and_type_check(code, Config::default()).is_ok(),
+            "expected no borrow conflict in:\n{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn test_borrows_held_across_loop_iterations_are_rejected() {
+    // `shared` is used again at the top of the next iteration
+    let code = "fun main() {\n    val x: I64 = 1\n    val shared = x.ref()\n    while (true) {\n        println(\"{}\", shared)\n        val exclusive = x.mutRef()\n        exclusive.deref() = 2\n    }\n}";
+    let error = parse_and_type_check(code, Config::default()).expect_err("shared is used again");
+    assert_eq!(
+        error.message,
+        "Cannot borrow 'x' as mutable because it is already borrowed as shared"
+    );
+    let (_, label) = error.context.related.expect("first borrow location");
+    assert_eq!(label, "first shared borrow here, still used at 5:23");
+
+    // Borrows made within the body end with it
+    let valid = "fun main() {\n    val x: I64 = 1\n    while (true) {\n        val shared = x.ref()\n        println(\"{}\", shared)\n        val exclusive = x.mutRef()\n        exclusive.deref() = 2\n    }\n}";
+    assert!(parse_and_type_check(valid, Config::default()).is_ok());
+}
+
+#[test]
+fn test_borrows_stored_in_values_stay_alive() {
+    assert_type_check_error(
+        "data class Holder(val r: Ref<I64>)\n\nfun main() {\n    val x: I64 = 1\n    val h = Holder(r = x.ref())\n    val exclusive = x.mutRef()\n    println(\"{:?}\", h)\n}",
+        &TestContext::default()
+            .expect_error("Cannot borrow 'x' as mutable because it is already borrowed as shared"),
+    );
+    assert_type_check_error(
+        "fun main() {\n    val x: I64 = 1\n    val pair = x.mutRef() to 2\n    val shared = x.ref()\n    println(\"{:?}\", pair)\n}",
+        &TestContext::default()
+            .expect_error("Cannot borrow 'x' as shared because it is already borrowed as mutable"),
+    );
+}
@@ -0,0 +1,299 @@
+//! Tests for operator overloading with `operator fun` members of data classes
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_arithmetic_operator_generates_ops_impl() {
+    // Copy data classes are operated on by value
+    assert_transpilation_match(
+        r#"data class Vector2(val x: I64, val y: I64) {
+    operator fun plus(other: Vector2): Vector2 {
+        return Vector2(x = this.x + other.x, y = this.y + other.y)
+    }
+}
+
+fun main() {
+    val a = Vector2(x = 1, y = 2)
+    val b = Vector2(x = 3, y = 4)
+    val sum = a + b
+    val total = a + b + sum
+}"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vector2 {
+    pub fn plus(&self, other: Vector2) -> Vector2 {
+        return Vector2 { x: self.x + other.x, y: self.y + other.y };
+    }
+}
+
+impl std::ops::Add<Vector2> for Vector2 {
+    type Output = Vector2;
+    fn add(self, other: Vector2) -> Vector2 {
+        self.plus(other)
+    }
+}
+
+impl std::fmt::Display for Vector2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Vector2(x={}, y={})", self.x, self.y)
+    }
+}
+
+fn main() {
+    let a = Vector2 { x: 1, y: 2 };
+    let b = Vector2 { x: 3, y: 4 };
+    let sum = a + b;
+    let total = a + b + sum;
+}"#,
+        &TestContext::default(),
+    );
+
+    // Owned operands of other data classes are borrowed for the impl on references
+    assert_transpilation_match(
+        r#"data class Tally(val counts: Own<Vec<I64>>) {
+    operator fun plus(other: Tally): I64 {
+        return this.counts[0] + other.counts[0]
+    }
+}
+
+fun main() {
+    val a = Tally(counts = vecOf(1, 2))
+    val b = Tally(counts = vecOf(3))
+    val total = a + b
+}"#,
+        r#"#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tally {
+    pub counts: Vec<i64>,
+}
+
+impl Tally {
+    pub fn plus(&self, other: &Tally) -> i64 {
+        return self.counts[0] + other.counts[0];
+    }
+}
+
+impl std::ops::Add<&Tally> for &Tally {
+    type Output = i64;
+    fn add(self, other: &Tally) -> i64 {
+        self.plus(other)
+    }
+}
+
+impl std::fmt::Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Tally(counts={:?})", self.counts)
+    }
+}
+
+fn main() {
+    let a = Tally { counts: vec![1, 2] };
+    let b = Tally { counts: vec![3] };
+    let total = &a + &b;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_comparison_and_index_operators() {
+    assert_transpilation_match(
+        r#"data class Version(val major: I64, val minor: I64) {
+    operator fun compareTo(other: Version): I64 {
+        return this.rank() - other.rank()
+    }
+
+    operator fun get(part: I64): I64 {
+        if (part == 0) {
+            return this.major
+        }
+        return this.minor
+    }
+
+    fun rank(): I64 {
+        return this.major * 1000 + this.minor
+    }
+}
+
+fun newer(a: Version, b: Version): Bool {
+    return a > b
+}"#,
+        r#"#[derive(Debug, Clone, Copy)]
+pub struct Version {
+    pub major: i64,
+    pub minor: i64,
+}
+
+impl Version {
+    pub fn compare_to(&self, other: Version) -> i64 {
+        return self.rank() - other.rank();
+    }
+    pub fn get(&self, part: i64) -> i64 {
+        if part == 0 {
+            return self.major;
+        }
+        return self.minor;
+    }
+    pub fn rank(&self) -> i64 {
+        return self.major * 1000 + self.minor;
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.compare_to(*other) == 0
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<std::cmp::Ordering> {
+        Some(self.compare_to(*other).cmp(&0))
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Version(major={}, minor={})", self.major, self.minor)
+    }
+}
+
+fn newer(a: Version, b: Version) -> bool {
+    return a > b;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_operators_compile() {
+    transpile_and_compile(
+        r#"data class Vector2(val x: I64, val y: I64) {
+    operator fun plus(other: Vector2): Vector2 {
+        return Vector2(x = this.x + other.x, y = this.y + other.y)
+    }
+
+    operator fun minus(other: Vector2): Vector2 {
+        return Vector2(x = this.x - other.x, y = this.y - other.y)
+    }
+
+    operator fun times(factor: I64): Vector2 {
+        return Vector2(x = this.x * factor, y = this.y * factor)
+    }
+
+    operator fun unaryMinus(): Vector2 {
+        return Vector2(x = -this.x, y = -this.y)
+    }
+
+    operator fun equals(other: Vector2): Bool {
+        return this.x == other.x && this.y == other.y
+    }
+
+    operator fun compareTo(other: Vector2): I64 {
+        return this.lengthSquared() - other.lengthSquared()
+    }
+
+    fun lengthSquared(): I64 {
+        return this.x * this.x + this.y * this.y
+    }
+}
+
+data class Grid(val cells: Own<Vec<I64>>) {
+    operator fun get(i: USize): Ref<I64> {
+        return this.cells[i].ref()
+    }
+}
+
+fun main() {
+    val a = Vector2(x = 1, y = 2)
+    val b = Vector2(x = 3, y = 4)
+    val c = -(a + b) * 2 - a
+    val grid = Grid(cells = vecOf(10, 20, 30))
+    println("{} {} {} {} {}", c.x, c.y, a < b, a == a, grid[1])
+    println("{}", a.plus(b).lengthSquared())
+}"#,
+        &TestContext::default().with_name("operators_compile"),
+    )
+    .expect("code with operator functions should compile");
+}
+
+#[test]
+fn test_equals_with_derived_partial_eq_compiles() {
+    transpile_and_compile(
+        r#"@Derive(PartialEq, Eq)
+data class Name(val first: Own<String>, val last: Own<String>) {
+    operator fun equals(other: Name): Bool {
+        return this.last == other.last
+    }
+}
+
+fun main() {
+    val a = Name(first = "Ada".toString(), last = "Lovelace".toString())
+    val b = Name(first = "Augusta".toString(), last = "Lovelace".toString())
+    println("{}", a == b)
+}"#,
+        &TestContext::default().with_name("equals_with_derived_partial_eq"),
+    )
+    .expect("a user-defined equals replaces the derived PartialEq");
+}
+
+#[test]
+fn test_operator_errors() {
+    assert_type_check_error(
+        r#"data class Point(val x: I64) {
+    operator fun power(other: Point): Point {
+        return Point(x = this.x)
+    }
+}"#,
+        &TestContext::default()
+            .expect_error("Invalid operator function 'Point.power': not an overloadable operator"),
+    );
+
+    assert_type_check_error(
+        r#"data class Point(val x: I64) {
+    operator fun plus(): Point {
+        return Point(x = this.x)
+    }
+}"#,
+        &TestContext::default().expect_error("must take exactly 1 parameter"),
+    );
+
+    assert_type_check_error(
+        r#"data class Point(val x: I64) {
+    operator fun equals(other: Point): I64 {
+        return 0
+    }
+}"#,
+        &TestContext::default().expect_error("must take a Point and return"),
+    );
+
+    assert_type_check_error(
+        r#"data class Point(val x: I64)
+
+fun main() {
+    val a = Point(x = 1)
+    val b = a - a
+}"#,
+        &TestContext::default().expect_error("Operator '-' is not supported"),
+    );
+
+    assert_type_check_error(
+        r#"data class Point(val x: I64) {
+    operator fun plus(other: Point): Point {
+        return Point(x = this.x + other.x)
+    }
+}
+
+fun main() {
+    val a = Point(x = 1)
+    val b = a + 1
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+}
@@ -336,36 +336,3 @@ fn test_nested_expressions() {
     let program = result.unwrap();
     assert_eq!(program.statements.len(), 3);
 }
-
-#[test]
-fn test_bump_allocation_analysis() {
-    // Test that bump allocation analysis works correctly
-    let source = r#"
-        fun outer(): Vec<String> {
-            vec()
-        }
-        
-        fun inner() {
-            val v = outer()
-            v.push("test")
-        }
-    "#;
-
-    let result = parse(source);
-    if let Err(e) = &result {
-        eprintln!("Parse error in bump test: {}", e);
-    }
-    assert!(result.is_ok());
-    let program = result.unwrap();
-
-    // Check that bump allocation flags are set correctly
-    for stmt in &program.statements {
-        if let Stmt::FunDecl(fun_decl) = stmt {
-            match fun_decl.name.as_str() {
-                "outer" => assert!(fun_decl.has_hidden_bump),
-                "inner" => assert!(fun_decl.has_hidden_bump),
-                _ => {}
-            }
-        }
-    }
-}
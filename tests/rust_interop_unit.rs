@@ -1303,3 +1303,51 @@ fn test_rustdoc_method_extraction() {
     assert_eq!(set_method.parameters[0].name, "value");
     assert_eq!(set_method.return_type.raw, "()");
 }
+
+#[test]
+fn test_operator_impl_resolution() {
+    let registry = RustInteropRegistry::new();
+    let str_ref = RustType::Ref {
+        lifetime: Some("'a".to_string()),
+        inner: Box::new(RustType::Str),
+    };
+    let string_ref = RustType::Ref {
+        lifetime: None,
+        inner: Box::new(RustType::String),
+    };
+
+    // impl Add<&str> for String is the only Add impl for String
+    let impls = registry.query_operator_impls("Add", &RustType::String);
+    assert_eq!(impls.len(), 1);
+    assert_eq!(impls[0].output, RustType::String);
+
+    // Lifetimes don't affect which impl applies
+    let found = registry
+        .resolve_operator_impl("Add", &RustType::String, &str_ref)
+        .unwrap();
+    assert!(!found.borrow_left && !found.borrow_right);
+    assert_eq!(found.output, RustType::String);
+
+    // An owned String is borrowed and deref-coerced to &str
+    let found = registry
+        .resolve_operator_impl("Add", &RustType::String, &RustType::String)
+        .unwrap();
+    assert!(!found.borrow_left && found.borrow_right);
+
+    // &String has no Add impl
+    assert!(registry
+        .resolve_operator_impl("Add", &string_ref, &str_ref)
+        .is_none());
+
+    // impl PartialEq<&B> for &A needs the owned operand borrowed
+    let found = registry
+        .resolve_operator_impl("PartialEq", &string_ref, &RustType::String)
+        .unwrap();
+    assert!(!found.borrow_left && found.borrow_right);
+    assert_eq!(found.output, RustType::Bool);
+
+    // String only orders against String
+    assert!(registry
+        .resolve_operator_impl("PartialOrd", &RustType::String, &str_ref)
+        .is_none());
+}
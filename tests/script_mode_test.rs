@@ -0,0 +1,94 @@
+//! Tests for script mode, where top-level statements are wrapped in `fn main()`
+
+mod common;
+
+use common::error_assertions::assert_error_location;
+use common::{assert_parse_error, assert_transpilation_match, transpile_and_compile, TestContext};
+use veltrano::config::Config;
+
+#[test]
+fn test_script_hoists_declarations() {
+    assert_transpilation_match(
+        r#"#!/usr/bin/env veltrano
+// Doubles a number
+fun double(x: I64): I64 {
+    return x * 2
+}
+
+// Start from one
+val start = 1
+println("{}", double(start))
+
+data class Point(val x: I64)
+val point = Point(x = double(2))
+// All done"#,
+        r#"// Doubles a number
+fn double(x: i64) -> i64 {
+    return x * 2;
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={})", self.x)
+    }
+}
+
+fn main() {
+    // Start from one
+    let start = 1;
+    println!("{}", double(start));
+    let point = Point { x: double(2) };
+    // All done
+}"#,
+        &TestContext::with_config(Config {
+            preserve_comments: true,
+        })
+        .script(),
+    );
+}
+
+#[test]
+fn test_script_without_statements_is_unchanged() {
+    assert_transpilation_match(
+        "fun main() {\n    println(\"hi\")\n}",
+        "fn main() {\n    println!(\"hi\");\n}",
+        &TestContext::default().script(),
+    );
+}
+
+#[test]
+fn test_script_compiles() {
+    transpile_and_compile(
+        r#"#!/usr/bin/env veltrano
+fun greet(name: Str): Str {
+    return name
+}
+
+val name = greet("script")
+if (name == "script") {
+    println("hello {}", name)
+}"#,
+        &TestContext::default().script().with_name("script_compiles"),
+    )
+    .expect("script should compile");
+}
+
+#[test]
+fn test_script_with_main_is_rejected() {
+    assert_parse_error(
+        "println(\"one\")\n\nfun main() {\n    println(\"two\")\n}",
+        &TestContext::default()
+            .script()
+            .expect_error("cannot also declare 'fun main'"),
+    );
+}
+
+#[test]
+fn test_shebang_keeps_line_numbers() {
+    let error = assert_parse_error("#!/usr/bin/env veltrano\nval = 1", &TestContext::default());
+    assert_error_location(&error, 2, 5);
+}
@@ -0,0 +1,91 @@
+//! Tests for reference-counted and interior-mutability types
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+use veltrano::rust_interop::RustInteropRegistry;
+use veltrano::types::VeltranoType;
+
+#[test]
+fn test_shared_types_map_to_rust() {
+    let mut registry = RustInteropRegistry::new();
+    let cases = [
+        (VeltranoType::rc(VeltranoType::i64()), "&std::rc::Rc<i64>"),
+        (
+            VeltranoType::own(VeltranoType::arc(VeltranoType::own(VeltranoType::string()))),
+            "std::sync::Arc<String>",
+        ),
+        (
+            VeltranoType::own(VeltranoType::rc(VeltranoType::own(VeltranoType::ref_cell(
+                VeltranoType::i64(),
+            )))),
+            "std::rc::Rc<std::cell::RefCell<i64>>",
+        ),
+        (
+            VeltranoType::own(VeltranoType::cell(VeltranoType::bool())),
+            "std::cell::Cell<bool>",
+        ),
+    ];
+    for (veltrano_type, expected) in cases {
+        assert_eq!(
+            veltrano_type.to_rust_type(&mut registry).to_rust_syntax(),
+            expected
+        );
+        assert!(!veltrano_type.implements_copy(&mut registry));
+    }
+}
+
+#[test]
+fn test_shared_type_construction() {
+    assert_transpilation_match(
+        r#"fun main() {
+    val shared: Own<Rc<I64>> = Rc(5)
+    val other: Own<Rc<I64>> = shared.ref().clone()
+    val counter: Own<Rc<Own<RefCell<I64>>>> = Rc(RefCell(0))
+    val flag = Cell(true)
+    val name = Arc("Ada".toString())
+    println("{} {} {:?} {:?} {}", shared, other, counter, flag, name)
+}"#,
+        r#"fn main() {
+    let shared: std::rc::Rc<i64> = std::rc::Rc::new(5);
+    let other: std::rc::Rc<i64> = Clone::clone(&shared);
+    let counter: std::rc::Rc<std::cell::RefCell<i64>> = std::rc::Rc::new(std::cell::RefCell::new(0));
+    let flag = std::cell::Cell::new(true);
+    let name = std::sync::Arc::new(ToString::to_string("Ada"));
+    println!("{} {} {:?} {:?} {}", shared, other, counter, flag, name);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_shared_types_compile() {
+    transpile_and_compile(
+        r#"fun describe(count: Rc<I64>, name: Arc<Own<String>>): I64 {
+    println("{} {}", count, name)
+    return 1
+}
+
+data class Node(val value: Own<Rc<I64>>)
+
+fun main() {
+    val count = Rc(2)
+    val name = Arc("Ada".toString())
+    val node = Node(value = count.ref().clone())
+    println("{}", describe(count.ref(), name.ref()))
+    println("{:?}", node)
+}"#,
+        &TestContext::default().with_name("shared_types_compile"),
+    )
+    .expect("shared types should compile");
+}
+
+#[test]
+fn test_shared_types_are_naturally_referenced() {
+    assert_type_check_error(
+        "fun main() {\n    val shared: Rc<I64> = Rc(5)\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+}
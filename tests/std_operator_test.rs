@@ -0,0 +1,99 @@
+//! Tests for binary operators resolved through the std trait impls of Rust types
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_string_concatenation_borrows_right_operand() {
+    assert_transpilation_match(
+        r#"fun greet(name: Own<String>, suffix: Str): Own<String> {
+    return name + suffix
+}
+
+fun join(a: Own<String>, b: Own<String>): Own<String> {
+    return a + b
+}
+
+fun append(a: Own<String>, b: String): Own<String> {
+    return a + b
+}"#,
+        r#"fn greet<'a>(bump: &'a bumpalo::Bump, name: String, suffix: &'a str) -> String {
+    return name + suffix;
+}
+fn join<'a>(bump: &'a bumpalo::Bump, a: String, b: String) -> String {
+    return a + &b;
+}
+fn append<'a>(bump: &'a bumpalo::Bump, a: String, b: &'a String) -> String {
+    return a + b;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_comparisons_across_reference_levels() {
+    assert_transpilation_match(
+        r#"fun same(a: String, b: Own<String>): Bool {
+    return a == b
+}
+
+fun sameText(a: Own<String>, b: Str): Bool {
+    return a == b
+}
+
+fun atMost(a: Ref<I64>, b: I64): Bool {
+    return a <= b
+}"#,
+        r#"fn same<'a>(bump: &'a bumpalo::Bump, a: &'a String, b: String) -> bool {
+    return a == &b;
+}
+fn same_text<'a>(bump: &'a bumpalo::Bump, a: String, b: &'a str) -> bool {
+    return a == b;
+}
+fn at_most(a: &i64, b: i64) -> bool {
+    return a <= &b;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_std_operators_compile() {
+    transpile_and_compile(
+        r#"fun join(a: Own<String>, b: Own<String>, c: Str): Own<String> {
+    return a + b + c
+}
+
+fun main() {
+    val first = "Hello, ".toString()
+    val second = "World".toString()
+    val joined = join(first, second, "!")
+    val name = "Ada".toString()
+    println("{} {} {}", joined, joined.ref() == name, name == "Ada")
+}"#,
+        &TestContext::default().with_name("std_operators_compile"),
+    )
+    .expect("operators on std types should compile");
+}
+
+#[test]
+fn test_std_operator_errors() {
+    // A borrowed String cannot be the left operand of String + &str
+    assert_type_check_error(
+        r#"fun greet(name: String, suffix: Str): Own<String> {
+    return name + suffix
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+
+    // String only orders against String
+    assert_type_check_error(
+        r#"fun before(a: Own<String>, b: Str): Bool {
+    return a < b
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+}
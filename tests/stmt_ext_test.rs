@@ -126,6 +126,7 @@ fn test_walk_expressions_in_statements() {
                 operator: BinaryOp::Add,
                 comment_after_operator: None,
                 right: Box::new(loc(Expr::Identifier("b".to_string()))),
+                id: 0,
             }))),
         }),
         Stmt::If(IfStmt {
@@ -197,7 +198,10 @@ fn test_nested_function_traversal() {
             }),
             Stmt::Return(Some(loc(Expr::Identifier("local".to_string())))),
         ])),
-        has_hidden_bump: false,
+        annotations: vec![],
+        lifetime_params: vec![],
+        is_async: false,
+        location: SourceLocation::new(1, 1),
     });
 
     // Count different statement types
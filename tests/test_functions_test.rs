@@ -0,0 +1,132 @@
+//! Tests for `@Test` functions, `assertEquals` and the test runner
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, parse_veltrano_code, transpile,
+    TestContext,
+};
+use veltrano::config::Config;
+use veltrano::test_runner;
+
+#[test]
+fn test_test_functions_generate_test_module() {
+    assert_transpilation_match(
+        r#"fun echo(name: Str): Str {
+    return name
+}
+
+@Test
+fun echoesNames() {
+    assertEquals("hi", echo("hi"))
+}
+
+fun add(a: I64, b: I64): I64 {
+    return a + b
+}
+
+@Test
+fun addsNumbers() {
+    assertEquals(3, add(1, 2), "1 + 2 was not {}", 3)
+    assert(add(2, 2) == 4)
+}"#,
+        r#"fn echo<'a>(bump: &'a bumpalo::Bump, name: &'a str) -> &'a str {
+    return name;
+}
+fn add(a: i64, b: i64) -> i64 {
+    return a + b;
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echoes_names() {
+        let bump = &bumpalo::Bump::new();
+        assert_eq!("hi", echo(bump, "hi"));
+    }
+
+    #[test]
+    fn adds_numbers() {
+        assert_eq!(3, add(1, 2), "1 + 2 was not {}", 3);
+        assert!(add(2, 2) == 4);
+    }
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_test_function_errors() {
+    assert_type_check_error(
+        "@Test\nfun withParam(x: I64) {\n}",
+        &TestContext::default().expect_error("test functions cannot take parameters"),
+    );
+    assert_type_check_error(
+        "@Test\nfun returnsValue(): I64 {\n    return 1\n}",
+        &TestContext::default().expect_error("test functions cannot return a value"),
+    );
+    assert_type_check_error(
+        "fun main() {\n    @Test\n    fun inner() {\n    }\n}",
+        &TestContext::default().expect_error("must be declared at the top level"),
+    );
+    assert_type_check_error(
+        "@Test(\"slow\")\nfun slow() {\n}",
+        &TestContext::default().expect_error("Invalid annotation @Test: expected no arguments"),
+    );
+    assert_type_check_error(
+        "@Test\nfun mismatched() {\n    assertEquals(1, \"one\")\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+    assert_type_check_error(
+        "@Test\nfun missingActual() {\n    assertEquals(1)\n}",
+        &TestContext::default().expect_error("expected the expected and actual values"),
+    );
+}
+
+#[test]
+fn test_runner_reports_veltrano_names() {
+    let code = r#"fun double(x: I64): I64 {
+    return x * 2
+}
+
+@Test
+fun doublesTwo() {
+    assertEquals(4, double(2))
+}
+
+@Test
+fun doublesThreeWrongly() {
+    assertEquals(7, double(3))
+}"#;
+    let ctx = TestContext::default();
+    let program = parse_veltrano_code(code, Config::default()).expect("valid program");
+    let rust_code = transpile(code, &ctx).expect("transpiles");
+
+    let report = test_runner::run_tests(&program, &rust_code, "runner_reports_names")
+        .expect("generated tests should build");
+    assert!(!report.success);
+    assert!(report.output.contains("test doublesTwo ... ok"));
+    assert!(report
+        .output
+        .contains("test doublesThreeWrongly ... FAILED"));
+    assert!(!report.output.contains("doubles_two"));
+}
+
+#[test]
+fn test_rename_tests_matches_whole_paths() {
+    let program = parse_veltrano_code(
+        "@Test\nfun add() {\n}\n\n@Test\nfun addOne() {\n}",
+        Config::default(),
+    )
+    .expect("valid program");
+    let names = test_runner::test_names(&program);
+
+    assert_eq!(
+        test_runner::rename_tests(
+            "test tests::add ... ok\nat veltrano_tests::tests::add_one\n    tests::other\n",
+            &names
+        ),
+        "test add ... ok\nat veltrano_tests::addOne\n    tests::other\n"
+    );
+}
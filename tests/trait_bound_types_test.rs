@@ -0,0 +1,146 @@
+//! Tests for trait object (`Dyn<Trait>`) and `Impl<Trait>` types
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+use veltrano::rust_interop::RustInteropRegistry;
+use veltrano::types::VeltranoType;
+
+#[test]
+fn test_trait_bound_types_map_to_rust() {
+    let mut registry = RustInteropRegistry::new();
+    let display = || VeltranoType::dyn_trait("Display", vec![]);
+    let cases = [
+        (display(), "&dyn std::fmt::Display"),
+        (
+            VeltranoType::own(VeltranoType::boxed(display())),
+            "Box<dyn std::fmt::Display>",
+        ),
+        (
+            VeltranoType::own(VeltranoType::rc(display())),
+            "std::rc::Rc<dyn std::fmt::Display>",
+        ),
+        (
+            VeltranoType::impl_trait("Iterator", vec![VeltranoType::i64()]),
+            "impl Iterator<Item = i64>",
+        ),
+    ];
+    for (veltrano_type, expected) in cases {
+        let rust_type = veltrano_type.to_rust_type(&mut registry);
+        assert_eq!(rust_type.to_rust_syntax(), expected);
+        assert!(!veltrano_type.implements_copy(&mut registry));
+    }
+}
+
+#[test]
+fn test_trait_objects_accept_implementing_types() {
+    assert_transpilation_match(
+        r#"fun show(item: Dyn<Display>) {
+    println("{}", item.toString())
+}
+
+fun main() {
+    val name = "Ada".toString()
+    val count: I64 = 3
+    show(name.ref())
+    show(count.ref())
+    val items: Own<Vec<Own<Box<Dyn<Display>>>>> = vecOf(Box(1), Box("two".toString()))
+    println("{}", items.ref().length())
+}"#,
+        r#"fn show<'a>(bump: &'a bumpalo::Bump, item: &'a dyn std::fmt::Display) {
+    println!("{}", ToString::to_string(item));
+}
+fn main() {
+    let bump = &bumpalo::Bump::new();
+    let name = ToString::to_string("Ada");
+    let count: i64 = 3;
+    show(bump, &name);
+    show(bump, &count);
+    let items: Vec<Box<dyn std::fmt::Display>> = vec![Box::new(1), Box::new(ToString::to_string("two"))];
+    println!("{}", Vec::len(&items));
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_impl_trait_methods_resolve_through_the_trait() {
+    assert_transpilation_match(
+        r#"fun firstOf(values: MutRef<Impl<Iterator<I64>>>): Option<I64> {
+    return values.next()
+}"#,
+        r#"fn first_of(values: &mut impl Iterator<Item = i64>) -> Option<i64> {
+    return Iterator::next(values);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_trait_bound_types_compile() {
+    transpile_and_compile(
+        r#"fun show(item: Dyn<Display>) {
+    println("[{}]", item)
+}
+
+fun counter(limit: I64): Impl<Iterator<I64>> {
+    return rust<Impl<Iterator<I64>>>("1..=limit")
+}
+
+fun firstOf(values: MutRef<Impl<Iterator<I64>>>): Option<I64> {
+    return values.next()
+}
+
+fun main() {
+    val label: Dyn<Display> = "label".ref()
+    show(label)
+    val boxed: Own<Box<Dyn<Display>>> = Box(true)
+    show(boxed.ref())
+    println("{:?} {}", firstOf(counter(3).mutRef()), counter(5).count())
+}"#,
+        &TestContext::default().with_name("trait_bound_types_compile"),
+    )
+    .expect("trait bound types should compile");
+}
+
+#[test]
+fn test_trait_object_rejects_non_implementing_type() {
+    assert_type_check_error(
+        "data class Point(val x: I64)\n\nfun show(item: Dyn<Display>) {\n}\n\nfun main() {\n    show(Point(x = 1))\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+}
+
+#[test]
+fn test_trait_object_cannot_be_owned_directly() {
+    assert_type_check_error(
+        "fun main() {\n    val item: Own<Dyn<Display>> = \"a\".toString()\n}",
+        &TestContext::default().expect_error("Own<Box<Dyn<Display>>>"),
+    );
+}
+
+#[test]
+fn test_impl_trait_not_allowed_in_val() {
+    assert_type_check_error(
+        "fun counter(): Impl<Iterator<I64>> {\n    return rust<Impl<Iterator<I64>>>(\"0..3\")\n}\n\nfun main() {\n    val numbers: Impl<Iterator<I64>> = counter()\n}",
+        &TestContext::default().expect_error("only allowed in function parameter and return types"),
+    );
+}
+
+#[test]
+fn test_trait_bound_requires_known_trait() {
+    assert_type_check_error(
+        "fun show(item: Dyn<Frobnicate>) {\n}",
+        &TestContext::default().expect_error("Unknown trait 'Frobnicate'"),
+    );
+}
+
+#[test]
+fn test_trait_object_rejects_unsized_pointee() {
+    assert_type_check_error(
+        "fun main() {\n    val label: Dyn<Display> = \"label\"\n}",
+        &TestContext::default().expect_error("type mismatch"),
+    );
+}
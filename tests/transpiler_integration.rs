@@ -116,8 +116,8 @@ fn test_readme_veltrano_snippets_transpile_and_compile() {
 
     // Check expected count of standalone Veltrano code blocks
     assert_eq!(
-        veltrano_examples.len(), 12,
-        "Expected 12 Veltrano code examples in README, found {}. If you added/removed examples, update this count.",
+        veltrano_examples.len(), 14,
+        "Expected 14 Veltrano code examples in README, found {}. If you added/removed examples, update this count.",
         veltrano_examples.len()
     );
 
@@ -1040,8 +1040,8 @@ data class Point(val x: I64, val y: I64)
     let rust_code = transpile(source1, &TestContext::with_config(config.clone()))
         .expect("Transpilation should succeed");
 
-    // Check struct generation without lifetime
-    assert!(rust_code.contains("#[derive(Debug, Clone)]"));
+    // Check struct generation without lifetime; all-value classes are Copy
+    assert!(rust_code.contains("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]"));
     assert!(rust_code.contains("pub struct Point {"));
     assert!(rust_code.contains("pub x: i64,"));
     assert!(rust_code.contains("pub y: i64,"));
@@ -1059,7 +1059,7 @@ data class Person(val name: String, val age: I64)
         .expect("Transpilation should succeed");
 
     // Check struct generation with lifetime
-    assert!(rust_code2.contains("#[derive(Debug, Clone)]"));
+    assert!(rust_code2.contains("#[derive(Debug, Clone, PartialEq, Eq, Hash)]"));
     assert!(rust_code2.contains("pub struct Person<'a> {"));
     assert!(rust_code2.contains("pub name: &'a String,"));
     assert!(rust_code2.contains("pub age: i64,"));
@@ -1072,7 +1072,8 @@ data class Container(val item: MyType, val count: I64)
     let rust_code3 = transpile(source3, &TestContext::with_config(config))
         .expect("Transpilation should succeed");
 
-    // Check struct generation with custom type (needs lifetime)
+    // Check struct generation with custom type (needs lifetime, only cloned by reference)
+    assert!(rust_code3.contains("#[derive(Clone)]"));
     assert!(rust_code3.contains("pub struct Container<'a> {"));
     assert!(rust_code3.contains("pub item: &'a MyType,"));
 }
@@ -1608,3 +1609,37 @@ fun testNoFallback() {
 
     assert!(rust_code.contains("String::len(text)"));
 }
+
+#[test]
+fn test_codegen_without_type_checker_results_infers_bumps() {
+    // Code generation on its own infers derives and hidden bump parameters
+    assert_transpilation_match(
+        r#"data class Point(val x: I64)
+
+fun label(name: Str): Str {
+    return name
+}
+
+fun greet() {
+    println("{}", label("hi"))
+}"#,
+        r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point(x={})", self.x)
+    }
+}
+
+fn label<'a>(bump: &'a bumpalo::Bump, name: &'a str) -> &'a str {
+    return name;
+}
+fn greet<'a>(bump: &'a bumpalo::Bump) {
+    println!("{}", label(bump, "hi"));
+}"#,
+        &TestContext::default().skip_type_check(true),
+    );
+}
@@ -0,0 +1,191 @@
+//! Tests for tuple types, Pair/Triple and destructuring declarations
+
+mod common;
+
+use common::{
+    assert_parse_error, assert_transpilation_match, assert_type_check_error, transpile_and_compile,
+    TestContext,
+};
+
+#[test]
+fn test_tuple_literal_and_positional_access() {
+    assert_transpilation_match(
+        r#"fun main() {
+    val pair = (1, "one")
+    val a = pair.first
+    val b = pair.1
+}"#,
+        r#"fn main() {
+    let pair = (1, "one");
+    let a = pair.0;
+    let b = pair.1;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_pair_and_triple_types_lower_to_rust_tuples() {
+    assert_transpilation_match(
+        r#"fun swap(pair: Pair<I64, Bool>): (Bool, I64) {
+    return Pair(pair.second, pair.first)
+}
+
+fun main() {
+    val triple = Triple(1, true, 3)
+    val c = triple.third
+}"#,
+        r#"fn swap(pair: (i64, bool)) -> (bool, i64) {
+    return (pair.1, pair.0);
+}
+fn main() {
+    let triple = (1, true, 3);
+    let c = triple.2;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_tuple_destructuring() {
+    assert_transpilation_match(
+        r#"fun main() {
+    val (a, _, c) = Triple(1, true, 3)
+    val (first) = (a, c)
+}"#,
+        r#"fn main() {
+    let (a, _, c) = (1, true, 3);
+    let (first, ..) = (a, c);
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_data_class_destructuring_per_component_binding() {
+    // Through a reference: Copy components are copied, owned ones are borrowed
+    assert_transpilation_match(
+        r#"data class Person(val name: Str, val age: I64, val nickname: Own<String>)
+
+fun describe(person: Person): I64 {
+    val (name, years, nickname) = person
+    return years
+}
+
+fun main() {
+    val p = Person(name = "Alice", age = 30, nickname = "Al".toString())
+    val (n) = p
+}"#,
+        r#"#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub age: i64,
+    pub nickname: String,
+}
+
+impl<'a> std::fmt::Display for Person<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Person(name={}, age={}, nickname={})", self.name, self.age, self.nickname)
+    }
+}
+
+fn describe<'a>(bump: &'a bumpalo::Bump, person: &'a Person<'a>) -> i64 {
+    let &Person { name, age: years, ref nickname } = person;
+    return years;
+}
+fn main() {
+    let p = Person { name: "Alice", age: 30, nickname: ToString::to_string("Al") };
+    let Person { name: n, .. } = p;
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_destructured_components_have_element_types() {
+    // Borrowed Own<String> component is typed as String, not Own<String>
+    assert_type_check_error(
+        r#"data class Tag(val label: Own<String>)
+
+fun f(tag: Tag) {
+    val (label) = tag
+    val owned: Own<String> = label
+}"#,
+        &TestContext::default().expect_error("Type mismatch"),
+    );
+}
+
+#[test]
+fn test_tuples_compile() {
+    transpile_and_compile(
+        r#"data class Person(val name: Str, val age: I64, val nickname: Own<String>)
+
+fun swap(pair: (I64, Str)): Pair<Str, I64> {
+    return Pair(pair.second, pair.first)
+}
+
+fun describe(person: Person): I64 {
+    val (name, age, nickname) = person
+    println("{} {} {}", name, age, nickname)
+    return age
+}
+
+fun main() {
+    val (label, number) = swap((1, "one"))
+    val alice = Person(name = label, age = number, nickname = "Al".toString())
+    val age = describe(alice.ref())
+    val (name, _, nickname) = alice
+    println("{} {} {}", name, age, nickname)
+}"#,
+        &TestContext::default().with_name("tuples_compile"),
+    )
+    .expect("tuple code should compile");
+}
+
+#[test]
+fn test_destructuring_errors() {
+    assert_type_check_error(
+        r#"fun main() {
+    val (a, b, c) = (1, 2)
+}"#,
+        &TestContext::default().expect_error("declares 3 variables but the value has only 2"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val (a, b) = 42
+}"#,
+        &TestContext::default().expect_error("only tuples and data classes have components"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val pair = (1, 2)
+    val c = pair.third
+}"#,
+        &TestContext::default().expect_error("Field 'third' not found"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val pair = Pair(1)
+}"#,
+        &TestContext::default().expect_error("Function 'Pair' expects 2 arguments"),
+    );
+}
+
+#[test]
+fn test_tuple_type_errors() {
+    assert_parse_error(
+        r#"fun f(x: (I64)) {
+}"#,
+        &TestContext::default().expect_error("Tuple types need at least two elements"),
+    );
+
+    assert_type_check_error(
+        r#"fun main() {
+    val x: Own<(I64, Str)> = (1, "a")
+}"#,
+        &TestContext::default().expect_error("Tuples are always owned"),
+    );
+}
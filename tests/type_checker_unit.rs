@@ -215,3 +215,67 @@ fn test_shorthand_argument_field_not_found() {
         );
     }
 }
+
+#[test]
+fn test_hidden_bump_analysis() {
+    // References in a signature call for a bump, and so do calls to such functions,
+    // but Copy data classes are passed by value
+    let code = r#"
+    data class Point(val x: I64, val y: I64)
+
+    fun label(name: Str): Str {
+        return name
+    }
+
+    fun greet() {
+        println("{}", label("hi"))
+    }
+
+    fun moved(p: Point): Point {
+        return Point(x = p.x + 1, y = p.y)
+    }
+    "#;
+
+    let program = common::parse_veltrano_code(code, Config::default()).unwrap();
+    let mut type_checker = VeltranoTypeChecker::new();
+    assert!(type_checker.check_program(&program).is_ok());
+
+    let hidden_bump_functions = type_checker.get_hidden_bump_functions();
+    assert!(hidden_bump_functions.contains("label"));
+    assert!(hidden_bump_functions.contains("greet"));
+    assert!(!hidden_bump_functions.contains("moved"));
+}
+
+#[test]
+fn test_bump_allocation_analysis() {
+    // Returning a collection of references calls for a bump, and so does every
+    // function that reaches it through calls
+    let code = r#"
+    fun outer(): Vec<String> {
+        val v: Own<Vec<String>> = vecOf("test".toString().bumpRef())
+        return v.bumpRef()
+    }
+
+    fun inner() {
+        val v = outer()
+    }
+
+    fun caller() {
+        inner()
+    }
+
+    fun unrelated(): I64 {
+        return 1
+    }
+    "#;
+
+    let program = common::parse_veltrano_code(code, Config::default()).unwrap();
+    let mut type_checker = VeltranoTypeChecker::new();
+    assert!(type_checker.check_program(&program).is_ok());
+
+    let hidden_bump_functions = type_checker.get_hidden_bump_functions();
+    assert!(hidden_bump_functions.contains("outer"));
+    assert!(hidden_bump_functions.contains("inner"));
+    assert!(hidden_bump_functions.contains("caller"));
+    assert!(!hidden_bump_functions.contains("unrelated"));
+}
@@ -0,0 +1,94 @@
+//! Tests for `unsafe { }` blocks and calls to unsafe imported Rust items
+
+mod common;
+
+use common::{
+    assert_transpilation_match, assert_type_check_error, transpile_and_compile, TestContext,
+};
+
+#[test]
+fn test_unsafe_block_generation() {
+    assert_transpilation_match(
+        r#"import i64.uncheckedAdd
+
+fun sum(a: I64, b: I64): I64 {
+    unsafe {
+        return a.uncheckedAdd(b)
+    }
+}"#,
+        r#"fn sum(a: i64, b: i64) -> i64 {
+    unsafe {
+        return i64::unchecked_add(a, b);
+    }
+}"#,
+        &TestContext::default(),
+    );
+}
+
+#[test]
+fn test_unsafe_block_compiles() {
+    transpile_and_compile(
+        r#"import i64.uncheckedAdd
+
+fun sum(a: I64, b: I64): I64 {
+    unsafe {
+        return a.uncheckedAdd(b)
+    }
+}
+
+fun main() {
+    val x: I64 = 1
+    unsafe {
+        val y = x.uncheckedAdd(2)
+        println("{} {}", y, sum(y, 3))
+    }
+}"#,
+        &TestContext::default().with_name("unsafe_block_compiles"),
+    )
+    .expect("calls inside unsafe blocks should compile");
+}
+
+#[test]
+fn test_unsafe_call_outside_unsafe_block() {
+    assert_type_check_error(
+        r#"import i64.uncheckedAdd
+
+fun main() {
+    val x: I64 = 1
+    val y = x.uncheckedAdd(2)
+}"#,
+        &TestContext::default()
+            .expect_error("Call to unsafe function 'uncheckedAdd' outside an unsafe block"),
+    );
+
+    // Nested functions do not inherit the enclosing unsafe block
+    assert_type_check_error(
+        r#"import i64.uncheckedAdd
+
+fun main() {
+    unsafe {
+        fun inner(x: I64): I64 {
+            return x.uncheckedAdd(1)
+        }
+    }
+}"#,
+        &TestContext::default().expect_error("outside an unsafe block"),
+    );
+}
+
+#[test]
+fn test_safe_imports_need_no_unsafe_block() {
+    assert_transpilation_match(
+        r#"import i64.abs
+
+fun main() {
+    val x: I64 = -1
+    val y = x.abs()
+}"#,
+        r#"fn main() {
+    let x: i64 = -1;
+    let y = i64::abs(x);
+}"#,
+        &TestContext::default(),
+    );
+}